@@ -1,7 +1,18 @@
 use clap::Parser;
+use phoenix::state::Side;
+use phoenix_cli_processor::helpers::archive_helpers::Compression;
+use phoenix_cli_processor::processor::process_get_all_markets::MarketSortKey;
+use phoenix_cli_processor::processor::process_get_all_seats::SeatStatusFilter;
+use phoenix_cli_processor::processor::process_get_taker_leaderboard::TakerSortKey;
+use phoenix_cli_processor::processor::process_get_traders_for_market::TraderSortKey;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 
+use crate::parse::{
+    parse_amount, parse_atoms, parse_duration, parse_duration_ms, parse_side, parse_timestamp,
+    parse_window,
+};
+
 // #[clap(author, version, about)]
 #[derive(Debug, Clone, Parser)]
 pub enum PhoenixCLICommand {
@@ -11,39 +22,221 @@ pub enum PhoenixCLICommand {
         /// Highly recommended to use this flag as GetProgramAccounts is an expensive call.
         #[clap(short, long, required = false)]
         no_gpa: bool,
+        /// Only list markets with this base token, given as a mint or a symbol such as SOL
+        #[clap(long, required = false)]
+        base: Option<String>,
+        /// Only list markets with this quote token, given as a mint or a symbol such as USDC
+        #[clap(long, required = false)]
+        quote: Option<String>,
+        /// Also fetch each market's top of book and TVL (vault balances valued at the midpoint), with totals per quote token
+        #[clap(long, required = false)]
+        stats: bool,
+        /// Sort markets by TVL or 24 hour volume, largest first, or by spread, tightest first. Implies --stats.
+        /// Sorting by volume scans each market's last day of transactions
+        #[clap(long, value_enum, required = false)]
+        sort_by: Option<MarketSortKey>,
     },
     /// Get detailed information on a specific market
-    GetMarket { market_pubkey: Pubkey },
+    GetMarket {
+        market_pubkey: Pubkey,
+        /// Write the market's static parameters to this TOML file instead of printing its details
+        #[clap(long, required = false)]
+        export_toml: Option<String>,
+    },
     /// Get active traders for a given market
-    GetTradersForMarket { market_pubkey: Pubkey },
+    GetTradersForMarket {
+        market_pubkey: Pubkey,
+        /// Sort traders by pubkey or by a balance, largest first. Sorting happens before --offset and --limit
+        #[clap(long, value_enum, required = false)]
+        sort_by: Option<TraderSortKey>,
+        /// Skip this many traders
+        #[clap(long, required = false, default_value = "0")]
+        offset: usize,
+        /// Show at most this many traders
+        #[clap(long, required = false)]
+        limit: Option<usize>,
+    },
     /// Get the best bid and ask price for a given market
-    GetTopOfBook { market_pubkey: Pubkey },
+    GetTopOfBook {
+        market_pubkey: Pubkey,
+        /// Print each level through a handlebars template instead of the default layout, e.g. '{{side}} {{price}} {{size}}'
+        #[clap(short, long, required = false)]
+        format: Option<String>,
+        /// Number of decimals to print prices with. Derived from the tick size by default
+        #[clap(long, required = false)]
+        precision: Option<usize>,
+    },
+    /// Get the best bid, best ask, midpoint and spread for a given market with a single RPC call
+    GetBbo { market_pubkey: Pubkey },
+    /// Poll the top of book several times and report the minimum, median and maximum spread in basis points
+    GetSpread {
+        market_pubkey: Pubkey,
+        /// Number of times to sample the top of book. Defaults to 10
+        #[clap(short = 'n', long, required = false, default_value = "10")]
+        samples: u64,
+        /// Time between samples, e.g. 1s or 5s. Defaults to 1s
+        #[clap(short, long, required = false, default_value = "1s", value_parser = parse_duration)]
+        interval: u64,
+    },
+    /// Get the base size and quote notional resting within several basis point bands of the midpoint on each side
+    GetLiquidity {
+        market_pubkey: Pubkey,
+        /// Comma separated list of bands around the midpoint, in basis points
+        #[clap(
+            short,
+            long,
+            required = false,
+            value_delimiter = ',',
+            default_value = "10,50,100"
+        )]
+        bps: Vec<u64>,
+    },
+    /// Look up an order by its sequence number: its side, price, maker and remaining size if it is resting,
+    /// and its original size and whether it was filled or cancelled from the market's recent events
+    GetOrder {
+        market_pubkey: Pubkey,
+        order_sequence_number: u64,
+        /// Scan the market's events over this window, e.g. 1h, for the order's placement and outcome
+        #[clap(short, long, required = false, value_parser = parse_duration)]
+        window: Option<u64>,
+    },
+    /// Reconstruct a market's book as of a past slot, by undoing the market's events since then from
+    /// its current state. Requires the RPC node to serve transaction history back to that slot
+    GetBookAtSlot {
+        market_pubkey: Pubkey,
+        slot: u64,
+        /// Number of decimals to print prices with. Derived from the tick size by default
+        #[clap(long, required = false)]
+        precision: Option<usize>,
+    },
+    /// Compare two markets' books side by side in quote units per base unit and base units: top of book,
+    /// spread, and depth near the midpoint, with which market is tighter and deeper
+    CompareBooks { market_a: Pubkey, market_b: Pubkey },
     /// Get the first N levels of the order book for a given market.
     /// Default is 10 levels
     GetBookLevels {
         market_pubkey: Pubkey,
-        #[clap(short, long, required = false, default_value = "10")]
+        #[clap(short, long, alias = "depth", required = false, default_value = "10")]
         levels: u64,
+        /// Print each level through a handlebars template instead of the default layout, e.g. '{{side}} {{price}} {{size}} {{trader_present}}'
+        #[clap(short, long, required = false)]
+        format: Option<String>,
+        /// Number of decimals to print prices with. Derived from the tick size by default
+        #[clap(long, required = false)]
+        precision: Option<usize>,
     },
     /// Get the full order book for a given market
-    GetFullBook { market_pubkey: Pubkey },
+    GetFullBook {
+        market_pubkey: Pubkey,
+        /// Only show this many levels per side
+        #[clap(short, long, required = false)]
+        depth: Option<u64>,
+        /// Aggregate levels into buckets this many ticks wide
+        #[clap(long, required = false, conflicts_with = "group_bps")]
+        group_ticks: Option<u64>,
+        /// Aggregate levels into buckets this many basis points of the midpoint wide
+        #[clap(long, required = false)]
+        group_bps: Option<u64>,
+        /// Render cumulative depth as a horizontal bar chart scaled to the terminal width
+        #[clap(long, required = false, conflicts_with = "format")]
+        chart: bool,
+        /// Show cumulative base size and quote notional at each level
+        #[clap(long, required = false, conflicts_with = "chart")]
+        cumulative: bool,
+        /// List individual orders with their maker and sequence number instead of aggregated levels
+        #[clap(long, required = false, conflicts_with_all = ["chart", "cumulative", "group_ticks", "group_bps", "format"])]
+        orders: bool,
+        /// With --orders, only list this trader's orders
+        #[clap(long, required = false, requires = "orders")]
+        trader: Option<Pubkey>,
+        /// With --orders, only list orders at or above this price
+        #[clap(long, required = false, requires = "orders")]
+        min_price: Option<f64>,
+        /// With --orders, only list orders at or below this price
+        #[clap(long, required = false, requires = "orders")]
+        max_price: Option<f64>,
+        /// Print each level through a handlebars template instead of the default layout, e.g. '{{side}} {{price}} {{size}}'
+        #[clap(short, long, required = false)]
+        format: Option<String>,
+        /// Number of decimals to print prices with. Derived from the tick size by default
+        #[clap(long, required = false)]
+        precision: Option<usize>,
+    },
+    /// Decode every Phoenix instruction in a transaction, with its arguments in human units, and the market events it emitted
+    InspectTx { signature: Signature },
     /// Get the market events that occured in a given transaction signature
-    GetTransaction { signature: Signature },
-    /// Get the current status of a market
+    GetTransaction {
+        signature: Signature,
+        /// Only show events belonging to the order with this client order id
+        #[clap(long, required = false)]
+        client_order_id: Option<u128>,
+        /// Print each event through a handlebars template instead of the default layout, e.g. '{{event_type}} {{price}} {{quantity}} {{side}}'
+        #[clap(short, long, required = false, conflicts_with_all = ["pretty", "compact"])]
+        format: Option<String>,
+        /// Print each event over multiple lines, one field per line
+        #[clap(long, required = false, conflicts_with = "compact")]
+        pretty: bool,
+        /// Print events as fixed-width columns with truncated pubkeys
+        #[clap(long, required = false)]
+        compact: bool,
+    },
+    /// Get every Phoenix event emitted by a transaction in the structured log format, including evictions
+    /// and expiries
+    GetEvents {
+        signature: Signature,
+        /// Only show events on this market
+        #[clap(short, long, required = false)]
+        market: Option<Pubkey>,
+    },
+    /// Dump every event on a market within a slot or signature range, oldest first, reporting progress to
+    /// stderr as the market's history is paged and parsed
+    GetEventsRange {
+        market_pubkey: Pubkey,
+        /// Oldest slot to include
+        #[clap(long, required_unless_present = "until")]
+        from_slot: Option<u64>,
+        /// Newest slot to include. Defaults to the latest
+        #[clap(long, required = false)]
+        to_slot: Option<u64>,
+        /// Start from the transaction before this signature (exclusive)
+        #[clap(long, required = false)]
+        before: Option<Signature>,
+        /// Stop at this signature (exclusive)
+        #[clap(long, required = false)]
+        until: Option<Signature>,
+        /// Save paging progress to this file so an interrupted run resumes where it stopped. The
+        /// file is deleted once the run completes
+        #[clap(long, required = false)]
+        checkpoint_file: Option<String>,
+    },
+    /// Get a market's taker fee, fee recipient, and collected, uncollected and cumulative quote fees
+    GetFees { market_pubkey: Pubkey },
+    /// Get the current status of a market, its sequence number and the slot it was read at
     GetMarketStatus { market_pubkey: Pubkey },
-    /// Get the status and address of a seat for a given market and trader
+    /// Get the address and approval status of a seat for a given market and trader, and any funds the trader has locked
     GetSeatInfo {
         market_pubkey: Pubkey,
         /// Pubkey of the trader associated with the seat. Defaults to the current payer
         #[clap(short, long, required = false)]
         trader_pubkey: Option<Pubkey>,
     },
+    /// List the seats on a given market with their traders and approval status
+    GetAllSeats {
+        market_pubkey: Pubkey,
+        /// Only list seats with this approval status
+        #[clap(long, value_enum, required = false)]
+        status: Option<SeatStatusFilter>,
+    },
     /// Get all open orders on a given market for a trader
     GetOpenOrders {
-        market_pubkey: Pubkey,
+        #[clap(required_unless_present = "all_markets")]
+        market_pubkey: Option<Pubkey>,
         /// Pubkey of the trader for whom to get open orders. Defaults to the current payer
         #[clap(short, long, required = false)]
         trader_pubkey: Option<Pubkey>,
+        /// Get open orders on every market the trader has a seat on
+        #[clap(long, required = false, conflicts_with = "market_pubkey")]
+        all_markets: bool,
     },
     /// Send a transaction on chain to allocate a seat for the payer on the given market. This will cost ~.0018 SOL for rent.
     /// Note that the seat will have to then be approved by the market authority. Only relevant for permissioned markets.
@@ -57,7 +250,7 @@ pub enum PhoenixCLICommand {
         /// Pubkey of the recipient of the tokens
         recipient_pubkey: Pubkey,
         /// Amount in atoms (1 * 10*(-decimals))
-        #[clap(short, long, required = false, default_value = "100000000000")]
+        #[clap(short, long, required = false, default_value = "100000000000", value_parser = parse_atoms)]
         amount: u64,
     },
     /// Mint both base and quote tokens to a recipient for a given market. Default amounts are 100_000_000_000 for base and 100_000_000 for quote.
@@ -67,10 +260,10 @@ pub enum PhoenixCLICommand {
         /// Pubkey of the recipient of the tokens
         recipient_pubkey: Pubkey,
         /// Amount in atoms (1 * 10*(-decimals))
-        #[clap(short, long, required = false, default_value = "100000000000")]
+        #[clap(short, long, required = false, default_value = "100000000000", value_parser = parse_atoms)]
         base_amount: u64,
         /// Amount in atoms (1 * 10*(-decimals))
-        #[clap(short, long, required = false, default_value = "100000000")]
+        #[clap(short, long, required = false, default_value = "100000000", value_parser = parse_atoms)]
         quote_amount: u64,
     },
     /// For the given market, get the seat manager data fields, including authority, successor, and designated market makers.
@@ -85,7 +278,393 @@ pub enum PhoenixCLICommand {
         market_pubkey: Pubkey,
         trader_to_evict: Option<Pubkey>,
     },
-    /// Retrieves the current uncollected revenue grouped by USDC, USDT, and SOL,
-    /// as well as the total denominated in USDC.
-    GetUncollectedRevenue,
+    /// Retrieves the current uncollected revenue grouped by quote token,
+    /// as well as the total denominated in USD.
+    GetUncollectedRevenue {
+        /// Comma separated list of stablecoins counted 1:1 towards the USD total
+        #[clap(
+            long,
+            required = false,
+            value_delimiter = ',',
+            default_value = "USDC,USDT,PYUSD"
+        )]
+        stablecoins: Vec<String>,
+        /// Value stablecoins at live prices instead of 1:1
+        #[clap(long, required = false)]
+        live_stablecoin_prices: bool,
+    },
+    /// Top up the free balances of several makers on a market from a treasury wallet.
+    /// Prints the funding plan and exits unless `--execute` is passed.
+    FundMakers {
+        market_pubkey: Pubkey,
+        /// Path to a JSON file listing makers, e.g. [{"wallet": "...", "keypairPath": "...", "baseTarget": 10.0, "quoteTarget": 1000.0}].
+        /// The keypair path is optional; without it tokens are only transferred to the maker's wallet.
+        makers_file: String,
+        /// Path to the treasury keypair. Defaults to the current payer
+        #[clap(short, long, required = false)]
+        treasury_keypair_path: Option<String>,
+        /// Send the transfers and deposits instead of only printing the plan
+        #[clap(short, long, required = false)]
+        execute: bool,
+    },
+    /// Find the payer's stale resting orders across all markets in the Phoenix config, optionally cancelling them.
+    /// An order is stale if it is priced too far from the current mid, too many orders have been placed on the market since,
+    /// or it has been resting for too long.
+    GetStaleOrders {
+        /// Only check this market instead of every market in the config
+        #[clap(short, long, required = false)]
+        market_pubkey: Option<Pubkey>,
+        /// Flag orders priced further than this many basis points from the current mid
+        #[clap(short = 'd', long, required = false)]
+        max_distance_bps: Option<u64>,
+        /// Flag orders with more than this many orders placed on the market after them
+        #[clap(short = 'a', long, required = false)]
+        max_age_orders: Option<u64>,
+        /// Flag orders that have been resting longer than this duration, e.g. 30s, 5m, or 1h
+        #[clap(long, required = false, value_parser = parse_duration)]
+        max_order_age: Option<u64>,
+        /// Send transactions cancelling every stale order found
+        #[clap(long, required = false)]
+        cancel: bool,
+    },
+    /// Get place, cancel, and fill counts per maker on a given market over a recent window,
+    /// along with each maker's order-to-trade ratio and cancel rate
+    GetMakerActivity {
+        market_pubkey: Pubkey,
+        /// Size of the window to scan, e.g. 30m or 6h. Defaults to 1h
+        #[clap(short, long, required = false, default_value = "1h", value_parser = parse_duration)]
+        window: u64,
+    },
+    /// Print a market's most recent fills with their price, size and the taker's side, newest first
+    GetRecentTrades {
+        market_pubkey: Pubkey,
+        /// Number of fills to print. Defaults to 20
+        #[clap(short = 'n', long, required = false, default_value = "20")]
+        count: usize,
+        /// How far back to look for fills, e.g. 1h or 7d. Defaults to 1d
+        #[clap(long, required = false, default_value = "1d", value_parser = parse_duration)]
+        lookback: u64,
+        /// Maximum number of transactions to scan. Defaults to 1000
+        #[clap(long, required = false, default_value = "1000")]
+        max_transactions: usize,
+    },
+    /// Aggregate a market's fills from transaction history into OHLCV candles. Supports `--output json` and `--output csv`
+    GetCandles {
+        market_pubkey: Pubkey,
+        /// Width of each candle, e.g. 1m, 5m or 1h
+        #[clap(short, long, required = false, default_value = "1h", value_parser = parse_duration)]
+        interval: u64,
+        /// Start of the range, as a unix timestamp, an RFC 3339 time, or a duration ago such as 7d.
+        /// Defaults to 24 hours before the end
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        from: Option<i64>,
+        /// End of the range, in the same formats as --from. Defaults to now
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        to: Option<i64>,
+    },
+    /// Sum a market's base and quote volume over a recent window from transaction history, split
+    /// by whether the taker bought or sold
+    GetVolume {
+        market_pubkey: Pubkey,
+        /// Size of the window, e.g. 1h or 24h. Defaults to 24 hours
+        #[clap(short, long, required = false, default_value = "24h", value_parser = parse_duration)]
+        window: u64,
+    },
+    /// Rank a market's makers by the volume of their orders filled over a recent window
+    GetMakerLeaderboard {
+        market_pubkey: Pubkey,
+        /// Size of the window, e.g. 24h or 7d. Defaults to 7 days
+        #[clap(short, long, required = false, default_value = "7d", value_parser = parse_duration)]
+        window: u64,
+        /// Number of makers to show
+        #[clap(short = 'n', long, required = false, default_value = "20")]
+        limit: usize,
+    },
+    /// Rank a market's takers by the volume they traded or the quote fees they paid over a recent window
+    GetTakerLeaderboard {
+        market_pubkey: Pubkey,
+        /// Size of the window, e.g. 24h or 7d. Defaults to 7 days
+        #[clap(short, long, required = false, default_value = "7d", value_parser = parse_duration)]
+        window: u64,
+        /// Rank takers by quote volume or by quote fees paid, largest first
+        #[clap(long, value_enum, required = false, default_value = "volume")]
+        sort_by: TakerSortKey,
+        /// Number of takers to show
+        #[clap(short = 'n', long, required = false, default_value = "20")]
+        limit: usize,
+    },
+    /// Get a market's volume-weighted average fill price over a recent window, and optionally the
+    /// average price the current book would give a market order of a given size on each side
+    GetVwap {
+        market_pubkey: Pubkey,
+        /// Size of the window, e.g. 1h or 24h. Defaults to one hour
+        #[clap(short, long, required = false, default_value = "1h", value_parser = parse_duration)]
+        window: u64,
+        /// Also price a market order of this many base units against the book, e.g. 2.5 or 10k
+        #[clap(long, required = false, value_parser = parse_amount)]
+        for_size: Option<f64>,
+    },
+    /// Continuously redraw the top levels of a market's book in place until interrupted
+    WatchBook {
+        market_pubkey: Pubkey,
+        /// Number of levels to show per side
+        #[clap(short, long, required = false, default_value = "10")]
+        depth: u64,
+        /// Time between refreshes, e.g. 500ms or 2s. Defaults to 500ms
+        #[clap(short, long, required = false, default_value = "500ms", value_parser = parse_duration_ms)]
+        interval: u64,
+        /// Number of decimals to print prices with. Derived from the tick size by default
+        #[clap(long, required = false)]
+        precision: Option<usize>,
+    },
+    /// Subscribe to a market's transactions and print its fills as they happen, buys and sells color coded.
+    /// Supports `--output json` and `--output jsonl`, which print one JSON object per fill
+    WatchTrades {
+        market_pubkey: Pubkey,
+        /// Websocket endpoint to subscribe through. Derived from the RPC url by default
+        #[clap(long, required = false)]
+        ws_url: Option<String>,
+    },
+    /// Get a market's base and quote vault addresses and balances, flagging any mismatch against the
+    /// sum of trader states and uncollected fees
+    GetVaults { market_pubkey: Pubkey },
+    /// Continuously reconcile a market's vault balances against the sum of trader states and
+    /// uncollected fees, printing an alert to stderr whenever they drift apart
+    WatchVaults {
+        market_pubkey: Pubkey,
+        /// Time between checks, e.g. 10s or 1m. Defaults to 10s
+        #[clap(short, long, required = false, default_value = "10s", value_parser = parse_duration)]
+        interval: u64,
+    },
+    /// Periodically record the bid and ask depth within several basis point bands of the midpoint
+    /// to a CSV file. Sizes are in base units. Rows are appended if the file already exists.
+    RecordBookDepth {
+        market_pubkey: Pubkey,
+        /// Path of the CSV file to write
+        out: String,
+        /// Comma separated list of bands around the midpoint, in basis points
+        #[clap(
+            short,
+            long,
+            required = false,
+            value_delimiter = ',',
+            default_value = "10,50,100"
+        )]
+        bands: Vec<u64>,
+        /// Time between samples, e.g. 30s or 5m. Defaults to 1m
+        #[clap(short, long, required = false, default_value = "1m", value_parser = parse_duration)]
+        interval: u64,
+        /// Stop after this many samples. Runs until interrupted by default
+        #[clap(short = 'n', long, required = false)]
+        samples: Option<u64>,
+    },
+    /// Append the current uncollected and collected fees of every market in the config to a CSV file.
+    /// Run this on a schedule to build the history used by get-fee-accrual.
+    RecordFeeSnapshot {
+        /// Path of the CSV file to append to
+        out: String,
+        /// Compress each appended snapshot. Readers of the file detect the compression automatically
+        #[clap(long, required = false, value_enum)]
+        compress: Option<Compression>,
+    },
+    /// Report the fee accrual rate per market from snapshots written by record-fee-snapshot
+    GetFeeAccrual {
+        /// Path of the CSV file written by record-fee-snapshot
+        snapshot_file: String,
+    },
+    /// Compare a market's spread and depth with and without a given maker's orders,
+    /// to gauge how dependent the market is on that liquidity provider
+    SimulateMakerRemoval {
+        market_pubkey: Pubkey,
+        /// Pubkey of the maker whose orders are removed from the book
+        maker_pubkey: Pubkey,
+    },
+    /// For each of a trader's resting orders on a given market, estimate the base size queued ahead of it,
+    /// both at the same price level and across the whole book
+    GetQueuePosition {
+        market_pubkey: Pubkey,
+        /// Pubkey of the trader whose orders to check. Defaults to the current payer
+        #[clap(short, long, required = false)]
+        trader_pubkey: Option<Pubkey>,
+    },
+    /// List every order resting at a given price level, with its maker, size, and queue position
+    GetLevel {
+        market_pubkey: Pubkey,
+        /// Price of the level, in quote units per base unit
+        #[clap(short, long, value_parser = parse_amount)]
+        price: f64,
+        /// Side of the book: bid or ask
+        #[clap(short, long, value_parser = parse_side)]
+        side: Side,
+    },
+    /// Sample recent prioritization fees paid on the accounts write-locked by a market's orders
+    /// and report percentiles, as a guide for what priority fee is needed to land orders
+    GetPriorityFees {
+        market_pubkey: Pubkey,
+        /// Number of samples to take. Defaults to 1
+        #[clap(short = 'n', long, required = false, default_value = "1")]
+        samples: u64,
+        /// Time between samples, e.g. 10s or 1m. Defaults to 10s
+        #[clap(short, long, required = false, default_value = "10s", value_parser = parse_duration)]
+        interval: u64,
+    },
+    /// Migrate the payer's maker seat on a market to a new keypair. Cancels orders, withdraws and transfers funds
+    /// from the current payer, then claims a seat and deposits with the new keypair. Prints the plan unless `--execute` is passed.
+    RotateKeypair {
+        market_pubkey: Pubkey,
+        /// Path to the new keypair
+        new_keypair_path: String,
+        /// Perform the rotation instead of only printing the plan
+        #[clap(short, long, required = false)]
+        execute: bool,
+    },
+    /// Keep the books of several markets in memory and serve them as JSON over HTTP at
+    /// http://127.0.0.1:<port>/book/<market>, as a local book cache for other processes on the same host.
+    /// Books are refreshed by polling and include the market sequence number.
+    ServeBooks {
+        /// Markets to serve. Defaults to every market in the config
+        markets: Vec<Pubkey>,
+        /// Local port to listen on. Defaults to 8080
+        #[clap(short, long, required = false, default_value = "8080")]
+        port: u16,
+        /// Time between book refreshes, e.g. 500ms or 2s. Defaults to 1s
+        #[clap(short, long, required = false, default_value = "1s", value_parser = parse_duration_ms)]
+        interval: u64,
+    },
+    /// Prefetch the Phoenix config and the metadata of every market in it, and persist them under ~/.cache/phoenix-cli.
+    /// Commands that look up the config read it from the cache for the next 24 hours instead of fetching it.
+    WarmCache,
+    /// Produce an accrued vs collected vs outstanding fee statement per market over a period, from snapshots
+    /// written by record-fee-snapshot. Totals are grouped by quote token.
+    GetRevenueStatement {
+        /// Path of the CSV file written by record-fee-snapshot
+        snapshot_file: String,
+        /// Start of the period, as a unix timestamp, an RFC 3339 time, or a duration ago such as 30d.
+        /// Defaults to the first snapshot
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        start: Option<i64>,
+        /// End of the period, in the same formats as --start. Defaults to the last snapshot
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        end: Option<i64>,
+        /// Write the statement to this CSV file instead of printing it
+        #[clap(short, long, required = false)]
+        out: Option<String>,
+    },
+    /// Attribute every token balance change of a wallet over a time range to a fill, fee, deposit, withdrawal,
+    /// or transfer, by decoding the transactions responsible for it
+    GetBalanceLedger {
+        /// Wallet to build the ledger for. Defaults to the current payer
+        #[clap(short, long, required = false)]
+        wallet: Option<Pubkey>,
+        /// Start of the range, as a unix timestamp, an RFC 3339 time, or a duration ago such as 7d.
+        /// Defaults to 24 hours ago
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        start: Option<i64>,
+        /// End of the range, in the same formats as --start. Defaults to now
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        end: Option<i64>,
+        /// Write the ledger to this CSV file instead of printing it
+        #[clap(short, long, required = false)]
+        out: Option<String>,
+    },
+    /// List a trader's fills over a time range by parsing the Phoenix events in transaction history.
+    /// Only fills of the trader's taker orders are found unless `--market` is passed, which scans the
+    /// market's history instead and also finds fills of the trader's resting orders.
+    GetTraderFills {
+        /// Trader to list fills for. Defaults to the current payer
+        #[clap(short, long, required = false)]
+        trader: Option<Pubkey>,
+        /// Scan this market's transaction history instead of the trader's
+        #[clap(short, long, required = false)]
+        market_pubkey: Option<Pubkey>,
+        /// Start of the range, as a unix timestamp, an RFC 3339 time, or a duration ago such as 7d.
+        /// Defaults to 24 hours ago
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        start: Option<i64>,
+        /// End of the range, in the same formats as --start. Defaults to now
+        #[clap(short, long, required = false, value_parser = parse_timestamp)]
+        end: Option<i64>,
+        /// Write the fills to this CSV file instead of printing them
+        #[clap(short, long, required = false)]
+        out: Option<String>,
+    },
+    /// Get a wallet's free and locked balances on every market in the Phoenix config, plus its token account
+    /// balances, consolidated per token
+    GetBalances {
+        /// Wallet to get balances for. Defaults to the current payer
+        wallet: Option<Pubkey>,
+    },
+    /// List every market in the Phoenix config where a trader has a seat or a nonzero balance
+    GetTraderMarkets {
+        /// Pubkey of the trader
+        trader: Pubkey,
+    },
+    /// Poll a trader's state on a market and print only the changes to its locked and free balances, with timestamps
+    WatchBalance {
+        /// Pubkey of the trader to watch
+        trader_pubkey: Pubkey,
+        #[clap(short, long)]
+        market_pubkey: Pubkey,
+        /// Time between checks, e.g. 5s or 1m. Defaults to 5s
+        #[clap(short, long, required = false, default_value = "5s", value_parser = parse_duration)]
+        interval: u64,
+    },
+    /// Wait for a market to become active with liquidity inside a price bound, then immediately send an
+    /// immediate-or-cancel order. The limit price bounds the worst possible fill.
+    Snipe {
+        market_pubkey: Pubkey,
+        /// Side of the order: bid (buy) or ask (sell)
+        #[clap(short, long, value_parser = parse_side)]
+        side: Side,
+        /// Size of the order, in base units
+        #[clap(short = 'z', long, required_unless_present = "size_in_lots", value_parser = parse_amount)]
+        size: Option<f64>,
+        /// Size of the order in base lots, used as is instead of converting --size
+        #[clap(long, required = false, conflicts_with = "size")]
+        size_in_lots: Option<u64>,
+        /// Worst price to fill at, in quote units per base unit
+        #[clap(short, long, required_unless_present = "price_in_ticks", value_parser = parse_amount)]
+        limit_price: Option<f64>,
+        /// Worst price to fill at in ticks, used as is instead of converting --limit-price
+        #[clap(long, required = false, conflicts_with = "limit_price")]
+        price_in_ticks: Option<u64>,
+        /// Time between market checks, e.g. 400ms or 1s. Defaults to 400ms
+        #[clap(short, long, required = false, default_value = "400ms", value_parser = parse_duration_ms)]
+        interval: u64,
+    },
+    /// Estimate makers' accrued rewards from an incentive program over a window. Makers are scored on
+    /// uptime, spread, and volume as weighted by the program rules, and the rewards are split pro rata by score.
+    EstimateIncentives {
+        market_pubkey: Pubkey,
+        /// Path to a JSON file with the program rules, e.g. {"rewardPerDay": 1000.0, "rewardSymbol": "PHX", "uptimeWeight": 0.4,
+        /// "spreadWeight": 0.3, "volumeWeight": 0.3, "maxSpreadBps": 50.0}
+        rules_file: String,
+        /// Only print the estimate for this maker
+        #[clap(short, long, required = false)]
+        maker: Option<Pubkey>,
+        /// Size of the window to scan, e.g. 6h or 7d. Defaults to 1d
+        #[clap(short, long, required = false, default_value = "1d", value_parser = parse_window)]
+        window: u64,
+    },
+    /// Move the payer's resting orders from one market to another market for the same pair, e.g. after a relisting
+    /// with new tick and lot sizes. Cancels and withdraws on the old market, then places the translated orders as
+    /// post-only orders on the new market. Prints the plan unless `--execute` is passed.
+    MigrateQuotes {
+        old_market_pubkey: Pubkey,
+        new_market_pubkey: Pubkey,
+        /// Perform the migration instead of only printing the plan
+        #[clap(short, long, required = false)]
+        execute: bool,
+    },
+    /// Settlement report for a market being wound down. Lists every trader with an outstanding balance, flags funds
+    /// still locked in orders, and checks the vaults cover what is owed
+    GetSettlementReport {
+        market_pubkey: Pubkey,
+        /// Write the per-trader balances to this CSV file instead of printing them
+        #[clap(short, long, required = false)]
+        out: Option<String>,
+        /// Keep reporting progress at this interval until both vaults are empty, e.g. 30s or 5m
+        #[clap(short, long, required = false, value_parser = parse_duration)]
+        watch: Option<u64>,
+    },
 }
@@ -0,0 +1,137 @@
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+
+use clap::ValueEnum;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Compression {
+    Zstd,
+    Gzip,
+}
+
+// Read a snapshot or archive file, transparently decompressing zstd and gzip files. The format is
+// detected from the file contents, so compressed files don't need a .zst or .gz extension.
+pub fn read_archive_to_string(path: &str) -> anyhow::Result<String> {
+    let path = shellexpand::tilde(path).to_string();
+    let bytes = std::fs::read(&path)?;
+    let mut contents = String::new();
+    match detect_compression(&bytes) {
+        // Appends are written as separate frames, which decode_all reads back to back
+        Some(Compression::Zstd) => {
+            contents = String::from_utf8(zstd::stream::decode_all(bytes.as_slice())?)?;
+        }
+        Some(Compression::Gzip) => {
+            MultiGzDecoder::new(bytes.as_slice()).read_to_string(&mut contents)?;
+        }
+        None => contents = String::from_utf8(bytes)?,
+    }
+    Ok(contents)
+}
+
+fn detect_compression(bytes: &[u8]) -> Option<Compression> {
+    if bytes.starts_with(&ZSTD_MAGIC) {
+        Some(Compression::Zstd)
+    } else if bytes.starts_with(&GZIP_MAGIC) {
+        Some(Compression::Gzip)
+    } else {
+        None
+    }
+}
+
+// Append text to a file, optionally compressed. Each append is written as its own zstd frame or
+// gzip member, so the file stays readable by read_archive_to_string and standard tools. Appending
+// to an existing file in a different format fails, since the format is detected from the start of
+// the file and the mixed contents could not be read back.
+pub fn append_to_archive(
+    path: &str,
+    contents: &str,
+    compression: Option<Compression>,
+) -> anyhow::Result<()> {
+    let path = shellexpand::tilde(path).to_string();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(&path)?;
+    let mut magic = Vec::with_capacity(ZSTD_MAGIC.len());
+    (&mut file)
+        .take(ZSTD_MAGIC.len() as u64)
+        .read_to_end(&mut magic)?;
+    if !magic.is_empty() {
+        let existing = detect_compression(&magic);
+        if existing != compression {
+            let describe = |compression: Option<Compression>| match compression {
+                Some(Compression::Zstd) => "zstd",
+                Some(Compression::Gzip) => "gzip",
+                None => "uncompressed",
+            };
+            return Err(anyhow::anyhow!(
+                "{} is {}, but --compress is {}. Append with the same compression or use a new file",
+                path,
+                describe(existing),
+                describe(compression)
+            ));
+        }
+    }
+    match compression {
+        Some(Compression::Zstd) => {
+            file.write_all(&zstd::stream::encode_all(contents.as_bytes(), 0)?)?;
+        }
+        Some(Compression::Gzip) => {
+            let mut encoder = GzEncoder::new(file, flate2::Compression::default());
+            encoder.write_all(contents.as_bytes())?;
+            encoder.finish()?;
+        }
+        None => file.write_all(contents.as_bytes())?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("phoenix-cli-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_appends_read_back_in_every_format() {
+        for (name, compression) in [
+            ("plain.jsonl", None),
+            ("zstd.jsonl", Some(Compression::Zstd)),
+            ("gzip.jsonl", Some(Compression::Gzip)),
+        ] {
+            let path = temp_path(name);
+            append_to_archive(&path, "{\"slot\":1}\n", compression).unwrap();
+            append_to_archive(&path, "{\"slot\":2}\n", compression).unwrap();
+            assert_eq!(
+                detect_compression(&std::fs::read(&path).unwrap()),
+                compression
+            );
+            assert_eq!(
+                read_archive_to_string(&path).unwrap(),
+                "{\"slot\":1}\n{\"slot\":2}\n"
+            );
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_append_with_different_compression_fails() {
+        let path = temp_path("mixed.jsonl");
+        append_to_archive(&path, "{\"slot\":1}\n", Some(Compression::Zstd)).unwrap();
+        assert!(append_to_archive(&path, "{\"slot\":2}\n", Some(Compression::Gzip)).is_err());
+        assert!(append_to_archive(&path, "{\"slot\":2}\n", None).is_err());
+        assert_eq!(read_archive_to_string(&path).unwrap(), "{\"slot\":1}\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Cached entries older than this are ignored and refetched
+pub const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn get_cache_root() -> PathBuf {
+    PathBuf::from(&*shellexpand::tilde("~/.cache/phoenix-cli"))
+}
+
+// Directory holding the cache for a cluster, e.g. ~/.cache/phoenix-cli/mainnet-beta
+pub fn get_cache_dir(cluster: &str) -> PathBuf {
+    get_cache_root().join(cluster)
+}
+
+// Read a cached value written by `write_cache`. Returns None if the entry is missing,
+// unreadable, or older than CACHE_TTL.
+pub fn read_cache<T: DeserializeOwned>(cluster: &str, name: &str) -> Option<T> {
+    read_cache_file(&get_cache_dir(cluster), name)
+}
+
+pub fn write_cache<T: Serialize>(cluster: &str, name: &str, value: &T) -> anyhow::Result<PathBuf> {
+    write_cache_file(&get_cache_dir(cluster), name, value)
+}
+
+// The cluster behind an RPC URL as cached by warm-cache, which lets commands skip the genesis
+// hash lookup. The mapping lives outside the per-cluster directories since it is needed to find them.
+pub fn read_cached_cluster(url: &str) -> Option<String> {
+    read_cache_file::<HashMap<String, String>>(&get_cache_root(), "clusters")?.remove(url)
+}
+
+pub fn write_cached_cluster(url: &str, cluster: &str) -> anyhow::Result<PathBuf> {
+    let root = get_cache_root();
+    let mut clusters =
+        read_cache_file::<HashMap<String, String>>(&root, "clusters").unwrap_or_default();
+    clusters.insert(url.to_string(), cluster.to_string());
+    write_cache_file(&root, "clusters", &clusters)
+}
+
+fn read_cache_file<T: DeserializeOwned>(dir: &Path, name: &str) -> Option<T> {
+    let path = dir.join(format!("{}.json", name));
+    let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+    if SystemTime::now().duration_since(modified).ok()? > CACHE_TTL {
+        return None;
+    }
+    serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()
+}
+
+fn write_cache_file<T: Serialize>(dir: &Path, name: &str, value: &T) -> anyhow::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", name));
+    std::fs::write(&path, serde_json::to_string_pretty(value)?)?;
+    Ok(path)
+}
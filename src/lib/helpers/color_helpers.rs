@@ -0,0 +1,93 @@
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use clap::ValueEnum;
+use colored::Color;
+use serde::Deserialize;
+
+// Optional user config file. Only the theme section is read for now, e.g.
+// {"theme": {"bid": "blue", "ask": "yellow", "warning": "magenta"}}
+pub const CONFIG_PATH: &str = "~/.config/phoenix-cli/config.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    // Color when writing to a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    pub bid: Color,
+    pub ask: Color,
+    pub warning: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            bid: Color::Green,
+            ask: Color::Red,
+            warning: Color::Red,
+        }
+    }
+}
+
+// Color names as accepted by `colored`, e.g. "blue", "bright magenta". Unset entries keep the
+// default theme.
+#[derive(Deserialize, Default)]
+struct ThemeConfig {
+    bid: Option<String>,
+    ask: Option<String>,
+    warning: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct CliConfig {
+    theme: Option<ThemeConfig>,
+}
+
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+// Apply the --color choice and load the theme from the config file, if there is one
+pub fn configure_colors(choice: ColorChoice) -> anyhow::Result<()> {
+    match choice {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+        ColorChoice::Auto => {
+            // https://no-color.org: any non-empty value disables color
+            if matches!(std::env::var("NO_COLOR"), Ok(v) if !v.is_empty()) {
+                colored::control::set_override(false);
+            }
+        }
+    }
+
+    let path = shellexpand::tilde(CONFIG_PATH).to_string();
+    let config: CliConfig = match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Failed to parse config file {}: {}", path, e))?,
+        Err(_) => CliConfig::default(),
+    };
+    let theme_config = config.theme.unwrap_or_default();
+    let default = Theme::default();
+    let parse = |name: Option<String>, default: Color| -> anyhow::Result<Color> {
+        match name {
+            Some(name) => Color::from_str(&name)
+                .map_err(|_| anyhow::anyhow!("Unknown color in {}: {}", path, name)),
+            None => Ok(default),
+        }
+    };
+    let theme = Theme {
+        bid: parse(theme_config.bid, default.bid)?,
+        ask: parse(theme_config.ask, default.ask)?,
+        warning: parse(theme_config.warning, default.warning)?,
+    };
+    *THEME.write().unwrap() = Some(theme);
+    Ok(())
+}
+
+pub fn get_theme() -> Theme {
+    THEME.read().unwrap().unwrap_or_default()
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::Serialize;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::transaction::TransactionError;
+
+// Failure categories, each with its own process exit code so scripts can tell them apart without
+// parsing error messages. Exit code 2 is left to clap for usage errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Other,
+    RpcFailure,
+    DeserializationFailure,
+    InsufficientFunds,
+    UserAbort,
+}
+
+impl ErrorCode {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCode::Other => 1,
+            ErrorCode::RpcFailure => 3,
+            ErrorCode::DeserializationFailure => 4,
+            ErrorCode::InsufficientFunds => 5,
+            ErrorCode::UserAbort => 6,
+        }
+    }
+}
+
+// Returned when the user interrupts a command
+#[derive(Debug)]
+pub struct UserAbort;
+
+impl fmt::Display for UserAbort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Aborted by user")
+    }
+}
+
+impl std::error::Error for UserAbort {}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ErrorOutput {
+    pub code: ErrorCode,
+    pub message: String,
+    pub context: Vec<String>,
+}
+
+// Categorize an error by the errors in its chain. Much of the market loading code reports
+// deserialization failures as plain messages, so those are matched by message as well.
+pub fn classify_error(error: &anyhow::Error) -> ErrorCode {
+    for cause in error.chain() {
+        if cause.is::<UserAbort>() {
+            return ErrorCode::UserAbort;
+        }
+        if let Some(client_error) = cause.downcast_ref::<ClientError>() {
+            // A transaction the cluster rejected is not an RPC failure, only a failed request is
+            return match client_error.get_transaction_error() {
+                Some(TransactionError::InsufficientFundsForFee)
+                | Some(TransactionError::InsufficientFundsForRent { .. }) => {
+                    ErrorCode::InsufficientFunds
+                }
+                Some(_) => ErrorCode::Other,
+                None => match client_error.kind() {
+                    ClientErrorKind::Io(_)
+                    | ClientErrorKind::Reqwest(_)
+                    | ClientErrorKind::RpcError(_) => ErrorCode::RpcFailure,
+                    ClientErrorKind::SerdeJson(_) => ErrorCode::DeserializationFailure,
+                    _ => ErrorCode::Other,
+                },
+            };
+        }
+        if cause.is::<bincode::Error>() || cause.is::<serde_json::Error>() {
+            return ErrorCode::DeserializationFailure;
+        }
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            // Borsh reports malformed account data as io errors
+            if matches!(
+                io_error.kind(),
+                std::io::ErrorKind::InvalidData | std::io::ErrorKind::UnexpectedEof
+            ) {
+                return ErrorCode::DeserializationFailure;
+            }
+        }
+    }
+    let message = format!("{:#}", error).to_lowercase();
+    if message.contains("insufficient funds") || message.contains("insufficient lamports") {
+        ErrorCode::InsufficientFunds
+    } else if message.contains("deserializ")
+        || message.contains("error getting market header")
+        || message.contains("failed to load market")
+    {
+        ErrorCode::DeserializationFailure
+    } else {
+        ErrorCode::Other
+    }
+}
+
+// Print the error as a single JSON object on stderr. The outermost error is the message and the
+// errors that caused it are the context, innermost last.
+pub fn print_error_json(error: &anyhow::Error, code: ErrorCode) {
+    let output = ErrorOutput {
+        code,
+        message: error.to_string(),
+        context: error
+            .chain()
+            .skip(1)
+            .map(|cause| cause.to_string())
+            .collect(),
+    };
+    match serde_json::to_string(&output) {
+        Ok(json) => eprintln!("{}", json),
+        Err(_) => eprintln!("Error: {:?}", error),
+    }
+}
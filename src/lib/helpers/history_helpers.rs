@@ -0,0 +1,527 @@
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ellipsis_client::EllipsisClient;
+use phoenix_sdk::sdk_client::*;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::print_info;
+
+// Maximum page size allowed by getSignaturesForAddress
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+// Delay between page requests, to stay under public RPC rate limits
+const DEFAULT_PAGE_DELAY: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+pub fn get_unix_timestamp() -> anyhow::Result<i64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64)
+}
+
+pub struct SignatureInfo {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+pub struct SignatureHistoryConfig {
+    // Start paging from before this signature (exclusive). Defaults to the newest transaction
+    pub before: Option<Signature>,
+    // Stop paging at this signature (exclusive)
+    pub until: Option<Signature>,
+    // Stop paging once transactions are older than this unix timestamp
+    pub min_block_time: Option<i64>,
+    // Stop paging once transactions are older than this slot
+    pub min_slot: Option<u64>,
+    // Stop paging after this many signatures
+    pub limit: Option<usize>,
+    // Append the signatures and paging cursor here after every page, and resume from them if the
+    // file exists. Remove it with remove_checkpoint once the command completes.
+    pub checkpoint_file: Option<String>,
+    pub page_delay: Duration,
+    pub max_retries: u32,
+    // Skip signatures of failed transactions
+    pub skip_failed: bool,
+}
+
+impl Default for SignatureHistoryConfig {
+    fn default() -> Self {
+        Self {
+            before: None,
+            until: None,
+            min_block_time: None,
+            min_slot: None,
+            limit: None,
+            checkpoint_file: None,
+            page_delay: DEFAULT_PAGE_DELAY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            skip_failed: true,
+        }
+    }
+}
+
+// Pages backwards through the transaction history of an address, newest first. Handles the
+// before/until cursors, rate limiting, retries with backoff, deduplication across pages, and
+// resumable checkpoints, so history-based commands don't each reimplement them.
+pub struct SignaturePager<'a> {
+    client: &'a EllipsisClient,
+    address: Pubkey,
+    config: SignatureHistoryConfig,
+    // Signatures returned before the run was interrupted, handed back as the first page
+    resumed: Vec<SignatureInfo>,
+    seen: HashSet<Signature>,
+    returned: usize,
+    done: bool,
+    // Whether paging stopped at a transaction older than min_block_time or min_slot, rather than
+    // at the end of the history the node serves
+    reached_lower_bound: bool,
+    first_page: bool,
+}
+
+impl<'a> SignaturePager<'a> {
+    pub fn new(
+        client: &'a EllipsisClient,
+        address: &Pubkey,
+        mut config: SignatureHistoryConfig,
+    ) -> anyhow::Result<Self> {
+        let mut resumed = vec![];
+        if let Some(checkpoint_file) = &config.checkpoint_file {
+            let path = shellexpand::tilde(checkpoint_file).to_string();
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                // One JSON object per line, either a returned signature or the cursor after a page
+                for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                    let entry: serde_json::Value = serde_json::from_str(line)?;
+                    if let Some(cursor) = entry["cursor"].as_str() {
+                        config.before = Some(Signature::from_str(cursor)?);
+                    } else if let Some(signature) = entry["signature"].as_str() {
+                        resumed.push(SignatureInfo {
+                            signature: Signature::from_str(signature)?,
+                            slot: entry["slot"].as_u64().unwrap_or_default(),
+                            block_time: entry["block_time"].as_i64(),
+                        });
+                    }
+                }
+                if let Some(cursor) = &config.before {
+                    tracing::info!(
+                        checkpoint_file = %path,
+                        signatures = resumed.len(),
+                        %cursor,
+                        "Resuming from checkpoint"
+                    );
+                }
+            }
+        }
+        Ok(Self {
+            client,
+            address: *address,
+            config,
+            seen: resumed.iter().map(|info| info.signature).collect(),
+            returned: resumed.len(),
+            resumed,
+            done: false,
+            reached_lower_bound: false,
+            first_page: true,
+        })
+    }
+
+    // Get the next page of signatures, or None once the history is exhausted
+    pub async fn next_page(&mut self) -> anyhow::Result<Option<Vec<SignatureInfo>>> {
+        if !self.resumed.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.resumed)));
+        }
+        if self.done {
+            return Ok(None);
+        }
+        if !self.first_page {
+            tokio::time::sleep(self.config.page_delay).await;
+        }
+        self.first_page = false;
+
+        let page = self.fetch_page_with_retries().await?;
+        if page.len() < SIGNATURE_PAGE_SIZE {
+            self.done = true;
+        }
+
+        let mut signatures = vec![];
+        for status in page {
+            let signature = Signature::from_str(&status.signature)?;
+            self.config.before = Some(signature);
+            let too_old = self
+                .config
+                .min_block_time
+                .zip(status.block_time)
+                .map(|(min, block_time)| block_time < min)
+                .unwrap_or(false)
+                || self
+                    .config
+                    .min_slot
+                    .map(|min| status.slot < min)
+                    .unwrap_or(false);
+            if too_old {
+                self.done = true;
+                self.reached_lower_bound = true;
+                break;
+            }
+            if !self.seen.insert(signature) || (self.config.skip_failed && status.err.is_some()) {
+                continue;
+            }
+            signatures.push(SignatureInfo {
+                signature,
+                slot: status.slot,
+                block_time: status.block_time,
+            });
+            self.returned += 1;
+            if self
+                .config
+                .limit
+                .map(|l| self.returned >= l)
+                .unwrap_or(false)
+            {
+                self.done = true;
+                break;
+            }
+        }
+        self.save_checkpoint(&signatures)?;
+        Ok(Some(signatures))
+    }
+
+    pub fn reached_lower_bound(&self) -> bool {
+        self.reached_lower_bound
+    }
+
+    // Collect the remaining history into a single list, newest first
+    pub async fn collect(mut self) -> anyhow::Result<Vec<SignatureInfo>> {
+        let mut signatures = vec![];
+        while let Some(page) = self.next_page().await? {
+            signatures.extend(page);
+        }
+        Ok(signatures)
+    }
+
+    async fn fetch_page_with_retries(
+        &self,
+    ) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .get_signatures_for_address_with_config(
+                    &self.address,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before: self.config.before,
+                        until: self.config.until,
+                        limit: Some(SIGNATURE_PAGE_SIZE),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                    },
+                )
+                .await;
+            match result {
+                Ok(page) => return Ok(page),
+                Err(e) if attempt < self.config.max_retries => {
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                    tracing::warn!(
+                        error = %e,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Failed to fetch signatures, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    fn save_checkpoint(&self, signatures: &[SignatureInfo]) -> anyhow::Result<()> {
+        if let (Some(checkpoint_file), Some(before)) =
+            (&self.config.checkpoint_file, &self.config.before)
+        {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&*shellexpand::tilde(checkpoint_file))?;
+            for info in signatures.iter() {
+                writeln!(
+                    file,
+                    "{}",
+                    serde_json::json!({
+                        "signature": info.signature.to_string(),
+                        "slot": info.slot,
+                        "block_time": info.block_time,
+                    })
+                )?;
+            }
+            writeln!(
+                file,
+                "{}",
+                serde_json::json!({ "cursor": before.to_string() })
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Delete a checkpoint file once the command that paged with it has completed, so the next run
+// starts from the newest transaction again
+pub fn remove_checkpoint(checkpoint_file: Option<&str>) -> anyhow::Result<()> {
+    if let Some(checkpoint_file) = checkpoint_file {
+        match std::fs::remove_file(&*shellexpand::tilde(checkpoint_file)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Get the signatures of successful transactions that touched the given address within the
+// last `window_seconds`, newest first
+pub async fn get_signatures_in_window(
+    client: &EllipsisClient,
+    address: &Pubkey,
+    window_seconds: u64,
+) -> anyhow::Result<Vec<Signature>> {
+    let config = SignatureHistoryConfig {
+        min_block_time: Some(get_unix_timestamp()? - window_seconds as i64),
+        ..SignatureHistoryConfig::default()
+    };
+    Ok(SignaturePager::new(client, address, config)?
+        .collect()
+        .await?
+        .into_iter()
+        .map(|info| info.signature)
+        .collect())
+}
+
+// Get all events emitted on the given market within the last `window_seconds`, oldest first
+pub async fn get_market_events_in_window(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    window_seconds: u64,
+) -> anyhow::Result<Vec<PhoenixEvent>> {
+    Ok(
+        scan_market_events_in_window(sdk, market_pubkey, window_seconds)
+            .await?
+            .events,
+    )
+}
+
+// Same as get_market_events_in_window, but also reports the transactions that failed to parse
+pub async fn scan_market_events_in_window(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    window_seconds: u64,
+) -> anyhow::Result<MarketEventScan> {
+    let signatures = get_signatures_in_window(&sdk.client, market_pubkey, window_seconds).await?;
+    scan_market_events_for_signatures(sdk, market_pubkey, &signatures).await
+}
+
+// Get every fill on the given market in transactions between the unix timestamps `start` and
+// `end`, oldest first, along with the block time of the transaction
+pub async fn get_market_fills_in_range(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    start: i64,
+    end: i64,
+) -> anyhow::Result<Vec<(i64, Fill)>> {
+    let config = SignatureHistoryConfig {
+        min_block_time: Some(start),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut signatures = SignaturePager::new(&sdk.client, market_pubkey, config)?
+        .collect()
+        .await?;
+    signatures.retain(|info| info.block_time.map(|t| t <= end).unwrap_or(false));
+    print_info!("Scanning {} transaction(s)", signatures.len());
+    let mut fills = vec![];
+    for info in signatures.iter().rev() {
+        if let Some(tx_events) = sdk.parse_events_from_transaction(&info.signature).await {
+            for event in tx_events.into_iter().filter(|e| e.market == *market_pubkey) {
+                if let MarketEventDetails::Fill(fill) = event.details {
+                    fills.push((info.block_time.unwrap_or_default(), fill));
+                }
+            }
+        }
+    }
+    Ok(fills)
+}
+
+// Parse the events emitted on the given market by each transaction. Signatures are expected
+// newest first, and events are returned oldest first. Transactions that fail to parse are skipped.
+pub async fn get_market_events_for_signatures(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    signatures: &[Signature],
+) -> anyhow::Result<Vec<PhoenixEvent>> {
+    Ok(
+        scan_market_events_for_signatures(sdk, market_pubkey, signatures)
+            .await?
+            .events,
+    )
+}
+
+pub struct MarketEventScan {
+    // Events on the market, oldest first
+    pub events: Vec<PhoenixEvent>,
+    // Transactions whose events could not be parsed, e.g. because they were not yet fetchable.
+    // Callers that need a complete history should treat any of these as an error.
+    pub unparsed: Vec<Signature>,
+}
+
+pub async fn scan_market_events_for_signatures(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    signatures: &[Signature],
+) -> anyhow::Result<MarketEventScan> {
+    print_info!("Scanning {} transaction(s)", signatures.len());
+    let mut scan = MarketEventScan {
+        events: vec![],
+        unparsed: vec![],
+    };
+    for signature in signatures.iter().rev() {
+        match sdk.parse_events_from_transaction(signature).await {
+            Some(tx_events) => scan
+                .events
+                .extend(tx_events.into_iter().filter(|e| e.market == *market_pubkey)),
+            None => scan.unparsed.push(*signature),
+        }
+    }
+    Ok(scan)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::signature::Keypair;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("phoenix-cli-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    // Newest first, like getSignaturesForAddress
+    fn history(len: u64) -> Vec<(Signature, u64)> {
+        (0..len)
+            .map(|i| (Signature::new_unique(), 1_000 - i))
+            .collect()
+    }
+
+    // A client whose getSignaturesForAddress call returns `page`
+    fn mock_client(page: &[(Signature, u64)]) -> EllipsisClient {
+        let page: Vec<_> = page
+            .iter()
+            .map(
+                |(signature, slot)| RpcConfirmedTransactionStatusWithSignature {
+                    signature: signature.to_string(),
+                    slot: *slot,
+                    err: None,
+                    memo: None,
+                    block_time: Some(*slot as i64),
+                    confirmation_status: None,
+                },
+            )
+            .collect();
+        let mocks = HashMap::from([(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(page).unwrap(),
+        )]);
+        EllipsisClient::from_rpc(
+            RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks),
+            &Keypair::new(),
+        )
+        .unwrap()
+    }
+
+    fn signatures(infos: &[SignatureInfo]) -> Vec<Signature> {
+        infos.iter().map(|info| info.signature).collect()
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_round_trip() {
+        let path = temp_path("round-trip.jsonl");
+        let address = Pubkey::new_unique();
+        let config = || SignatureHistoryConfig {
+            checkpoint_file: Some(path.clone()),
+            page_delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let history = history(3);
+        let client = mock_client(&history);
+        let fetched = SignaturePager::new(&client, &address, config())
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(fetched.len(), 3);
+
+        // Loading the checkpoint hands back every signature and resumes after the oldest one
+        let client = mock_client(&[]);
+        let pager = SignaturePager::new(&client, &address, config()).unwrap();
+        assert_eq!(pager.config.before, Some(history[2].0));
+        assert_eq!(signatures(&pager.resumed), signatures(&fetched));
+        for (resumed, fetched) in pager.resumed.iter().zip(fetched.iter()) {
+            assert_eq!(resumed.slot, fetched.slot);
+            assert_eq!(resumed.block_time, fetched.block_time);
+        }
+
+        remove_checkpoint(Some(&path)).unwrap();
+        assert!(!std::path::Path::new(&path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_resume_neither_repeats_nor_skips_the_boundary() {
+        let path = temp_path("boundary.jsonl");
+        let address = Pubkey::new_unique();
+        let config = || SignatureHistoryConfig {
+            checkpoint_file: Some(path.clone()),
+            page_delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let history = history(5);
+
+        // Interrupt the first run after two signatures
+        let client = mock_client(&history);
+        let first = SignaturePager::new(
+            &client,
+            &address,
+            SignatureHistoryConfig {
+                limit: Some(2),
+                ..config()
+            },
+        )
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        assert_eq!(
+            signatures(&first),
+            history[..2].iter().map(|(s, _)| *s).collect::<Vec<_>>()
+        );
+
+        // The cursor is the last signature returned. Serve it again with the rest of the history,
+        // as a node treating the cursor as inclusive would, to check it isn't returned twice.
+        let client = mock_client(&history[1..]);
+        let pager = SignaturePager::new(&client, &address, config()).unwrap();
+        assert_eq!(pager.config.before, Some(history[1].0));
+        let resumed = pager.collect().await.unwrap();
+        assert_eq!(
+            signatures(&resumed),
+            history.iter().map(|(s, _)| *s).collect::<Vec<_>>()
+        );
+
+        remove_checkpoint(Some(&path)).unwrap();
+    }
+}
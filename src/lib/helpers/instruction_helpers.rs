@@ -0,0 +1,128 @@
+use phoenix::program::instruction::PhoenixInstruction;
+use serde_json::json;
+use solana_sdk::bs58;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{
+    EncodedTransactionWithStatusMeta, UiInnerInstructions, UiInstruction, UiLoadedAddresses,
+};
+
+// A Phoenix instruction found in a transaction
+pub struct PhoenixInstructionInTx {
+    // Position of the top level instruction
+    pub index: usize,
+    // Position among the inner instructions of the top level instruction at `index`, for
+    // instructions invoked through CPI
+    pub inner_index: Option<usize>,
+    pub instruction: PhoenixInstruction,
+    pub market: Option<Pubkey>,
+    // Instruction data following the tag
+    pub args: Vec<u8>,
+}
+
+// Print instructions as JSON (program id, accounts, and base64 data) instead of sending them,
+// so they can be composed into larger transactions with other programs
+pub fn print_instructions_json(instructions: &[Instruction]) -> anyhow::Result<()> {
+    let instructions = instructions
+        .iter()
+        .map(|ix| {
+            json!({
+                "programId": ix.program_id.to_string(),
+                "accounts": ix
+                    .accounts
+                    .iter()
+                    .map(|meta| {
+                        json!({
+                            "pubkey": meta.pubkey.to_string(),
+                            "isSigner": meta.is_signer,
+                            "isWritable": meta.is_writable,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                "data": base64::encode(&ix.data),
+            })
+        })
+        .collect::<Vec<_>>();
+    println!("{}", serde_json::to_string_pretty(&instructions)?);
+    Ok(())
+}
+
+// Find every Phoenix instruction in a transaction fetched with base64 encoding, in execution order,
+// including those invoked through CPI such as fills routed by aggregators
+pub fn get_phoenix_instructions(
+    tx: &EncodedTransactionWithStatusMeta,
+) -> anyhow::Result<Vec<PhoenixInstructionInTx>> {
+    let versioned_tx = tx
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow::anyhow!("Failed to decode transaction"))?;
+
+    // Accounts loaded from lookup tables follow the static keys, writable ones first
+    let mut account_keys = versioned_tx.message.static_account_keys().to_vec();
+    let meta = tx.meta.as_ref();
+    let loaded_addresses: Option<UiLoadedAddresses> =
+        meta.and_then(|meta| meta.loaded_addresses.clone().into());
+    if let Some(loaded) = loaded_addresses {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(key.parse()?);
+        }
+    }
+
+    // Top level instructions in order, each followed by the instructions it invoked
+    let inner_instructions: Option<Vec<UiInnerInstructions>> =
+        meta.and_then(|meta| meta.inner_instructions.clone().into());
+    let inner_instructions = inner_instructions.unwrap_or_default();
+    let mut compiled_instructions = vec![];
+    for (index, ix) in versioned_tx.message.instructions().iter().enumerate() {
+        compiled_instructions.push((
+            index,
+            None,
+            ix.program_id_index,
+            ix.accounts.clone(),
+            ix.data.clone(),
+        ));
+        let inner = inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == index)
+            .flat_map(|inner| inner.instructions.iter());
+        for (inner_index, ix) in inner.enumerate() {
+            // Transactions fetched as base64 report inner instructions in compiled form
+            if let UiInstruction::Compiled(ix) = ix {
+                compiled_instructions.push((
+                    index,
+                    Some(inner_index),
+                    ix.program_id_index,
+                    ix.accounts.clone(),
+                    bs58::decode(&ix.data).into_vec()?,
+                ));
+            }
+        }
+    }
+
+    let mut instructions = vec![];
+    for (index, inner_index, program_id_index, accounts, data) in compiled_instructions {
+        if account_keys.get(program_id_index as usize) != Some(&phoenix::id()) {
+            continue;
+        }
+        let (tag, args) = match data.split_first() {
+            Some(split) => split,
+            None => continue,
+        };
+        let instruction = match PhoenixInstruction::try_from(*tag) {
+            Ok(instruction) => instruction,
+            Err(_) => continue,
+        };
+        instructions.push(PhoenixInstructionInTx {
+            index,
+            inner_index,
+            instruction,
+            // Phoenix instructions take the program, the log authority, then the market
+            market: accounts
+                .get(2)
+                .and_then(|i| account_keys.get(*i as usize))
+                .copied(),
+            args: args.to_vec(),
+        });
+    }
+    Ok(instructions)
+}
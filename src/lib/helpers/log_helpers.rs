@@ -0,0 +1,94 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+use ellipsis_client::EllipsisClient;
+use phoenix_sdk::sdk_client::SDKClient;
+use solana_client::client_error::Result as ClientResult;
+use solana_client::http_sender::HttpSender;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Keypair;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+// Diagnostics go to stderr so they never mix with command results. Verbosity raises the CLI's own
+// level from warnings to info (-v) and debug (-vv). Dependencies stay at warn unless RUST_LOG is set.
+pub fn init_logging(format: LogFormat, verbosity: u8) {
+    let level = match verbosity {
+        0 => "warn",
+        1 => "info",
+        _ => "debug",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(format!(
+            "warn,phoenix_cli={0},phoenix_cli_processor={0}",
+            level
+        ))
+    });
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Pretty => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+// HTTP transport that logs every request with its latency at debug level. Transaction sends and
+// confirmation polling go through the same transport, so they are covered too.
+struct TimedSender(HttpSender);
+
+#[async_trait]
+impl RpcSender for TimedSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let start = Instant::now();
+        let result = self.0.send(request, params).await;
+        tracing::debug!(
+            target: "phoenix_cli_processor::rpc",
+            method = %request,
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            ok = result.is_ok(),
+            "rpc request"
+        );
+        result
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.0.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.0.url()
+    }
+}
+
+pub fn new_timed_rpc_client(url: &str, commitment: CommitmentConfig) -> RpcClient {
+    RpcClient::new_sender(
+        TimedSender(HttpSender::new(url.to_string())),
+        RpcClientConfig::with_commitment(commitment),
+    )
+}
+
+// Same as SDKClient::new, but with RPC requests logged through the timed transport
+pub async fn new_timed_sdk_client(
+    payer: &Keypair,
+    url: &str,
+    commitment: CommitmentConfig,
+) -> anyhow::Result<SDKClient> {
+    let client = EllipsisClient::from_rpc(new_timed_rpc_client(url, commitment), payer)?;
+    SDKClient::new_from_ellipsis_client(client).await
+}
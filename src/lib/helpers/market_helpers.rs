@@ -1,11 +1,14 @@
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use ellipsis_client::EllipsisClient;
-use phoenix::program::{load_with_dispatch, status::SeatApprovalStatus, MarketHeader};
+use phoenix::program::{
+    get_vault_address, load_with_dispatch, status::SeatApprovalStatus, MarketHeader,
+};
+use phoenix::quantities::WrapperU64;
 use phoenix::state::markets::FIFOOrderId;
 use phoenix::state::markets::FIFORestingOrder;
-use phoenix::state::markets::{Ladder, Market};
-use phoenix::state::OrderPacket;
+use phoenix::state::markets::{Ladder, LadderOrder, Market, RestingOrder};
+use phoenix::state::{OrderPacket, Side};
 
 use phoenix_sdk::sdk_client::*;
 use phoenix_seat_manager::get_seat_manager_address;
@@ -17,6 +20,7 @@ use solana_sdk::account::Account;
 use solana_sdk::clock::Clock;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::keccak;
+use solana_sdk::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::sysvar;
 use std::collections::BTreeMap;
@@ -28,6 +32,79 @@ pub fn get_discriminant(type_name: &str) -> anyhow::Result<u64> {
     ))
 }
 
+// Convert a price in quote units per base unit to the nearest price in ticks
+pub fn float_price_to_ticks(meta: &MarketMetadata, price: f64) -> u64 {
+    float_price_to_fractional_ticks(meta, price).round() as u64
+}
+
+// Convert a price to ticks for a resting order, rounding bids down and asks up so the order is
+// never priced more aggressively than requested
+pub fn float_price_to_passive_ticks(meta: &MarketMetadata, price: f64, side: Side) -> u64 {
+    let ticks = float_price_to_fractional_ticks(meta, price);
+    match side {
+        Side::Bid => ticks.floor() as u64,
+        Side::Ask => ticks.ceil() as u64,
+    }
+}
+
+// Convert a price in ticks to quote units per base unit
+pub fn ticks_to_float_price(meta: &MarketMetadata, price_in_ticks: u64) -> f64 {
+    price_in_ticks as f64 * meta.tick_size_in_quote_atoms_per_base_unit as f64
+        / (10f64.powi(meta.quote_decimals as i32) * meta.raw_base_units_per_base_unit as f64)
+}
+
+// Number of base units in one base lot
+pub fn get_raw_base_units_per_base_lot(meta: &MarketMetadata) -> f64 {
+    meta.base_atoms_per_base_lot as f64 / meta.base_atoms_per_raw_base_unit as f64
+}
+
+// Size in base units and quote notional of a fill or book level
+pub fn get_base_and_quote_amounts(
+    meta: &MarketMetadata,
+    price_in_ticks: u64,
+    base_lots: u64,
+) -> (f64, f64) {
+    let base = base_lots as f64 * get_raw_base_units_per_base_lot(meta);
+    (base, ticks_to_float_price(meta, price_in_ticks) * base)
+}
+
+// Convert an amount in token units, written as a decimal such as 12.5, to atoms exactly. Scaling a
+// float instead can land an atom short, e.g. 0.29 * 100.0 is 28.999...
+pub fn decimal_to_atoms(amount: &str, decimals: u32) -> anyhow::Result<u64> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid amount {}: expected a non-negative decimal with at most {} decimals",
+            amount,
+            decimals
+        )
+    };
+    let trimmed = amount.trim();
+    let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    let fraction = fraction.trim_end_matches('0');
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+        || fraction.len() > decimals as usize
+    {
+        return Err(invalid());
+    }
+    let parse = |digits: &str| match digits {
+        "" => Ok(0),
+        digits => digits.parse::<u64>().map_err(|_| invalid()),
+    };
+    let fraction_atoms = parse(&format!("{:0<1$}", fraction, decimals as usize))?;
+    10u64
+        .checked_pow(decimals)
+        .and_then(|scale| parse(whole).ok()?.checked_mul(scale))
+        .and_then(|whole_atoms| whole_atoms.checked_add(fraction_atoms))
+        .ok_or_else(invalid)
+}
+
+fn float_price_to_fractional_ticks(meta: &MarketMetadata, price: f64) -> f64 {
+    price * 10f64.powi(meta.quote_decimals as i32) * meta.raw_base_units_per_base_unit as f64
+        / meta.tick_size_in_quote_atoms_per_base_unit as f64
+}
+
 pub async fn get_seat_status(
     sdk: &SDKClient,
     seat_key: &Pubkey,
@@ -118,14 +195,26 @@ pub async fn get_book_levels(
     client: &EllipsisClient,
     levels: u64,
 ) -> anyhow::Result<Ladder> {
+    Ok(get_book_levels_with_slot(market_pubkey, client, levels)
+        .await?
+        .0)
+}
+
+// Same as get_book_levels, along with the slot the market account was read at
+pub async fn get_book_levels_with_slot(
+    market_pubkey: &Pubkey,
+    client: &EllipsisClient,
+    levels: u64,
+) -> anyhow::Result<(Ladder, u64)> {
     // Get market account
-    let mut market_and_clock = client
+    let response = client
         .get_multiple_accounts_with_commitment(
             &[*market_pubkey, sysvar::clock::id()],
             CommitmentConfig::confirmed(),
         )
-        .await?
-        .value;
+        .await?;
+    let slot = response.context.slot;
+    let mut market_and_clock = response.value;
 
     let market_account_data = market_and_clock
         .remove(0)
@@ -147,16 +236,300 @@ pub async fn get_book_levels(
     // Derserialize data and load into correct type
     let market = load_with_dispatch(&header.market_size_params, market_bytes)?.inner;
 
-    Ok(market.get_ladder_with_expiration(
+    let ladder = market.get_ladder_with_expiration(
         levels,
         Some(clock.slot),
         Some(clock.unix_timestamp as u64),
+    );
+    Ok((ladder, slot))
+}
+
+pub async fn get_market_data_and_clock(
+    client: &EllipsisClient,
+    market_pubkey: &Pubkey,
+) -> anyhow::Result<(Vec<u8>, Clock)> {
+    let mut market_and_clock = client
+        .get_multiple_accounts_with_commitment(
+            &[*market_pubkey, sysvar::clock::id()],
+            CommitmentConfig::confirmed(),
+        )
+        .await?
+        .value;
+
+    let market_account_data = market_and_clock
+        .remove(0)
+        .ok_or_else(|| anyhow::Error::msg("Market account not found"))?
+        .data;
+
+    let clock_account_data = market_and_clock
+        .remove(0)
+        .ok_or_else(|| anyhow::Error::msg("Clock account not found"))?
+        .data;
+
+    let clock: Clock = bincode::deserialize(&clock_account_data)
+        .map_err(|_| anyhow::Error::msg("Error deserializing clock"))?;
+
+    Ok((market_account_data, clock))
+}
+
+// Split the market account into its header and the market loaded into the correct type
+pub fn load_market(
+    market_account_data: &[u8],
+) -> anyhow::Result<(
+    &MarketHeader,
+    &dyn Market<Pubkey, FIFOOrderId, FIFORestingOrder, OrderPacket>,
+)> {
+    let (header_bytes, market_bytes) = market_account_data.split_at(size_of::<MarketHeader>());
+    let header: &MarketHeader = bytemuck::try_from_bytes(header_bytes)
+        .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
+    let market = load_with_dispatch(&header.market_size_params, market_bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to load market. Error {:?}", e))?
+        .inner;
+    Ok((header, market))
+}
+
+// Get the best bid and ask prices in ticks, ignoring expired orders
+pub fn get_best_bid_and_ask(
+    market: &dyn Market<Pubkey, FIFOOrderId, FIFORestingOrder, OrderPacket>,
+    clock: &Clock,
+) -> (Option<u64>, Option<u64>) {
+    let best_price = |side: Side| {
+        market
+            .get_book(side)
+            .iter()
+            .find(|(_, order)| !order.is_expired(clock.slot, clock.unix_timestamp as u64))
+            .map(|(order_id, _)| order_id.price_in_ticks.as_u64())
+    };
+    (best_price(Side::Bid), best_price(Side::Ask))
+}
+
+// Aggregate the book into a ladder, skipping expired orders and any order for which `exclude`
+// returns true
+pub fn get_ladder_from_book(
+    market: &dyn Market<Pubkey, FIFOOrderId, FIFORestingOrder, OrderPacket>,
+    clock: &Clock,
+    exclude: impl Fn(&FIFORestingOrder) -> bool,
+) -> Ladder {
+    let get_levels = |side: Side| {
+        let mut levels: Vec<LadderOrder> = vec![];
+        for (order_id, order) in market.get_book(side).iter() {
+            if order.is_expired(clock.slot, clock.unix_timestamp as u64) || exclude(order) {
+                continue;
+            }
+            let price_in_ticks = order_id.price_in_ticks.as_u64();
+            match levels.last_mut() {
+                Some(level) if level.price_in_ticks == price_in_ticks => {
+                    level.size_in_base_lots += order.num_base_lots.as_u64();
+                }
+                _ => levels.push(LadderOrder {
+                    price_in_ticks,
+                    size_in_base_lots: order.num_base_lots.as_u64(),
+                }),
+            }
+        }
+        levels
+    };
+    Ladder {
+        bids: get_levels(Side::Bid),
+        asks: get_levels(Side::Ask),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BookGrouping {
+    // Buckets a fixed number of ticks wide
+    Ticks(u64),
+    // Buckets a number of basis points of the midpoint wide
+    Bps(u64),
+}
+
+// Collapse the ladder's levels into price buckets. Bids are bucketed down and asks up, so each
+// bucket is labeled with its price furthest from the spread.
+pub fn group_ladder(ladder: &Ladder, grouping: BookGrouping) -> Ladder {
+    let width = match grouping {
+        BookGrouping::Ticks(ticks) => ticks,
+        BookGrouping::Bps(bps) => {
+            let reference = match (ladder.bids.first(), ladder.asks.first()) {
+                (Some(bid), Some(ask)) => (bid.price_in_ticks + ask.price_in_ticks) / 2,
+                (Some(level), None) | (None, Some(level)) => level.price_in_ticks,
+                (None, None) => 0,
+            };
+            (reference * bps + 9_999) / 10_000
+        }
+    }
+    .max(1);
+    let group = |levels: &[LadderOrder], side: Side| {
+        let mut grouped: Vec<LadderOrder> = vec![];
+        for level in levels.iter() {
+            let price_in_ticks = match side {
+                Side::Bid => level.price_in_ticks / width * width,
+                Side::Ask => (level.price_in_ticks + width - 1) / width * width,
+            };
+            match grouped.last_mut() {
+                Some(bucket) if bucket.price_in_ticks == price_in_ticks => {
+                    bucket.size_in_base_lots += level.size_in_base_lots;
+                }
+                _ => grouped.push(LadderOrder {
+                    price_in_ticks,
+                    size_in_base_lots: level.size_in_base_lots,
+                }),
+            }
+        }
+        grouped
+    };
+    Ladder {
+        bids: group(&ladder.bids, Side::Bid),
+        asks: group(&ladder.asks, Side::Ask),
+    }
+}
+
+// Get the levels within `bps` basis points of the midpoint on each side of the ladder, best first.
+// Returns None if either side of the book is empty.
+pub fn get_ladder_levels_within_bps(
+    ladder: &Ladder,
+    bps: u64,
+) -> Option<(&[LadderOrder], &[LadderOrder])> {
+    let best_bid = ladder.bids.first()?.price_in_ticks as f64;
+    let best_ask = ladder.asks.first()?.price_in_ticks as f64;
+    let mid = (best_bid + best_ask) / 2.0;
+    let band = mid * bps as f64 / 10_000.0;
+    let bid_count = ladder
+        .bids
+        .iter()
+        .take_while(|level| level.price_in_ticks as f64 >= mid - band)
+        .count();
+    let ask_count = ladder
+        .asks
+        .iter()
+        .take_while(|level| level.price_in_ticks as f64 <= mid + band)
+        .count();
+    Some((&ladder.bids[..bid_count], &ladder.asks[..ask_count]))
+}
+
+// Sum the base lots resting within `bps` basis points of the midpoint on each side of the ladder.
+// Returns None if either side of the book is empty.
+pub fn get_ladder_depth_within_bps(ladder: &Ladder, bps: u64) -> Option<(u64, u64)> {
+    let (bids, asks) = get_ladder_levels_within_bps(ladder, bps)?;
+    let total = |levels: &[LadderOrder]| -> u64 {
+        levels.iter().map(|level| level.size_in_base_lots).sum()
+    };
+    Some((total(bids), total(asks)))
+}
+
+pub struct VaultReconciliation {
+    pub slot: u64,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    // Vault token balances, in atoms
+    pub base_vault_balance: u64,
+    pub quote_vault_balance: u64,
+    // Balances implied by market state (trader states plus uncollected fees), in atoms
+    pub base_expected: u64,
+    pub quote_expected: u64,
+}
+
+impl VaultReconciliation {
+    pub fn base_drift(&self) -> i128 {
+        self.base_vault_balance as i128 - self.base_expected as i128
+    }
+
+    pub fn quote_drift(&self) -> i128 {
+        self.quote_vault_balance as i128 - self.quote_expected as i128
+    }
+}
+
+// Token balance of a vault account, in atoms
+pub fn unpack_vault_balance(vault: &Pubkey, account: Option<&Account>) -> anyhow::Result<u64> {
+    let account = account.ok_or_else(|| anyhow::anyhow!("Vault {} not found", vault))?;
+    Ok(spl_token::state::Account::unpack(&account.data)?.amount)
+}
+
+// Get the market's base and quote vault addresses along with their token balances, in atoms
+pub async fn get_vault_balances(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    meta: &MarketMetadata,
+) -> anyhow::Result<((Pubkey, u64), (Pubkey, u64))> {
+    let base_vault = get_vault_address(market_pubkey, &meta.base_mint).0;
+    let quote_vault = get_vault_address(market_pubkey, &meta.quote_mint).0;
+    let accounts = sdk
+        .client
+        .get_multiple_accounts(&[base_vault, quote_vault])
+        .await?;
+    Ok((
+        (
+            base_vault,
+            unpack_vault_balance(&base_vault, accounts[0].as_ref())?,
+        ),
+        (
+            quote_vault,
+            unpack_vault_balance(&quote_vault, accounts[1].as_ref())?,
+        ),
     ))
 }
 
+// Compare the market's vault balances against the sum of all trader states plus uncollected fees.
+// The market and both vaults are fetched in a single call so they reflect the same slot.
+pub async fn get_vault_reconciliation(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+) -> anyhow::Result<VaultReconciliation> {
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    let header = get_market_header(sdk, market_pubkey).await?;
+    let base_vault = header.base_params.vault_key;
+    let quote_vault = header.quote_params.vault_key;
+
+    let response = sdk
+        .client
+        .get_multiple_accounts_with_commitment(
+            &[*market_pubkey, base_vault, quote_vault],
+            CommitmentConfig::confirmed(),
+        )
+        .await?;
+    let accounts = response.value;
+    let market_account_data = &accounts[0]
+        .as_ref()
+        .ok_or_else(|| anyhow::Error::msg("Market account not found"))?
+        .data;
+    let base_vault_balance = unpack_vault_balance(&base_vault, accounts[1].as_ref())?;
+    let quote_vault_balance = unpack_vault_balance(&quote_vault, accounts[2].as_ref())?;
+
+    let (_, market) = load_market(market_account_data)?;
+    let (base_lots, quote_lots) =
+        market
+            .get_registered_traders()
+            .iter()
+            .fold((0u64, 0u64), |(base, quote), (_, state)| {
+                (
+                    base + state.base_lots_locked.as_u64() + state.base_lots_free.as_u64(),
+                    quote + state.quote_lots_locked.as_u64() + state.quote_lots_free.as_u64(),
+                )
+            });
+    let quote_lots = quote_lots + market.get_uncollected_fee_amount().as_u64();
+
+    Ok(VaultReconciliation {
+        slot: response.context.slot,
+        base_vault,
+        quote_vault,
+        base_vault_balance,
+        quote_vault_balance,
+        base_expected: base_lots * meta.base_atoms_per_base_lot,
+        quote_expected: quote_lots * meta.quote_atoms_per_quote_lot,
+    })
+}
+
 pub async fn get_all_approved_seats_for_market(
     sdk: &SDKClient,
     market: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Account)>> {
+    get_seats_for_market(sdk, market, Some(SeatApprovalStatus::Approved)).await
+}
+
+// Get the seats on a market, optionally only those with the given approval status
+pub async fn get_seats_for_market(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    status: Option<SeatApprovalStatus>,
 ) -> anyhow::Result<Vec<(Pubkey, Account)>> {
     // Get discriminant for seat account
     let seat_account_discriminant = get_discriminant("phoenix::program::accounts::Seat")?;
@@ -170,14 +543,16 @@ pub async fn get_all_approved_seats_for_market(
         ]
         .concat(),
     ));
-
-    let approved = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
-        72,
-        SeatApprovalStatus::Approved.try_to_vec()?,
-    ));
+    let mut filters = vec![memcmp];
+    if let Some(status) = status {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            72,
+            status.try_to_vec()?,
+        )));
+    }
 
     let config = RpcProgramAccountsConfig {
-        filters: Some(vec![memcmp, approved]),
+        filters: Some(filters),
         account_config: RpcAccountInfoConfig {
             encoding: Some(UiAccountEncoding::Base64),
             commitment: Some(CommitmentConfig::confirmed()),
@@ -222,3 +597,81 @@ pub async fn get_seat_manager_data_with_pubkey(
 
     Ok(*seat_manager_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_to_atoms() {
+        assert_eq!(decimal_to_atoms("0.29", 2).unwrap(), 29);
+        assert_eq!(decimal_to_atoms("12.5", 6).unwrap(), 12_500_000);
+        assert_eq!(decimal_to_atoms("3", 9).unwrap(), 3_000_000_000);
+        assert_eq!(decimal_to_atoms(".5", 1).unwrap(), 5);
+        assert_eq!(decimal_to_atoms("1.500", 2).unwrap(), 150);
+        assert_eq!(decimal_to_atoms(&0.1f64.to_string(), 6).unwrap(), 100_000);
+        assert!(decimal_to_atoms("1.234", 2).is_err());
+        assert!(decimal_to_atoms("-1", 6).is_err());
+        assert!(decimal_to_atoms("", 6).is_err());
+        assert!(decimal_to_atoms("1e3", 6).is_err());
+        assert!(decimal_to_atoms("18446744073709551616", 0).is_err());
+    }
+
+    fn levels(levels: &[(u64, u64)]) -> Vec<LadderOrder> {
+        levels
+            .iter()
+            .map(|&(price_in_ticks, size_in_base_lots)| LadderOrder {
+                price_in_ticks,
+                size_in_base_lots,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_ladder_by_ticks() {
+        let ladder = Ladder {
+            bids: levels(&[(105, 1), (101, 2), (99, 3), (90, 4)]),
+            asks: levels(&[(106, 1), (110, 2), (111, 3)]),
+        };
+        // Bids are bucketed down and asks up, so no bucket crosses the spread
+        assert_eq!(
+            group_ladder(&ladder, BookGrouping::Ticks(10)),
+            Ladder {
+                bids: levels(&[(100, 3), (90, 7)]),
+                asks: levels(&[(110, 3), (120, 3)]),
+            }
+        );
+        // A zero width leaves the ladder as is
+        assert_eq!(group_ladder(&ladder, BookGrouping::Ticks(0)), ladder);
+    }
+
+    #[test]
+    fn test_group_ladder_by_bps() {
+        let ladder = Ladder {
+            bids: levels(&[(9_990, 1), (9_975, 2)]),
+            asks: levels(&[(10_010, 1), (10_025, 2), (10_026, 1)]),
+        };
+        // 25 bps of the 10,000 tick midpoint is 25 ticks
+        assert_eq!(
+            group_ladder(&ladder, BookGrouping::Bps(25)),
+            Ladder {
+                bids: levels(&[(9_975, 3)]),
+                asks: levels(&[(10_025, 3), (10_050, 1)]),
+            }
+        );
+        // One-sided books use their best level, and empty books stay empty
+        let bids_only = Ladder {
+            bids: ladder.bids.clone(),
+            asks: vec![],
+        };
+        assert_eq!(
+            group_ladder(&bids_only, BookGrouping::Bps(25)).bids,
+            levels(&[(9_975, 3)])
+        );
+        let empty = Ladder {
+            bids: vec![],
+            asks: vec![],
+        };
+        assert_eq!(group_ladder(&empty, BookGrouping::Bps(25)), empty);
+    }
+}
@@ -1,3 +1,12 @@
+pub mod archive_helpers;
+pub mod cache_helpers;
+pub mod color_helpers;
 pub mod devnet_helpers;
+pub mod error_helpers;
+pub mod history_helpers;
+pub mod instruction_helpers;
+pub mod log_helpers;
 pub mod market_helpers;
+pub mod output_helpers;
 pub mod print_helpers;
+pub mod progress_helpers;
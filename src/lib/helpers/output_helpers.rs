@@ -0,0 +1,615 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{SecondsFormat, TimeZone};
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use ellipsis_client::EllipsisClient;
+use phoenix::program::MarketHeader;
+use phoenix::state::markets::{Ladder, LadderOrder};
+use phoenix::state::TraderState;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcBlockhash};
+use solana_sdk::pubkey::Pubkey;
+
+use super::print_helpers::LadderLevelEntry;
+
+// How command results are printed. Text is the human readable default; the other formats
+// serialize the same results for scripts and bots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    // Only book and trader listings are tabular enough to export as CSV
+    Csv,
+    // Aligned tables for multi-row listings: traders, markets, and seats
+    Table,
+    // For embedding market parameters into config files
+    Yaml,
+    // One JSON object per line, printed as each event is processed, for stream parsers
+    Jsonl,
+    // Markdown tables for pasting reports into issues and docs
+    Markdown,
+    // Rendered through the handlebars template loaded with --template
+    #[value(skip)]
+    Template,
+}
+
+pub const BOOK_CSV_HEADER: &str = "price,size,side";
+pub const TRADERS_CSV_HEADER: &str = "trader,base_locked,base_free,quote_locked,quote_free";
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+static CSV_METADATA: AtomicBool = AtomicBool::new(false);
+
+pub fn set_csv_metadata(csv_metadata: bool) {
+    CSV_METADATA.store(csv_metadata, Ordering::Relaxed);
+}
+
+pub fn is_csv_metadata() -> bool {
+    CSV_METADATA.load(Ordering::Relaxed)
+}
+
+static REPORT_TEMPLATE: OnceLock<String> = OnceLock::new();
+
+// Load the handlebars template that OutputFormat::Template results are rendered through
+pub fn load_report_template(path: &str) -> anyhow::Result<()> {
+    let template = std::fs::read_to_string(shellexpand::tilde(path).to_string())?;
+    REPORT_TEMPLATE
+        .set(template)
+        .map_err(|_| anyhow::anyhow!("Report template already loaded"))
+}
+
+struct TimeDisplay {
+    timezone: Tz,
+    format: Option<String>,
+}
+
+static TIME_DISPLAY: OnceLock<TimeDisplay> = OnceLock::new();
+
+// Set the zone and strftime format timestamps are rendered in. Invalid formats are rejected here,
+// since chrono panics when rendering them
+pub fn set_time_display(timezone: Tz, format: Option<String>) -> anyhow::Result<()> {
+    if let Some(format) = &format {
+        if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+            return Err(anyhow::anyhow!("Invalid time format: {}", format));
+        }
+    }
+    TIME_DISPLAY
+        .set(TimeDisplay { timezone, format })
+        .map_err(|_| anyhow::anyhow!("Time display already set"))
+}
+
+// Render a unix timestamp in the configured zone, as ISO-8601 in UTC by default
+pub fn format_timestamp(unix_timestamp: i64) -> String {
+    let display = TIME_DISPLAY.get();
+    let timezone = display.map(|d| d.timezone).unwrap_or(Tz::UTC);
+    let time = match timezone.timestamp_opt(unix_timestamp, 0).single() {
+        Some(time) => time,
+        None => return unix_timestamp.to_string(),
+    };
+    match display.and_then(|d| d.format.as_deref()) {
+        Some(format) => time.format(format).to_string(),
+        None => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+    }
+}
+
+static RAW_UNITS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_raw_units(raw_units: bool) {
+    RAW_UNITS.store(raw_units, Ordering::Relaxed);
+}
+
+pub fn is_raw_units() -> bool {
+    RAW_UNITS.load(Ordering::Relaxed)
+}
+
+// Price in quote units per base unit, or the on-chain price in ticks with --raw-units
+pub fn display_price(sdk: &SDKClient, market: &Pubkey, price_in_ticks: u64) -> anyhow::Result<f64> {
+    if is_raw_units() {
+        return Ok(price_in_ticks as f64);
+    }
+    sdk.ticks_to_float_price(market, price_in_ticks)
+}
+
+// Size in base units, or the on-chain size in base lots with --raw-units
+pub fn display_base_size(sdk: &SDKClient, market: &Pubkey, base_lots: u64) -> anyhow::Result<f64> {
+    if is_raw_units() {
+        return Ok(base_lots as f64);
+    }
+    let meta = sdk.get_market_metadata_from_cache(market)?;
+    Ok(base_lots as f64 * meta.base_atoms_per_base_lot as f64
+        / meta.base_atoms_per_raw_base_unit as f64)
+}
+
+// Quote notional of a size at a price, in quote units, or in quote lots with --raw-units
+pub fn display_quote_notional(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    price_in_ticks: u64,
+    base_lots: u64,
+) -> anyhow::Result<f64> {
+    let meta = sdk.get_market_metadata_from_cache(market)?;
+    if is_raw_units() {
+        return Ok((price_in_ticks as u128 * base_lots as u128) as f64
+            * meta.tick_size_in_quote_atoms_per_base_unit as f64
+            / meta.quote_atoms_per_quote_lot as f64
+            / meta.num_base_lots_per_base_unit as f64);
+    }
+    Ok(sdk.ticks_to_float_price(market, price_in_ticks)?
+        * display_base_size(sdk, market, base_lots)?)
+}
+
+// Token amount in token units, or the on-chain amount in atoms with --raw-units
+pub fn format_atoms(atoms: u64, decimals: u32) -> String {
+    if is_raw_units() {
+        return atoms.to_string();
+    }
+    get_decimal_string(atoms, decimals)
+}
+
+pub fn format_base_lots(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    base_lots: u64,
+) -> anyhow::Result<String> {
+    if is_raw_units() {
+        return Ok(base_lots.to_string());
+    }
+    let meta = sdk.get_market_metadata_from_cache(market)?;
+    Ok(get_decimal_string(
+        sdk.base_lots_to_base_atoms(market, base_lots)?,
+        meta.base_decimals,
+    ))
+}
+
+pub fn format_quote_lots(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    quote_lots: u64,
+) -> anyhow::Result<String> {
+    if is_raw_units() {
+        return Ok(quote_lots.to_string());
+    }
+    let meta = sdk.get_market_metadata_from_cache(market)?;
+    Ok(get_decimal_string(
+        sdk.quote_lots_to_quote_atoms(market, quote_lots)?,
+        meta.quote_decimals,
+    ))
+}
+
+// Print an informational message that isn't part of a command's result, like progress. These go
+// to stderr so that stdout only carries results, and are suppressed entirely by --quiet.
+#[macro_export]
+macro_rules! print_info {
+    ($($arg:tt)*) => {
+        if !$crate::helpers::output_helpers::is_quiet() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Print part of a command's human readable output, on stderr when stdout is reserved for machine
+// readable output such as the instructions printed with --ix-only
+#[macro_export]
+macro_rules! print_summary {
+    ($to_stderr:expr, $($arg:tt)*) => {
+        if $to_stderr {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub fn print_json<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+// Template engine shared by --template reports and the per-row --format templates. Strict, so a
+// misspelled field is an error, and without HTML escaping, since the output is plain text.
+pub fn new_template_engine() -> handlebars::Handlebars<'static> {
+    let mut handlebars = handlebars::Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars
+}
+
+// Render a result through the report template. The result's JSON form is the template's data, so
+// the fields available are the same as with --output json.
+pub fn print_template<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    let template = REPORT_TEMPLATE
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("No report template loaded"))?;
+    print!(
+        "{}",
+        new_template_engine().render_template(template, value)?
+    );
+    Ok(())
+}
+
+pub fn print_yaml<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    print!("{}", serde_yaml::to_string(value)?);
+    Ok(())
+}
+
+// Where and when an export was taken, so snapshots can be audited and compared later
+#[derive(Serialize, Clone, Debug)]
+pub struct ExportMetadata {
+    pub slot: u64,
+    pub blockhash: String,
+    pub rpc_endpoint: String,
+    pub cli_version: String,
+}
+
+impl ExportMetadata {
+    // The slot is the context slot of the response the exported data was read from. The blockhash
+    // is the latest one at export time.
+    pub async fn fetch(client: &EllipsisClient, slot: u64) -> anyhow::Result<Self> {
+        let response: Response<RpcBlockhash> = client
+            .send(
+                RpcRequest::GetLatestBlockhash,
+                serde_json::json!([client.commitment()]),
+            )
+            .await?;
+        Ok(Self {
+            slot,
+            blockhash: response.value.blockhash,
+            rpc_endpoint: get_rpc_endpoint_origin(&client.url()),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    // Comment line placed above the column header of CSV exports with --csv-metadata
+    pub fn print_csv_comment(&self) {
+        println!(
+            "# slot={} blockhash={} rpc_endpoint={} cli_version={}",
+            self.slot, self.blockhash, self.rpc_endpoint, self.cli_version
+        );
+    }
+}
+
+// Only the scheme and host of the RPC URL are recorded, since paths and query strings often
+// carry API keys
+fn get_rpc_endpoint_origin(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => match url.port() {
+            Some(port) => format!(
+                "{}://{}:{}",
+                url.scheme(),
+                url.host_str().unwrap_or(""),
+                port
+            ),
+            None => format!("{}://{}", url.scheme(), url.host_str().unwrap_or("")),
+        },
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MarketSummaryOutput {
+    pub market: String,
+    pub base_symbol: Option<String>,
+    pub quote_symbol: Option<String>,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub authority: String,
+    // Only set when stats are requested. Prices and values are in quote units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<MarketStatsOutput>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct MarketStatsOutput {
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub spread_bps: Option<f64>,
+    // Vault balances valued at the midpoint
+    pub tvl: Option<f64>,
+    // Quote volume over the last 24 hours, only computed when sorting by volume
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_24h: Option<f64>,
+}
+
+impl MarketSummaryOutput {
+    pub fn new(
+        market_pubkey: &Pubkey,
+        header: &MarketHeader,
+        base_symbol: Option<String>,
+        quote_symbol: Option<String>,
+    ) -> Self {
+        Self {
+            market: market_pubkey.to_string(),
+            base_symbol,
+            quote_symbol,
+            base_mint: header.base_params.mint_key.to_string(),
+            quote_mint: header.quote_params.mint_key.to_string(),
+            authority: header.authority.to_string(),
+            stats: None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MarketDetailsOutput {
+    pub market: String,
+    pub base_symbol: Option<String>,
+    pub quote_symbol: Option<String>,
+    pub status: String,
+    pub authority: String,
+    pub sequence_number: u64,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub base_vault_balance: String,
+    pub quote_vault_balance: String,
+    pub raw_base_units_per_base_lot: String,
+    pub quote_units_per_quote_lot: String,
+    pub tick_size_in_quote_units_per_base_unit: String,
+    pub num_base_lots_per_base_unit: u64,
+    pub tick_size_in_quote_atoms_per_base_unit: u64,
+    pub taker_fee_bps: u64,
+    pub fee_recipient: String,
+    pub raw_base_units_per_base_unit: u32,
+    pub successor: String,
+    pub uncollected_fees: String,
+    pub collected_fees: String,
+}
+
+// The static parameters of a market, as written by get-market --export-toml. Balances, fees and
+// status are left out, so the file doesn't go stale as the market trades.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarketConfigOutput {
+    pub market: String,
+    pub base_symbol: Option<String>,
+    pub quote_symbol: Option<String>,
+    pub authority: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub raw_base_units_per_base_lot: String,
+    pub quote_units_per_quote_lot: String,
+    pub tick_size_in_quote_units_per_base_unit: String,
+    pub num_base_lots_per_base_unit: u64,
+    pub tick_size_in_quote_atoms_per_base_unit: u64,
+    pub taker_fee_bps: u64,
+    pub fee_recipient: String,
+    pub raw_base_units_per_base_unit: u32,
+    pub successor: String,
+}
+
+impl From<&MarketDetailsOutput> for MarketConfigOutput {
+    fn from(details: &MarketDetailsOutput) -> Self {
+        Self {
+            market: details.market.clone(),
+            base_symbol: details.base_symbol.clone(),
+            quote_symbol: details.quote_symbol.clone(),
+            authority: details.authority.clone(),
+            base_mint: details.base_mint.clone(),
+            quote_mint: details.quote_mint.clone(),
+            base_vault: details.base_vault.clone(),
+            quote_vault: details.quote_vault.clone(),
+            raw_base_units_per_base_lot: details.raw_base_units_per_base_lot.clone(),
+            quote_units_per_quote_lot: details.quote_units_per_quote_lot.clone(),
+            tick_size_in_quote_units_per_base_unit: details
+                .tick_size_in_quote_units_per_base_unit
+                .clone(),
+            num_base_lots_per_base_unit: details.num_base_lots_per_base_unit,
+            tick_size_in_quote_atoms_per_base_unit: details.tick_size_in_quote_atoms_per_base_unit,
+            taker_fee_bps: details.taker_fee_bps,
+            fee_recipient: details.fee_recipient.clone(),
+            raw_base_units_per_base_unit: details.raw_base_units_per_base_unit,
+            successor: details.successor.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookLevelOutput {
+    pub price: f64,
+    pub size: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trader_present: Option<bool>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookOutput {
+    pub market: String,
+    pub bids: Vec<BookLevelOutput>,
+    pub asks: Vec<BookLevelOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ExportMetadata>,
+}
+
+impl BookOutput {
+    pub fn from_ladder(sdk: &SDKClient, market: &Pubkey, book: &Ladder) -> anyhow::Result<Self> {
+        let levels = |levels: &[LadderOrder]| {
+            levels
+                .iter()
+                .map(|lvl| {
+                    Ok(BookLevelOutput {
+                        price: display_price(sdk, market, lvl.price_in_ticks)?,
+                        size: display_base_size(sdk, market, lvl.size_in_base_lots)?,
+                        trader_present: None,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(Self {
+            market: market.to_string(),
+            bids: levels(&book.bids)?,
+            asks: levels(&book.asks)?,
+            metadata: None,
+        })
+    }
+
+    pub fn from_entries(
+        sdk: &SDKClient,
+        market: &Pubkey,
+        bid_entries: &[LadderLevelEntry],
+        ask_entries: &[LadderLevelEntry],
+    ) -> anyhow::Result<Self> {
+        let levels = |entries: &[LadderLevelEntry]| {
+            entries
+                .iter()
+                .map(|lvl| {
+                    Ok(BookLevelOutput {
+                        price: display_price(sdk, market, lvl.tick)?,
+                        size: display_base_size(sdk, market, lvl.lots)?,
+                        trader_present: Some(lvl.trader_present),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(Self {
+            market: market.to_string(),
+            bids: levels(bid_entries)?,
+            asks: levels(ask_entries)?,
+            metadata: None,
+        })
+    }
+
+    pub fn with_metadata(self, metadata: ExportMetadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..self
+        }
+    }
+
+    // One row per level, bids first. When a trader is given, a trader column is added and filled
+    // in on levels where the trader has an order, and left empty otherwise
+    pub fn print_csv(&self, trader: Option<&Pubkey>) {
+        if let Some(metadata) = &self.metadata {
+            metadata.print_csv_comment();
+        }
+        match trader {
+            Some(_) => println!("{},trader", BOOK_CSV_HEADER),
+            None => println!("{}", BOOK_CSV_HEADER),
+        }
+        for (side, levels) in [("bid", &self.bids), ("ask", &self.asks)] {
+            for level in levels.iter() {
+                match (trader, level.trader_present) {
+                    (Some(trader), Some(true)) => {
+                        println!("{},{},{},{}", level.price, level.size, side, trader)
+                    }
+                    (Some(_), _) => println!("{},{},{},", level.price, level.size, side),
+                    (None, _) => println!("{},{},{}", level.price, level.size, side),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TraderStateOutput {
+    pub trader: String,
+    pub base_locked: String,
+    pub base_free: String,
+    pub quote_locked: String,
+    pub quote_free: String,
+}
+
+impl TraderStateOutput {
+    pub fn new(
+        sdk: &SDKClient,
+        market_pubkey: &Pubkey,
+        trader: &Pubkey,
+        state: &TraderState,
+    ) -> anyhow::Result<Self> {
+        let base = |lots: u64| format_base_lots(sdk, market_pubkey, lots);
+        let quote = |lots: u64| format_quote_lots(sdk, market_pubkey, lots);
+        Ok(Self {
+            trader: trader.to_string(),
+            base_locked: base(state.base_lots_locked.into())?,
+            base_free: base(state.base_lots_free.into())?,
+            quote_locked: quote(state.quote_lots_locked.into())?,
+            quote_free: quote(state.quote_lots_free.into())?,
+        })
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TradersOutput {
+    pub traders: Vec<TraderStateOutput>,
+    pub metadata: ExportMetadata,
+}
+
+pub fn print_traders_csv(traders: &[TraderStateOutput], metadata: Option<&ExportMetadata>) {
+    if let Some(metadata) = metadata {
+        metadata.print_csv_comment();
+    }
+    println!("{}", TRADERS_CSV_HEADER);
+    for trader in traders.iter() {
+        println!(
+            "{},{},{},{},{}",
+            trader.trader,
+            trader.base_locked,
+            trader.base_free,
+            trader.quote_locked,
+            trader.quote_free
+        );
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct OpenOrderOutput {
+    pub market: String,
+    pub side: String,
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    pub price: f64,
+    pub quantity: f64,
+    // None when the order has no slot or time based expiry
+    pub slots_remaining: Option<u64>,
+    pub seconds_remaining: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SeatInfoOutput {
+    pub seat_address: String,
+    pub status: Option<String>,
+    pub base_locked: Option<String>,
+    pub quote_locked: Option<String>,
+    pub has_locked_funds: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MarketStatusOutput {
+    pub market: String,
+    pub status: String,
+    pub sequence_number: u64,
+    pub slot: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SeatManagerOutput {
+    pub seat_manager: String,
+    pub market: String,
+    pub authority: String,
+    pub successor: String,
+    pub num_makers: u64,
+    pub designated_market_makers: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UncollectedRevenueOutput {
+    pub uncollected_fees: BTreeMap<String, f32>,
+    pub total_usd: f32,
+    pub metadata: ExportMetadata,
+}
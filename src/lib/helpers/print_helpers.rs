@@ -1,86 +1,227 @@
+use std::collections::HashMap;
 use std::mem::size_of;
 
 use colored::Colorize;
+use comfy_table::{
+    presets::{ASCII_MARKDOWN, UTF8_FULL},
+    Cell, CellAlignment, ContentArrangement, Table,
+};
+use phoenix::program::load_with_dispatch;
 use phoenix::program::status::MarketStatus;
 use phoenix::program::MarketHeader;
-use phoenix::program::{get_vault_address, load_with_dispatch};
 use phoenix::quantities::WrapperU64;
-use phoenix::state::{markets::Ladder, Side, TraderState};
+use phoenix::state::{
+    markets::{Ladder, LadderOrder},
+    Side, TraderState,
+};
 use phoenix_sdk::sdk_client::*;
-use solana_sdk::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
 
-pub fn print_book(sdk: &SDKClient, market: &Pubkey, book: &Ladder) -> anyhow::Result<()> {
+use super::color_helpers::get_theme;
+use super::market_helpers::get_vault_balances;
+use super::output_helpers::{
+    display_base_size, display_price, display_quote_notional, format_base_lots, format_quote_lots,
+    format_timestamp, is_raw_units, new_template_engine, print_json, print_template, print_yaml,
+    MarketConfigOutput, MarketDetailsOutput, MarketStatsOutput, MarketSummaryOutput, OutputFormat,
+    TraderStateOutput,
+};
+use crate::print_info;
+
+pub fn print_book(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    book: &Ladder,
+    template: Option<&str>,
+    precision: Option<usize>,
+    cumulative: bool,
+) -> anyhow::Result<()> {
     let meta = sdk.get_market_metadata_from_cache(market)?;
-    let raw_base_units_per_base_lot =
-        meta.base_atoms_per_base_lot as f64 / meta.base_atoms_per_raw_base_unit as f64;
 
-    let asks = book.asks.iter().filter_map(|lvl| {
-        Some((
-            sdk.ticks_to_float_price(market, lvl.price_in_ticks).ok()?,
-            lvl.size_in_base_lots as f64 * raw_base_units_per_base_lot,
-        ))
-    });
+    // Running totals are taken outward from the spread, so they always follow the Ladder's order
+    let with_totals = |levels: &[LadderOrder]| {
+        let (mut cum_size, mut cum_quote) = (0.0, 0.0);
+        levels
+            .iter()
+            .filter_map(|lvl| {
+                let price = display_price(sdk, market, lvl.price_in_ticks).ok()?;
+                let size = display_base_size(sdk, market, lvl.size_in_base_lots).ok()?;
+                cum_size += size;
+                cum_quote +=
+                    display_quote_notional(sdk, market, lvl.price_in_ticks, lvl.size_in_base_lots)
+                        .ok()?;
+                Some((price, size, cum_size, cum_quote))
+            })
+            .collect::<Vec<_>>()
+    };
+    let asks = with_totals(&book.asks);
+    let bids = with_totals(&book.bids);
+    let (price_precision, size_precision) = get_book_precisions(meta, precision);
 
-    let bids = book.bids.iter().filter_map(|lvl| {
-        Some((
-            sdk.ticks_to_float_price(market, lvl.price_in_ticks).ok()?,
-            lvl.size_in_base_lots as f64 * raw_base_units_per_base_lot,
-        ))
-    });
-    let price_precision: usize = get_precision(
-        10_u64.pow(meta.quote_decimals) * meta.raw_base_units_per_base_unit as u64
-            / meta.tick_size_in_quote_atoms_per_base_unit,
-    );
-    let size_precision: usize =
-        get_precision(meta.num_base_lots_per_base_unit / meta.raw_base_units_per_base_unit as u64);
-    let bid_strings = bids
-        .into_iter()
-        .map(|(price, size)| {
-            let p = format_float(price, price_precision);
-            let s = format_float(size, size_precision).green();
-            (s, p)
+    if let Some(template) = template {
+        for (side, (price, size, cum_size, cum_quote)) in asks
+            .iter()
+            .rev()
+            .map(|level| ("Ask", level))
+            .chain(bids.iter().map(|level| ("Bid", level)))
+        {
+            println!(
+                "{}",
+                render_template(
+                    template,
+                    &[
+                        ("price", format_float(*price, price_precision)),
+                        ("size", format_float(*size, size_precision)),
+                        ("side", side.to_string()),
+                        ("cum_size", format_float(*cum_size, size_precision)),
+                        ("cum_quote", format_float(*cum_quote, price_precision)),
+                    ],
+                )?
+            );
+        }
+        return Ok(());
+    }
+
+    // Columns read outward from the price: size, then cumulative size and notional if requested
+    let columns = |(_, size, cum_size, cum_quote): &(f64, f64, f64, f64)| {
+        let mut columns = vec![format_float(*size, size_precision)];
+        if cumulative {
+            columns.push(format_float(*cum_size, size_precision));
+            columns.push(format_float(*cum_quote, price_precision));
+        }
+        columns
+    };
+    let bid_columns = bids.iter().map(columns).collect::<Vec<_>>();
+    let ask_columns = asks.iter().rev().map(columns).collect::<Vec<_>>();
+    let num_columns = if cumulative { 3 } else { 1 };
+    let column_widths = (0..num_columns)
+        .map(|i| {
+            bid_columns
+                .iter()
+                .chain(ask_columns.iter())
+                .map(|row| row[i].len())
+                .max()
+                .unwrap_or(0)
+                + 1
         })
         .collect::<Vec<_>>();
+    let price_width = asks
+        .iter()
+        .chain(bids.iter())
+        .map(|(price, ..)| format_float(*price, price_precision).len())
+        .max()
+        .unwrap_or(0);
+    let side_width = column_widths.iter().sum::<usize>() + num_columns - 1;
 
-    let bid_width = bid_strings.iter().map(|(s, _)| s.len()).max().unwrap_or(0) + 1;
+    let theme = get_theme();
+    for ((price, ..), row) in asks.iter().rev().zip(ask_columns.iter()) {
+        let right = row
+            .iter()
+            .zip(column_widths.iter())
+            .map(|(column, width)| format!("{:>width$}", column.color(theme.ask)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!(
+            "{:side_width$} {:>price_width$} {}",
+            "",
+            format_float(*price, price_precision),
+            right
+        );
+    }
+    for ((price, ..), row) in bids.iter().zip(bid_columns.iter()) {
+        let left = row
+            .iter()
+            .zip(column_widths.iter())
+            .rev()
+            .map(|(column, width)| format!("{:>width$}", column.color(theme.bid)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!(
+            "{} {:>price_width$}",
+            left,
+            format_float(*price, price_precision)
+        );
+    }
+    Ok(())
+}
 
-    let ask_strings = asks
-        .into_iter()
-        .rev()
-        .map(|(price, size)| {
-            let p = format_float(price, price_precision);
-            let s = format_float(size, size_precision).red();
-            (p, s)
-        })
-        .collect::<Vec<_>>();
+// Render cumulative depth on each side as horizontal bars, scaled to the terminal width. Asks
+// are printed above bids, both running outward from the spread.
+pub fn print_depth_chart(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    book: &Ladder,
+    precision: Option<usize>,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market)?;
+    let (price_precision, size_precision) = get_book_precisions(meta, precision);
 
-    let price_width = bid_strings
+    let cumulative = |levels: &[LadderOrder]| -> anyhow::Result<Vec<(String, f64)>> {
+        let mut total = 0.0;
+        levels
+            .iter()
+            .map(|lvl| {
+                total += display_base_size(sdk, market, lvl.size_in_base_lots)?;
+                Ok((
+                    format_float(
+                        display_price(sdk, market, lvl.price_in_ticks)?,
+                        price_precision,
+                    ),
+                    total,
+                ))
+            })
+            .collect()
+    };
+    let asks = cumulative(&book.asks)?;
+    let bids = cumulative(&book.bids)?;
+    let max_depth = asks
         .iter()
-        .zip(ask_strings.iter())
-        .map(|(a, b)| a.0.len().max(b.1.len()))
+        .chain(bids.iter())
+        .map(|(_, depth)| *depth)
+        .fold(0.0, f64::max);
+    if max_depth == 0.0 {
+        println!("Book is empty");
+        return Ok(());
+    }
+
+    let price_width = asks
+        .iter()
+        .chain(bids.iter())
+        .map(|(price, _)| price.len())
         .max()
         .unwrap_or(0);
+    let size_width = format_float(max_depth, size_precision).len();
+    let bar_width = get_terminal_width()
+        .saturating_sub(price_width + size_width + 4)
+        .max(10);
 
-    let ask_width = ask_strings.iter().map(|(_, s)| s.len()).max().unwrap_or(0) + 1;
-
-    for (price, size) in ask_strings {
-        let str = format!(
-            "{:bid_width$} {:>price_width$} {:>ask_width$}",
-            "", price, size
+    let theme = get_theme();
+    let print_level = |price: &str, depth: f64, color: colored::Color| {
+        let bar = "█".repeat(((depth / max_depth) * bar_width as f64).round().max(1.0) as usize);
+        println!(
+            "{:>price_width$} | {} {}",
+            price,
+            bar.color(color),
+            format_float(depth, size_precision)
         );
-        println!("{}", str);
+    };
+    for (price, depth) in asks.iter().rev() {
+        print_level(price, *depth, theme.ask);
     }
-    for (size, price) in bid_strings {
-        let str = format!(
-            "{:>bid_width$} {:>price_width$} {:ask_width$}",
-            size, price, ""
-        );
-        println!("{}", str);
+    for (price, depth) in bids.iter() {
+        print_level(price, *depth, theme.bid);
     }
     Ok(())
 }
 
+// Width of the attached terminal, falling back to $COLUMNS and then 80 columns when piped
+fn get_terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80)
+}
+
 pub struct LadderLevelEntry {
     pub tick: u64,
     pub lots: u64,
@@ -92,38 +233,57 @@ pub fn print_book_with_trader(
     market: &Pubkey,
     bid_entries: &[LadderLevelEntry],
     ask_entries: &[LadderLevelEntry],
+    template: Option<&str>,
+    precision: Option<usize>,
 ) -> anyhow::Result<()> {
     let meta = sdk.get_market_metadata_from_cache(market)?;
-    let raw_base_units_per_base_lot =
-        meta.base_atoms_per_base_lot as f64 / meta.base_atoms_per_raw_base_unit as f64;
 
     let asks = ask_entries.iter().filter_map(|lvl| {
         Some((
-            sdk.ticks_to_float_price(market, lvl.tick).ok()?,
-            lvl.lots as f64 * raw_base_units_per_base_lot,
+            display_price(sdk, market, lvl.tick).ok()?,
+            display_base_size(sdk, market, lvl.lots).ok()?,
             lvl.trader_present,
         ))
     });
 
     let bids = bid_entries.iter().filter_map(|lvl| {
         Some((
-            sdk.ticks_to_float_price(market, lvl.tick).ok()?,
-            lvl.lots as f64 * raw_base_units_per_base_lot,
+            display_price(sdk, market, lvl.tick).ok()?,
+            display_base_size(sdk, market, lvl.lots).ok()?,
             lvl.trader_present,
         ))
     });
-    let price_precision: usize = get_precision(
-        10_u64.pow(meta.quote_decimals) * meta.raw_base_units_per_base_unit as u64
-            / meta.tick_size_in_quote_atoms_per_base_unit,
-    );
-    let size_precision: usize =
-        get_precision(meta.num_base_lots_per_base_unit / meta.raw_base_units_per_base_unit as u64);
+    let (price_precision, size_precision) = get_book_precisions(meta, precision);
+
+    if let Some(template) = template {
+        for (side, (price, size, present)) in asks
+            .rev()
+            .map(|level| ("Ask", level))
+            .chain(bids.map(|level| ("Bid", level)))
+        {
+            println!(
+                "{}",
+                render_template(
+                    template,
+                    &[
+                        ("price", format_float(price, price_precision)),
+                        ("size", format_float(size, size_precision)),
+                        ("side", side.to_string()),
+                        ("trader_present", present.to_string()),
+                    ],
+                )?
+            );
+        }
+        return Ok(());
+    }
+
+    let theme = get_theme();
     let bid_strings = bids
         .into_iter()
         .map(|(price, size, present)| {
             let p = format_float(price, price_precision);
-            let s = format_float(size, size_precision).green();
-            let m = if present { "→".green() } else { " ".green() };
+            let s = format_float(size, size_precision).color(theme.bid);
+            let m = if present { "→" } else { " " }.color(theme.bid);
 
             (m, s, p)
         })
@@ -141,8 +301,8 @@ pub fn print_book_with_trader(
         .rev()
         .map(|(price, size, present)| {
             let p = format_float(price, price_precision);
-            let s = format_float(size, size_precision).red();
-            let m = if present { "←".red() } else { " ".red() };
+            let s = format_float(size, size_precision).color(theme.ask);
+            let m = if present { "←" } else { " " }.color(theme.ask);
 
             (p, s, m)
         })
@@ -179,6 +339,45 @@ pub fn print_book_with_trader(
     Ok(())
 }
 
+// Decimals for book prices and sizes. Unless overridden, prices get as many decimals as the tick
+// size needs. Ticks and lots are whole numbers, so raw units get none.
+pub fn get_book_precisions(meta: &MarketMetadata, precision: Option<usize>) -> (usize, usize) {
+    if is_raw_units() {
+        return (precision.unwrap_or(0), 0);
+    }
+    let price_precision = precision.unwrap_or_else(|| {
+        get_precision(
+            10_u64.pow(meta.quote_decimals) * meta.raw_base_units_per_base_unit as u64
+                / meta.tick_size_in_quote_atoms_per_base_unit,
+        )
+    });
+    let size_precision =
+        get_precision(meta.num_base_lots_per_base_unit / meta.raw_base_units_per_base_unit as u64);
+    (price_precision, size_precision)
+}
+
+// Render a template with handlebars placeholders such as `{{price}}` against the given fields.
+// Unknown placeholder names are an error rather than being printed as empty.
+pub fn render_template(template: &str, fields: &[(&str, String)]) -> anyhow::Result<String> {
+    let data = fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), serde_json::Value::String(value.clone())))
+        .collect::<serde_json::Map<_, _>>();
+    new_template_engine()
+        .render_template(template, &data)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to render template: {} Expected placeholders among: {}",
+                e,
+                fields
+                    .iter()
+                    .map(|(field, _)| format!("{{{{{}}}}}", field))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
 pub fn get_precision(mut target: u64) -> usize {
     let mut fives = 0;
     let mut twos = 0;
@@ -216,25 +415,18 @@ pub fn format_float(float: f64, precision: usize) -> String {
     }
 }
 
-pub fn print_market_summary_data(
-    market_pubkey: &Pubkey,
-    header: &MarketHeader,
-    base_mint_symbol: Option<String>,
-    quote_mint_symbol: Option<String>,
-) {
-    let base_pubkey = header.base_params.mint_key;
-    let quote_pubkey = header.quote_params.mint_key;
-
+pub fn print_market_summary_data(summary: &MarketSummaryOutput) {
     println!("--------------------------------------------");
-    if let (Some(base), Some(quote)) = (base_mint_symbol, quote_mint_symbol) {
+    if let (Some(base), Some(quote)) = (&summary.base_symbol, &summary.quote_symbol) {
         println!("Market: {}/{}", base, quote);
     }
-    println!("Market Address: {:?}", market_pubkey);
-    println!("Base Token: {:?}", base_pubkey);
-    println!("Quote Token: {:?}", quote_pubkey);
-    println!("Authority: {:?}", header.authority);
+    println!("Market Address: {}", summary.market);
+    println!("Base Token: {}", summary.base_mint);
+    println!("Quote Token: {}", summary.quote_mint);
+    println!("Authority: {}", summary.authority);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn print_market_details(
     sdk: &SDKClient,
     market_pubkey: &Pubkey,
@@ -243,20 +435,16 @@ pub async fn print_market_details(
     taker_fees: u64,
     base_mint_symbol: Option<String>,
     quote_mint_symbol: Option<String>,
+    export_toml: Option<&str>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let base_pubkey = market_metadata.base_mint;
     let quote_pubkey = market_metadata.quote_mint;
 
     let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
 
-    let base_vault = get_vault_address(market_pubkey, &base_pubkey).0;
-    let quote_vault = get_vault_address(market_pubkey, &quote_pubkey).0;
-
-    let base_vault_acct =
-        spl_token::state::Account::unpack(&sdk.client.get_account(&base_vault).await?.data)?;
-
-    let quote_vault_acct =
-        spl_token::state::Account::unpack(&sdk.client.get_account(&quote_vault).await?.data)?;
+    let ((_, base_vault_balance), (_, quote_vault_balance)) =
+        get_vault_balances(sdk, market_pubkey, &meta).await?;
 
     // Get market account
     let mut market_account_data = sdk.client.get_account_data(market_pubkey).await?;
@@ -267,89 +455,125 @@ pub async fn print_market_details(
     // Derserialize data and load into correct type
     let market = load_with_dispatch(&header.market_size_params, market_bytes)?.inner;
 
+    let details = MarketDetailsOutput {
+        market: market_pubkey.to_string(),
+        base_symbol: base_mint_symbol,
+        quote_symbol: quote_mint_symbol,
+        status: MarketStatus::from(market_header.status).to_string(),
+        authority: market_header.authority.to_string(),
+        sequence_number: market_header.market_sequence_number,
+        base_mint: base_pubkey.to_string(),
+        quote_mint: quote_pubkey.to_string(),
+        base_vault: market_header.base_params.vault_key.to_string(),
+        quote_vault: market_header.quote_params.vault_key.to_string(),
+        base_vault_balance: get_decimal_string(base_vault_balance, meta.base_decimals),
+        quote_vault_balance: get_decimal_string(quote_vault_balance, meta.quote_decimals),
+        raw_base_units_per_base_lot: get_decimal_string(
+            market_metadata.base_atoms_per_base_lot,
+            market_metadata.base_decimals,
+        ),
+        quote_units_per_quote_lot: get_decimal_string(
+            market_metadata.quote_atoms_per_quote_lot,
+            market_metadata.quote_decimals,
+        ),
+        tick_size_in_quote_units_per_base_unit: get_decimal_string(
+            market_metadata.tick_size_in_quote_atoms_per_base_unit,
+            market_metadata.quote_decimals,
+        ),
+        num_base_lots_per_base_unit: market_metadata.num_base_lots_per_base_unit,
+        tick_size_in_quote_atoms_per_base_unit: market_metadata
+            .tick_size_in_quote_atoms_per_base_unit,
+        taker_fee_bps: taker_fees,
+        fee_recipient: market_header.fee_recipient.to_string(),
+        raw_base_units_per_base_unit: market_metadata.raw_base_units_per_base_unit,
+        successor: market_header.successor.to_string(),
+        uncollected_fees: get_decimal_string(
+            sdk.quote_lots_to_quote_atoms(
+                market_pubkey,
+                market.get_uncollected_fee_amount().as_u64(),
+            )?,
+            market_metadata.quote_decimals,
+        ),
+        collected_fees: get_decimal_string(
+            sdk.quote_lots_to_quote_atoms(
+                market_pubkey,
+                market.get_collected_fee_amount().as_u64(),
+            )?,
+            market_metadata.quote_decimals,
+        ),
+    };
+    if let Some(path) = export_toml {
+        let path = shellexpand::tilde(path).to_string();
+        std::fs::write(&path, toml::to_string(&MarketConfigOutput::from(&details))?)?;
+        print_info!("Wrote market configuration to {}", path);
+        return Ok(());
+    }
+    match output {
+        OutputFormat::Json => return print_json(&details),
+        OutputFormat::Yaml => return print_yaml(&details),
+        OutputFormat::Template => return print_template(&details),
+        _ => {}
+    }
+
     println!("--------------------------------------------");
-    if let (Some(base), Some(quote)) = (base_mint_symbol, quote_mint_symbol) {
+    if let (Some(base), Some(quote)) = (&details.base_symbol, &details.quote_symbol) {
         println!("Market: {}/{}", base, quote);
     }
-    println!("Market Address: {}", market_pubkey);
-    println!("Status: {}", MarketStatus::from(market_header.status));
-    println!("Authority: {}", market_header.authority);
-    println!("Sequence number: {}", market_header.market_sequence_number);
+    println!("Market Address: {}", details.market);
+    println!("Status: {}", details.status);
+    println!("Authority: {}", details.authority);
+    println!("Sequence number: {}", details.sequence_number);
 
     println!(
         "Base Vault balance: {:.3}",
-        get_decimal_string(base_vault_acct.amount, meta.base_decimals).parse::<f64>()?
+        details.base_vault_balance.parse::<f64>()?
     );
 
     println!(
         "Quote Vault balance: {:.3}",
-        get_decimal_string(quote_vault_acct.amount, meta.quote_decimals).parse::<f64>()?
+        details.quote_vault_balance.parse::<f64>()?
     );
 
-    println!("Base Token: {}", base_pubkey);
-    println!("Quote Token: {}", quote_pubkey);
+    println!("Base Token: {}", details.base_mint);
+    println!("Quote Token: {}", details.quote_mint);
 
-    println!("Base vault key: {}", market_header.base_params.vault_key);
-    println!("Quote vault key: {}", market_header.quote_params.vault_key);
+    println!("Base vault key: {}", details.base_vault);
+    println!("Quote vault key: {}", details.quote_vault);
 
     println!(
         "Raw base units per base lot: {}",
-        get_decimal_string(
-            market_metadata.base_atoms_per_base_lot,
-            market_metadata.base_decimals
-        ),
+        details.raw_base_units_per_base_lot
     );
     println!(
         "Quote units per quote lot: {}",
-        get_decimal_string(
-            market_metadata.quote_atoms_per_quote_lot,
-            market_metadata.quote_decimals
-        )
+        details.quote_units_per_quote_lot
     );
     println!(
         "Tick size in quote units per base unit: {}",
-        get_decimal_string(
-            market_metadata.tick_size_in_quote_atoms_per_base_unit,
-            market_metadata.quote_decimals
-        )
+        details.tick_size_in_quote_units_per_base_unit
     );
     println!(
         "Num base lots per base unit: {}",
-        market_metadata.num_base_lots_per_base_unit,
+        details.num_base_lots_per_base_unit,
     );
     println!(
         "Tick size in quote atoms per base unit: {}",
-        market_metadata.tick_size_in_quote_atoms_per_base_unit,
+        details.tick_size_in_quote_atoms_per_base_unit,
     );
-    println!("Taker fees in basis points: {}", taker_fees);
-    println!("Fee destination pubkey: {:?}", market_header.fee_recipient);
+    println!("Taker fees in basis points: {}", details.taker_fee_bps);
+    println!("Fee destination pubkey: {}", details.fee_recipient);
     println!(
         "Raw base units per base unit: {}",
-        market_metadata.raw_base_units_per_base_unit
+        details.raw_base_units_per_base_unit
     );
     println!("Market Size Params: {:?}", market_header.market_size_params);
-    println!("Successor pubkey: {:?}", market_header.successor);
+    println!("Successor pubkey: {}", details.successor);
 
     println!(
         "Uncollected fees, in quote units: {}",
-        get_decimal_string(
-            sdk.quote_lots_to_quote_atoms(
-                market_pubkey,
-                market.get_uncollected_fee_amount().as_u64()
-            )?,
-            market_metadata.quote_decimals
-        )
-    );
-    println!(
-        "Collected fees, in quote units: {}",
-        get_decimal_string(
-            sdk.quote_lots_to_quote_atoms(
-                market_pubkey,
-                market.get_collected_fee_amount().as_u64()
-            )?,
-            market_metadata.quote_decimals
-        )
+        details.uncollected_fees
     );
+    println!("Collected fees, in quote units: {}", details.collected_fees);
 
     Ok(())
 }
@@ -360,7 +584,6 @@ pub fn print_trader_state(
     pubkey: &Pubkey,
     state: &TraderState,
 ) -> anyhow::Result<()> {
-    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
     if state.base_lots_locked == 0
         && state.base_lots_free == 0
         && state.quote_lots_locked == 0
@@ -372,172 +595,607 @@ pub fn print_trader_state(
     println!("Trader pubkey: {:?}", pubkey);
     println!(
         "Base token locked: {}",
-        get_decimal_string(
-            sdk.base_lots_to_base_atoms(market_pubkey, state.base_lots_locked.into())?,
-            meta.base_decimals
-        )
+        format_base_lots(sdk, market_pubkey, state.base_lots_locked.into())?
     );
     println!(
         "Base token free: {}",
-        get_decimal_string(
-            sdk.base_lots_to_base_atoms(market_pubkey, state.base_lots_free.into())?,
-            meta.base_decimals
-        )
+        format_base_lots(sdk, market_pubkey, state.base_lots_free.into())?
     );
     println!(
         "Quote token locked: {}",
-        get_decimal_string(
-            sdk.quote_lots_to_quote_atoms(market_pubkey, state.quote_lots_locked.into())?,
-            meta.quote_decimals
-        )
+        format_quote_lots(sdk, market_pubkey, state.quote_lots_locked.into())?
     );
     println!(
         "Quote token free: {}",
-        get_decimal_string(
-            sdk.quote_lots_to_quote_atoms(market_pubkey, state.quote_lots_free.into())?,
-            meta.quote_decimals
-        )
+        format_quote_lots(sdk, market_pubkey, state.quote_lots_free.into())?
     );
     Ok(())
 }
 
+// How market events are rendered by `log_market_events`
+pub enum EventLogFormat<'a> {
+    // Single line of comma separated key/value pairs
+    Default,
+    // One key/value pair per line
+    Pretty,
+    // Fixed-width columns with truncated pubkeys
+    Compact,
+    // User provided handlebars template with `{{name}}` placeholders
+    Template(&'a str),
+    // One JSON object per event
+    JsonLines,
+}
+
+// Structured form of a Place, Fill, or Reduce event, shared by every rendering
+pub struct EventLogEntry {
+    pub market: Pubkey,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub signature: Signature,
+    pub slot: u64,
+    pub sequence_number: u64,
+    pub event_index: u64,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>,
+    pub price: f64,
+    pub side: Side,
+    pub quantity: String,
+    // Client order id of the resting order the event is about, and for fills, of the taker order
+    pub client_order_id: Option<u128>,
+    pub taker_client_order_id: Option<u128>,
+}
+
+impl EventLogEntry {
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("market", self.market.to_string()),
+            ("event_type", self.event_type.clone()),
+            ("timestamp", self.timestamp.to_string()),
+            ("time", format_timestamp(self.timestamp)),
+            ("signature", self.signature.to_string()),
+            ("slot", self.slot.to_string()),
+            ("sequence_number", self.sequence_number.to_string()),
+            ("event_index", self.event_index.to_string()),
+            ("maker", self.maker.to_string()),
+            (
+                "taker",
+                self.taker.map(|t| t.to_string()).unwrap_or_default(),
+            ),
+            ("price", self.price.to_string()),
+            ("side", format!("{:?}", self.side)),
+            ("quantity", self.quantity.clone()),
+            (
+                "client_order_id",
+                self.client_order_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "taker_client_order_id",
+                self.taker_client_order_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
+    pub fn render(&self, format: &EventLogFormat) -> anyhow::Result<String> {
+        let rendered = match format {
+            EventLogFormat::Default => self
+                .fields()
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join(", "),
+            EventLogFormat::Pretty => {
+                let mut lines = vec![format!(
+                    "{} #{} ({})",
+                    self.event_type, self.event_index, self.signature
+                )];
+                lines.extend(
+                    self.fields()
+                        .iter()
+                        .filter(|(name, value)| {
+                            !value.is_empty()
+                                && !matches!(*name, "event_type" | "event_index" | "timestamp")
+                        })
+                        .map(|(name, value)| format!("  {:<16} {}", name, value)),
+                );
+                lines.join("\n")
+            }
+            EventLogFormat::Compact => format!(
+                "{:<10} {:<20} {:<6} {:<4} {:>14} {:>14} {:<12} {:<12} {}",
+                self.slot,
+                format_timestamp(self.timestamp),
+                self.event_type,
+                format!("{:?}", self.side),
+                self.price,
+                self.quantity,
+                truncate_pubkey(&self.maker),
+                self.taker.as_ref().map(truncate_pubkey).unwrap_or_default(),
+                self.client_order_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            ),
+            EventLogFormat::Template(template) => render_template(template, &self.fields())?,
+            EventLogFormat::JsonLines => self.to_json().to_string(),
+        };
+        Ok(rendered)
+    }
+
+    // The event as a JSON object with numeric fields kept as numbers. Shared by the jsonl event
+    // output and inspect-tx, so both agree on field names and types.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "market": self.market.to_string(),
+            "event_type": self.event_type,
+            "timestamp": self.timestamp,
+            "time": format_timestamp(self.timestamp),
+            "signature": self.signature.to_string(),
+            "slot": self.slot,
+            "sequence_number": self.sequence_number,
+            "event_index": self.event_index,
+            "maker": self.maker.to_string(),
+            "taker": self.taker.map(|t| t.to_string()),
+            "price": self.price,
+            "side": format!("{:?}", self.side),
+            "quantity": self.quantity,
+            // u128 exceeds what most JSON parsers read losslessly
+            "client_order_id": self.client_order_id.map(|id| id.to_string()),
+            "taker_client_order_id": self.taker_client_order_id.map(|id| id.to_string()),
+        })
+    }
+}
+
+pub fn truncate_pubkey(pubkey: &Pubkey) -> String {
+    truncate_key(&pubkey.to_string(), false)
+}
+
+// Shorten a base58 key to its first 8 characters, unless wide output was requested
+pub fn truncate_key(key: &str, wide: bool) -> String {
+    if wide || key.len() <= 8 {
+        key.to_string()
+    } else {
+        format!("{}…", &key[..8])
+    }
+}
+
+// Render rows as an aligned table. Numeric columns are right aligned. Unless wide, the table is
+// fit to the terminal width by wrapping long cells
+pub fn print_table(header: &[&str], rows: &[Vec<String>], wide: bool) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(if wide {
+            ContentArrangement::Disabled
+        } else {
+            ContentArrangement::Dynamic
+        })
+        .set_header(header.to_vec());
+    for row in rows.iter() {
+        table.add_row(row.iter().map(|value| {
+            let cell = Cell::new(value);
+            if value.parse::<f64>().is_ok() {
+                cell.set_alignment(CellAlignment::Right)
+            } else {
+                cell
+            }
+        }));
+    }
+    println!("{}", table);
+}
+
+// Render rows as a markdown table. Cells are never truncated or wrapped, since the table is meant
+// to be pasted elsewhere
+pub fn print_markdown_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut table = Table::new();
+    table
+        .load_preset(ASCII_MARKDOWN)
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header(header.to_vec());
+    for row in rows.iter() {
+        table.add_row(row);
+    }
+    println!("{}", table);
+}
+
+pub const MARKET_SUMMARY_HEADER: [&str; 6] = [
+    "Market",
+    "Base",
+    "Quote",
+    "Base Mint",
+    "Quote Mint",
+    "Authority",
+];
+
+pub const MARKET_STATS_HEADER: [&str; 5] =
+    ["Best Bid", "Best Ask", "Spread (bps)", "TVL", "Volume 24h"];
+
+pub fn print_market_summary_markdown(summaries: &[MarketSummaryOutput]) {
+    print_markdown_table(
+        &market_summary_header(summaries),
+        &market_summary_rows(summaries, true),
+    );
+}
+
+pub fn print_market_summary_table(summaries: &[MarketSummaryOutput], wide: bool) {
+    print_table(
+        &market_summary_header(summaries),
+        &market_summary_rows(summaries, wide),
+        wide,
+    );
+}
+
+fn market_summary_header(summaries: &[MarketSummaryOutput]) -> Vec<&'static str> {
+    let mut header = MARKET_SUMMARY_HEADER.to_vec();
+    if summaries.iter().any(|summary| summary.stats.is_some()) {
+        header.extend(MARKET_STATS_HEADER);
+    }
+    header
+}
+
+fn format_optional_float(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+// Rows for each market, followed by a totals row per quote token when stats are present
+fn market_summary_rows(summaries: &[MarketSummaryOutput], wide: bool) -> Vec<Vec<String>> {
+    let with_stats = summaries.iter().any(|summary| summary.stats.is_some());
+    let mut rows = summaries
+        .iter()
+        .map(|summary| {
+            let mut row = vec![
+                truncate_key(&summary.market, wide),
+                summary.base_symbol.clone().unwrap_or_default(),
+                summary.quote_symbol.clone().unwrap_or_default(),
+                truncate_key(&summary.base_mint, wide),
+                truncate_key(&summary.quote_mint, wide),
+                truncate_key(&summary.authority, wide),
+            ];
+            if with_stats {
+                let stats = summary.stats.clone().unwrap_or_default();
+                row.extend([
+                    format_optional_float(stats.best_bid),
+                    format_optional_float(stats.best_ask),
+                    stats
+                        .spread_bps
+                        .map(|spread| format!("{:.2}", spread))
+                        .unwrap_or_default(),
+                    format_optional_float(stats.tvl),
+                    format_optional_float(stats.volume_24h),
+                ]);
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    if with_stats {
+        for (quote_mint, quote_symbol, tvl, volume) in market_stats_totals(summaries) {
+            rows.push(vec![
+                "Total".to_string(),
+                String::new(),
+                quote_symbol.unwrap_or_default(),
+                String::new(),
+                truncate_key(&quote_mint, wide),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                tvl.to_string(),
+                format_optional_float(volume),
+            ]);
+        }
+    }
+    rows
+}
+
+// Total TVL and volume per quote mint, since values in different quote tokens can't be summed
+pub fn market_stats_totals(
+    summaries: &[MarketSummaryOutput],
+) -> Vec<(String, Option<String>, f64, Option<f64>)> {
+    let mut totals: Vec<(String, Option<String>, f64, Option<f64>)> = vec![];
+    for summary in summaries.iter() {
+        let stats = match &summary.stats {
+            Some(stats) => stats,
+            None => continue,
+        };
+        let index = match totals
+            .iter()
+            .position(|(quote_mint, ..)| *quote_mint == summary.quote_mint)
+        {
+            Some(index) => index,
+            None => {
+                totals.push((
+                    summary.quote_mint.clone(),
+                    summary.quote_symbol.clone(),
+                    0.0,
+                    None,
+                ));
+                totals.len() - 1
+            }
+        };
+        let total = &mut totals[index];
+        total.2 += stats.tvl.unwrap_or(0.0);
+        if let Some(volume) = stats.volume_24h {
+            total.3 = Some(total.3.unwrap_or(0.0) + volume);
+        }
+    }
+    totals
+}
+
+// Text output for a market summary followed by its stats
+pub fn print_market_summary_with_stats(summary: &MarketSummaryOutput) {
+    print_market_summary_data(summary);
+    if let Some(stats) = &summary.stats {
+        print_market_stats(stats);
+    }
+}
+
+fn print_market_stats(stats: &MarketStatsOutput) {
+    let format = |value: Option<f64>| value.map_or("-".to_string(), |value| value.to_string());
+    println!("Best Bid: {}", format(stats.best_bid));
+    println!("Best Ask: {}", format(stats.best_ask));
+    println!(
+        "Spread: {}",
+        stats
+            .spread_bps
+            .map_or("-".to_string(), |spread| format!("{:.2} bps", spread))
+    );
+    println!("TVL: {}", format(stats.tvl));
+    if let Some(volume) = stats.volume_24h {
+        println!("Volume 24h: {}", volume);
+    }
+}
+
+pub fn print_market_stats_totals(summaries: &[MarketSummaryOutput]) {
+    let totals = market_stats_totals(summaries);
+    if totals.is_empty() {
+        return;
+    }
+    println!("--------------------------------------------");
+    for (quote_mint, quote_symbol, tvl, volume) in totals {
+        let quote = quote_symbol.unwrap_or(quote_mint);
+        println!("Total TVL ({}): {}", quote, tvl);
+        if let Some(volume) = volume {
+            println!("Total Volume 24h ({}): {}", quote, volume);
+        }
+    }
+}
+
+pub fn print_trader_table(traders: &[TraderStateOutput], wide: bool) {
+    let rows = traders
+        .iter()
+        .map(|trader| {
+            vec![
+                truncate_key(&trader.trader, wide),
+                trader.base_locked.clone(),
+                trader.base_free.clone(),
+                trader.quote_locked.clone(),
+                trader.quote_free.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(
+        &[
+            "Trader",
+            "Base Locked",
+            "Base Free",
+            "Quote Locked",
+            "Quote Free",
+        ],
+        &rows,
+        wide,
+    );
+}
+
 pub async fn log_market_events(
     sdk: &mut SDKClient,
     market_events: Vec<PhoenixEvent>,
+    client_order_id_filter: Option<u128>,
+    format: &EventLogFormat<'_>,
 ) -> anyhow::Result<()> {
-    for event in market_events {
+    if let EventLogFormat::Compact = format {
+        println!(
+            "{:<10} {:<20} {:<6} {:<4} {:>14} {:>14} {:<12} {:<12} {}",
+            "slot",
+            "timestamp",
+            "type",
+            "side",
+            "price",
+            "quantity",
+            "maker",
+            "taker",
+            "client_order_id"
+        );
+    }
+    let client_order_ids = get_event_client_order_ids(&market_events);
+    for (event, client_order_ids) in market_events.iter().zip(client_order_ids) {
+        if client_order_id_filter.is_some()
+            && client_order_id_filter != client_order_ids.maker
+            && client_order_id_filter != client_order_ids.taker
+        {
+            continue;
+        }
         let market_pubkey = event.market;
         if !sdk.markets.contains_key(&market_pubkey) {
             sdk.add_market(&market_pubkey).await?;
         }
-        let metadata = sdk.get_market_metadata_from_cache(&market_pubkey)?;
-        match event.details {
-            MarketEventDetails::Fill(fill) => {
-                let Fill {
-                    maker,
-                    taker,
-                    price_in_ticks,
-                    base_lots_filled,
-                    side_filled,
-                    ..
-                } = fill;
-                let keys = initialize_log(&event, "Fill".to_string());
-                let fill_data = vec![
-                    maker.to_string(),
-                    taker.to_string(),
-                    (sdk.ticks_to_float_price(&market_pubkey, price_in_ticks))?.to_string(),
-                    format!("{:?}", side_filled),
-                    get_decimal_string(
-                        sdk.base_lots_to_base_atoms(&market_pubkey, base_lots_filled)?,
-                        metadata.base_decimals,
-                    ),
-                ];
-                println!("{}", finalize_log(keys, fill_data));
+        if let MarketEventDetails::FillSummary(fill_summary) = &event.details {
+            let total_quote_fees = sdk.quote_atoms_to_quote_units_as_float(
+                &market_pubkey,
+                fill_summary.total_quote_fees,
+            )?;
+            match format {
+                EventLogFormat::JsonLines => println!(
+                    "{}",
+                    serde_json::json!({
+                        "market": market_pubkey.to_string(),
+                        "event_type": "FillSummary",
+                        "timestamp": event.timestamp,
+                        "time": format_timestamp(event.timestamp),
+                        "signature": event.signature.to_string(),
+                        "slot": event.slot,
+                        "sequence_number": event.sequence_number,
+                        "event_index": event.event_index,
+                        "client_order_id": fill_summary.client_order_id.to_string(),
+                        "total_quote_fees": total_quote_fees,
+                    })
+                ),
+                EventLogFormat::Default | EventLogFormat::Pretty => {
+                    println!("Total quote token fees paid: {}", total_quote_fees)
+                }
+                // These print one row per order event, and a summary has no price, side or size
+                EventLogFormat::Compact | EventLogFormat::Template(_) => {}
             }
-            MarketEventDetails::Place(place) => {
-                let Place {
-                    order_sequence_number,
-                    client_order_id: _,
-                    maker,
-                    price_in_ticks,
-                    base_lots_placed,
-                } = place;
-                let side = Side::from_order_sequence_number(order_sequence_number);
-                let keys = initialize_log(&event, "Place".to_string());
-                let place_data = vec![
-                    maker.to_string(),
-                    "".to_string(),
-                    sdk.ticks_to_float_price(&market_pubkey, price_in_ticks)?
-                        .to_string(),
-                    format!("{:?}", side),
-                    get_decimal_string(
-                        sdk.base_lots_to_base_atoms(&market_pubkey, base_lots_placed)?,
-                        metadata.base_decimals,
-                    ),
-                ];
+            continue;
+        }
+        if let Some(entry) = get_event_log_entry(sdk, event, client_order_ids)? {
+            println!("{}", entry.render(format)?);
+        }
+    }
+    Ok(())
+}
 
-                println!("{}", finalize_log(keys, place_data));
+// Convert a Place, Fill, Reduce or Evict event into its structured log entry. The SDK reports
+// orders removed for expiring as a Reduce. Other events return None.
+// The event's market must already be loaded into the SDK.
+pub fn get_event_log_entry(
+    sdk: &SDKClient,
+    event: &PhoenixEvent,
+    client_order_ids: EventClientOrderIds,
+) -> anyhow::Result<Option<EventLogEntry>> {
+    let market_pubkey = event.market;
+    let (event_type, maker, taker, price_in_ticks, side, base_lots) = match &event.details {
+        MarketEventDetails::Fill(fill) => {
+            let Fill {
+                maker,
+                taker,
+                price_in_ticks,
+                base_lots_filled,
+                side_filled,
+                ..
+            } = fill;
+            (
+                "Fill",
+                *maker,
+                Some(*taker),
+                *price_in_ticks,
+                *side_filled,
+                *base_lots_filled,
+            )
+        }
+        MarketEventDetails::Place(place) => {
+            let Place {
+                order_sequence_number,
+                client_order_id: _,
+                maker,
+                price_in_ticks,
+                base_lots_placed,
+            } = place;
+            (
+                "Place",
+                *maker,
+                None,
+                *price_in_ticks,
+                Side::from_order_sequence_number(*order_sequence_number),
+                *base_lots_placed,
+            )
+        }
+        MarketEventDetails::Reduce(reduce) => {
+            let Reduce {
+                order_sequence_number,
+                maker,
+                price_in_ticks,
+                base_lots_removed,
+                ..
+            } = reduce;
+            (
+                "Reduce",
+                *maker,
+                None,
+                *price_in_ticks,
+                Side::from_order_sequence_number(*order_sequence_number),
+                *base_lots_removed,
+            )
+        }
+        MarketEventDetails::Evict(evict) => (
+            "Evict",
+            evict.maker,
+            None,
+            evict.price_in_ticks,
+            Side::from_order_sequence_number(evict.order_sequence_number),
+            evict.base_lots_evicted,
+        ),
+        _ => return Ok(None),
+    };
+    Ok(Some(EventLogEntry {
+        market: market_pubkey,
+        event_type: event_type.to_string(),
+        timestamp: event.timestamp as i64,
+        signature: event.signature,
+        slot: event.slot,
+        sequence_number: event.sequence_number,
+        event_index: event.event_index,
+        maker,
+        taker,
+        price: display_price(sdk, &market_pubkey, price_in_ticks)?,
+        side,
+        quantity: format_base_lots(sdk, &market_pubkey, base_lots)?,
+        client_order_id: client_order_ids.maker,
+        taker_client_order_id: client_order_ids.taker,
+    }))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventClientOrderIds {
+    // Id of the resting order the event is about
+    pub maker: Option<u128>,
+    // Id of the taker order, for fills and fill summaries
+    pub taker: Option<u128>,
+}
+
+// Get the client order ids associated with each event, if known. Place events carry the resting
+// order's id, which later Fill, Reduce and Evict events of the same order are attributed by its
+// sequence number, so orders placed before the first event have no maker-side id. Fill events
+// get the taker-side id from the FillSummary that closes out the taker order they belong to,
+// which is emitted right after its fills in the same transaction.
+pub fn get_event_client_order_ids(events: &[PhoenixEvent]) -> Vec<EventClientOrderIds> {
+    let mut client_order_ids = vec![EventClientOrderIds::default(); events.len()];
+    let mut resting_orders: HashMap<(Pubkey, u64), u128> = HashMap::new();
+    let mut pending_fills = vec![];
+    for (i, event) in events.iter().enumerate() {
+        let resting_order_id = |sequence_number: u64| {
+            resting_orders
+                .get(&(event.market, sequence_number))
+                .copied()
+        };
+        match &event.details {
+            MarketEventDetails::Place(place) => {
+                client_order_ids[i].maker = Some(place.client_order_id);
+                resting_orders.insert(
+                    (event.market, place.order_sequence_number),
+                    place.client_order_id,
+                );
+            }
+            MarketEventDetails::Fill(fill) => {
+                client_order_ids[i].maker = resting_order_id(fill.order_sequence_number);
+                pending_fills.push(i);
             }
             MarketEventDetails::Reduce(reduce) => {
-                let Reduce {
-                    order_sequence_number,
-                    maker,
-                    price_in_ticks,
-                    base_lots_removed,
-                    ..
-                } = reduce;
-                let side = Side::from_order_sequence_number(order_sequence_number);
-                let keys = initialize_log(&event, "Reduce".to_string());
-
-                let reduce_data = vec![
-                    maker.to_string(),
-                    "".to_string(),
-                    sdk.ticks_to_float_price(&market_pubkey, price_in_ticks)?
-                        .to_string(),
-                    format!("{:?}", side),
-                    get_decimal_string(
-                        sdk.base_lots_to_base_atoms(&market_pubkey, base_lots_removed)?,
-                        metadata.base_decimals,
-                    ),
-                ];
-                println!("{}", finalize_log(keys, reduce_data));
+                client_order_ids[i].maker = resting_order_id(reduce.order_sequence_number);
             }
-            MarketEventDetails::FillSummary(fill_summary) => {
-                let FillSummary {
-                    total_quote_fees, ..
-                } = fill_summary;
-                println!(
-                    "Total quote token fees paid: {}",
-                    sdk.quote_atoms_to_quote_units_as_float(&market_pubkey, total_quote_fees)?
-                );
+            MarketEventDetails::Evict(evict) => {
+                client_order_ids[i].maker = resting_order_id(evict.order_sequence_number);
             }
-            _ => {
-                continue;
+            MarketEventDetails::FillSummary(fill_summary) => {
+                client_order_ids[i].taker = Some(fill_summary.client_order_id);
+                for j in pending_fills.drain(..) {
+                    if events[j].signature == event.signature {
+                        client_order_ids[j].taker = Some(fill_summary.client_order_id);
+                    }
+                }
             }
+            _ => {}
         }
     }
-    Ok(())
-}
-pub fn initialize_log(event: &PhoenixEvent, event_type: String) -> Vec<String> {
-    let base_schema: Vec<String> = vec![
-        "market".to_string(),
-        "event_type".to_string(),
-        "timestamp".to_string(),
-        "signature".to_string(),
-        "slot".to_string(),
-        "sequence_number".to_string(),
-        "event_index".to_string(),
-    ];
-    let base = vec![
-        event.market.to_string(),
-        event_type,
-        event.timestamp.to_string(),
-        event.signature.to_string(),
-        event.slot.to_string(),
-        event.sequence_number.to_string(),
-        event.event_index.to_string(),
-    ];
-    base_schema
-        .iter()
-        .zip(base.iter())
-        .map(|(a, b)| format!("{}: {}", a, b))
-        .collect::<Vec<String>>()
-}
-
-pub fn finalize_log(mut log: Vec<String>, data: Vec<String>) -> String {
-    let event_schema: Vec<String> = vec![
-        "maker".to_string(),
-        "taker".to_string(),
-        "price".to_string(),
-        "side".to_string(),
-        "quantity".to_string(),
-    ];
-    log.extend_from_slice(
-        &event_schema
-            .iter()
-            .zip(data.iter())
-            .map(|(a, b)| format!("{}: {}", a, b))
-            .collect::<Vec<String>>(),
-    );
-    log.join(", ")
+    client_order_ids
 }
@@ -0,0 +1,46 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+
+use serde_json::{json, Value};
+
+use super::history_helpers::get_unix_timestamp;
+
+// Emits newline-delimited JSON progress events to a side channel so orchestrators can track
+// liveness of long-running commands. Any path works, including `/dev/fd/<n>` for an inherited
+// file descriptor. When no path is given, events are dropped.
+pub struct ProgressReporter {
+    file: Option<File>,
+    command: String,
+}
+
+impl ProgressReporter {
+    pub fn new(path: Option<&str>, command: &str) -> anyhow::Result<Self> {
+        let file = match path {
+            Some(path) => Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&*shellexpand::tilde(path))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            file,
+            command: command.to_string(),
+        })
+    }
+
+    pub fn emit(&mut self, event: &str, details: Value) -> anyhow::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            let line = json!({
+                "timestamp": get_unix_timestamp()?,
+                "command": self.command,
+                "event": event,
+                "details": details,
+            });
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
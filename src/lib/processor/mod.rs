@@ -15,3 +15,48 @@ pub mod process_mint_tokens;
 pub mod process_mint_tokens_for_market;
 pub mod process_request_seat;
 pub mod process_get_uncollected_revenue;
+pub mod process_fund_makers;
+pub mod process_get_stale_orders;
+pub mod process_get_maker_activity;
+pub mod process_watch_vaults;
+pub mod process_record_book_depth;
+pub mod process_record_fee_snapshot;
+pub mod process_get_fee_accrual;
+pub mod process_simulate_maker_removal;
+pub mod process_get_queue_position;
+pub mod process_get_level;
+pub mod process_get_priority_fees;
+pub mod process_rotate_keypair;
+pub mod process_serve_books;
+pub mod process_warm_cache;
+pub mod process_get_revenue_statement;
+pub mod process_get_balance_ledger;
+pub mod process_watch_balance;
+pub mod process_snipe;
+pub mod process_estimate_incentives;
+pub mod process_migrate_quotes;
+pub mod process_get_settlement_report;
+pub mod process_get_book_orders;
+pub mod process_get_bbo;
+pub mod process_get_spread;
+pub mod process_get_all_seats;
+pub mod process_get_trader_fills;
+pub mod process_get_recent_trades;
+pub mod process_get_candles;
+pub mod process_get_volume;
+pub mod process_get_liquidity;
+pub mod process_get_fees;
+pub mod process_get_balances;
+pub mod process_inspect_tx;
+pub mod process_get_order;
+pub mod process_get_vwap;
+pub mod process_watch_book;
+pub mod process_watch_trades;
+pub mod process_compare_books;
+pub mod process_get_trader_markets;
+pub mod process_get_vaults;
+pub mod process_get_book_at_slot;
+pub mod process_get_events;
+pub mod process_get_events_range;
+pub mod process_get_maker_leaderboard;
+pub mod process_get_taker_leaderboard;
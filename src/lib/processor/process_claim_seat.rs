@@ -2,13 +2,20 @@ use ellipsis_client::EllipsisClient;
 use phoenix_sdk::utils::create_claim_seat_ix_if_needed;
 use solana_sdk::{pubkey::Pubkey, signer::Signer};
 
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::print_info;
+
 pub async fn process_claim_seat(
     client: &EllipsisClient,
     market_pubkey: &Pubkey,
+    ix_only: bool,
 ) -> anyhow::Result<()> {
     let claim_seat_ix =
         create_claim_seat_ix_if_needed(client, market_pubkey, &client.payer.pubkey()).await?;
-    println!("Claiming seat for pubkey: {}", client.payer.pubkey());
+    if ix_only {
+        return print_instructions_json(&claim_seat_ix);
+    }
+    print_info!("Claiming seat for pubkey: {}", client.payer.pubkey());
 
     if !claim_seat_ix.is_empty() {
         let tx = client.sign_send_instructions(claim_seat_ix, vec![]).await?;
@@ -0,0 +1,178 @@
+use phoenix::state::markets::{Ladder, LadderOrder};
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    get_book_levels, get_ladder_depth_within_bps, get_raw_base_units_per_base_lot,
+};
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+const DEPTH_BANDS_BPS: [u64; 3] = [10, 50, 100];
+// Band used to decide which market is deeper
+const VERDICT_BAND_BPS: u64 = 50;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookSummaryOutput {
+    pub market: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub mid: Option<f64>,
+    pub spread_bps: Option<f64>,
+    // Base units resting within each band of the midpoint, as (bps, bid depth, ask depth)
+    pub depth: Vec<(u64, f64, f64)>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CompareBooksOutput {
+    pub books: [BookSummaryOutput; 2],
+    pub tighter: Option<String>,
+    pub deeper: Option<String>,
+}
+
+// Summarize a ladder in quote units per base unit and base units, so books of markets with
+// different lot and tick sizes can be compared directly
+fn summarize_book(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    ladder: &Ladder,
+) -> anyhow::Result<BookSummaryOutput> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let raw_base_units_per_base_lot = get_raw_base_units_per_base_lot(meta);
+    let price = |level: Option<&LadderOrder>| {
+        level
+            .map(|level| sdk.ticks_to_float_price(market_pubkey, level.price_in_ticks))
+            .transpose()
+    };
+    let best_bid = price(ladder.bids.first())?;
+    let best_ask = price(ladder.asks.first())?;
+    let (mid, spread_bps) = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => {
+            let mid = (bid + ask) / 2.0;
+            (Some(mid), Some((ask - bid) / mid * 10_000.0))
+        }
+        _ => (None, None),
+    };
+    let depth = DEPTH_BANDS_BPS
+        .iter()
+        .map(|bps| {
+            let (bid_lots, ask_lots) = get_ladder_depth_within_bps(ladder, *bps).unwrap_or((0, 0));
+            (
+                *bps,
+                bid_lots as f64 * raw_base_units_per_base_lot,
+                ask_lots as f64 * raw_base_units_per_base_lot,
+            )
+        })
+        .collect();
+    Ok(BookSummaryOutput {
+        market: market_pubkey.to_string(),
+        best_bid,
+        best_ask,
+        mid,
+        spread_bps,
+        depth,
+    })
+}
+
+pub async fn process_compare_books(
+    market_a: &Pubkey,
+    market_b: &Pubkey,
+    sdk: &SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta_a = sdk.get_market_metadata_from_cache(market_a)?;
+    let meta_b = sdk.get_market_metadata_from_cache(market_b)?;
+    if meta_a.base_mint != meta_b.base_mint || meta_a.quote_mint != meta_b.quote_mint {
+        tracing::warn!(
+            "The markets trade different mints, so prices are only comparable if the tokens are equivalent"
+        );
+    }
+
+    let book_a = summarize_book(
+        sdk,
+        market_a,
+        &get_book_levels(market_a, &sdk.client, u64::MAX).await?,
+    )?;
+    let book_b = summarize_book(
+        sdk,
+        market_b,
+        &get_book_levels(market_b, &sdk.client, u64::MAX).await?,
+    )?;
+
+    let tighter = match (book_a.spread_bps, book_b.spread_bps) {
+        (Some(a), Some(b)) if a < b => Some(book_a.market.clone()),
+        (Some(a), Some(b)) if b < a => Some(book_b.market.clone()),
+        _ => None,
+    };
+    let verdict_depth = |book: &BookSummaryOutput| {
+        book.depth
+            .iter()
+            .find(|(bps, ..)| *bps == VERDICT_BAND_BPS)
+            .map(|(_, bid, ask)| bid + ask)
+            .unwrap_or(0.0)
+    };
+    let deeper = match verdict_depth(&book_a).partial_cmp(&verdict_depth(&book_b)) {
+        Some(std::cmp::Ordering::Greater) => Some(book_a.market.clone()),
+        Some(std::cmp::Ordering::Less) => Some(book_b.market.clone()),
+        _ => None,
+    };
+    let comparison = CompareBooksOutput {
+        books: [book_a, book_b],
+        tighter,
+        deeper,
+    };
+
+    if output == OutputFormat::Json {
+        return print_json(&comparison);
+    }
+    let [book_a, book_b] = &comparison.books;
+    let format = |value: Option<f64>| value.map_or("-".to_string(), |v| v.to_string());
+    let format_bps = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.2}", v));
+    let mut rows = vec![
+        (
+            "Best bid".to_string(),
+            format(book_a.best_bid),
+            format(book_b.best_bid),
+        ),
+        (
+            "Best ask".to_string(),
+            format(book_a.best_ask),
+            format(book_b.best_ask),
+        ),
+        ("Mid".to_string(), format(book_a.mid), format(book_b.mid)),
+        (
+            "Spread (bps)".to_string(),
+            format_bps(book_a.spread_bps),
+            format_bps(book_b.spread_bps),
+        ),
+    ];
+    for ((bps, bid_a, ask_a), (_, bid_b, ask_b)) in book_a.depth.iter().zip(book_b.depth.iter()) {
+        rows.push((
+            format!("Bid depth within {} bps", bps),
+            format!("{:.3}", bid_a),
+            format!("{:.3}", bid_b),
+        ));
+        rows.push((
+            format!("Ask depth within {} bps", bps),
+            format!("{:.3}", ask_a),
+            format!("{:.3}", ask_b),
+        ));
+    }
+    println!(
+        "{0: <24} | {1: <44} | {2: <44}",
+        "Metric", book_a.market, book_b.market
+    );
+    for (metric, a, b) in rows {
+        println!("{0: <24} | {1: <44} | {2: <44}", metric, a, b);
+    }
+    println!(
+        "Tighter: {}",
+        comparison.tighter.as_deref().unwrap_or("neither")
+    );
+    println!(
+        "Deeper within {} bps: {}",
+        VERDICT_BAND_BPS,
+        comparison.deeper.as_deref().unwrap_or("neither")
+    );
+    Ok(())
+}
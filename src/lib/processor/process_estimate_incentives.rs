@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_market_events_in_window, get_unix_timestamp};
+use crate::helpers::market_helpers::get_base_and_quote_amounts;
+use crate::helpers::print_helpers::print_table;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IncentiveProgramRules {
+    /// Rewards distributed across all makers per day
+    pub reward_per_day: f64,
+    /// Symbol of the reward token, for display only
+    pub reward_symbol: Option<String>,
+    /// Weight of the fraction of time spent quoting both sides within the max spread
+    pub uptime_weight: f64,
+    /// Weight of how tight the maker's quotes were while quoting
+    pub spread_weight: f64,
+    /// Weight of the maker's share of filled volume
+    pub volume_weight: f64,
+    /// Widest two-sided quote, in basis points of its midpoint, that counts towards uptime
+    pub max_spread_bps: f64,
+}
+
+#[derive(Default)]
+struct MakerPerformance {
+    // Resting orders seen in the window, keyed by order sequence number, with the unix timestamp
+    // they expire at if they were placed with a time in force
+    orders: HashMap<u64, (Side, u64, Option<i64>)>,
+    quoting_seconds: f64,
+    // Sum over quoting time of how far inside the max spread the quote was, from 0 to 1
+    spread_score_seconds: f64,
+    volume: f64,
+}
+
+impl MakerPerformance {
+    fn spread_bps(&self) -> Option<f64> {
+        let best_bid = self
+            .orders
+            .values()
+            .filter(|(side, _, _)| *side == Side::Bid)
+            .map(|(_, price, _)| *price)
+            .max()?;
+        let best_ask = self
+            .orders
+            .values()
+            .filter(|(side, _, _)| *side == Side::Ask)
+            .map(|(_, price, _)| *price)
+            .min()?;
+        let mid = (best_bid + best_ask) as f64 / 2.0;
+        Some((best_ask as f64 - best_bid as f64) / mid * 10_000.0)
+    }
+
+    // Credit the time from `start` to `end`. Orders that expired by `start` no longer count, since
+    // expired orders stay in the book until matched against and so never emit an event of their own.
+    fn accrue(&mut self, start: i64, end: i64, max_spread_bps: f64) {
+        self.orders
+            .retain(|_, (_, _, expiry)| expiry.map(|expiry| expiry > start).unwrap_or(true));
+        let seconds = (end - start).max(0) as f64;
+        if let Some(spread_bps) = self.spread_bps() {
+            if spread_bps <= max_spread_bps {
+                self.quoting_seconds += seconds;
+                self.spread_score_seconds += seconds * (1.0 - spread_bps / max_spread_bps);
+            }
+        }
+    }
+}
+
+// Estimate each maker's share of an incentive program's rewards over a window. Makers are scored
+// on uptime, spread, and volume, as weighted by the program rules, and the window's rewards are
+// split pro rata by score. Quotes are reconstructed from the Place, Reduce, Fill, Evict, and
+// TimeInForce events in the window, so orders resting since before the window only count once they
+// are placed again.
+pub async fn process_estimate_incentives(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    rules_file: &str,
+    maker: Option<&Pubkey>,
+    window_seconds: u64,
+    wide: bool,
+) -> anyhow::Result<()> {
+    let rules: IncentiveProgramRules =
+        serde_json::from_str(&std::fs::read_to_string(&*shellexpand::tilde(rules_file))?)?;
+    if rules.max_spread_bps <= 0.0 {
+        return Err(anyhow::anyhow!("maxSpreadBps must be positive"));
+    }
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let now = get_unix_timestamp()?;
+    let window_start = now - window_seconds as i64;
+    let events = get_market_events_in_window(sdk, market_pubkey, window_seconds).await?;
+
+    let mut makers: HashMap<Pubkey, MakerPerformance> = HashMap::new();
+    let mut last_timestamp = window_start;
+    for event in events.iter() {
+        let timestamp = event.timestamp.max(last_timestamp);
+        for performance in makers.values_mut() {
+            performance.accrue(last_timestamp, timestamp, rules.max_spread_bps);
+        }
+        last_timestamp = timestamp;
+
+        match &event.details {
+            MarketEventDetails::Place(place) => {
+                makers.entry(place.maker).or_default().orders.insert(
+                    place.order_sequence_number,
+                    (
+                        Side::from_order_sequence_number(place.order_sequence_number),
+                        place.price_in_ticks,
+                        None,
+                    ),
+                );
+            }
+            // Follows the Place event of an order with a time in force
+            MarketEventDetails::TimeInForce(time_in_force) => {
+                let expiry = time_in_force.last_valid_unix_timestamp_in_seconds;
+                if expiry > 0 {
+                    for performance in makers.values_mut() {
+                        if let Some(order) = performance
+                            .orders
+                            .get_mut(&time_in_force.order_sequence_number)
+                        {
+                            order.2 = Some(expiry as i64);
+                        }
+                    }
+                }
+            }
+            // The SDK reports orders removed for expiring as a Reduce of all remaining lots
+            MarketEventDetails::Reduce(reduce) if reduce.base_lots_remaining == 0 => {
+                if let Some(performance) = makers.get_mut(&reduce.maker) {
+                    performance.orders.remove(&reduce.order_sequence_number);
+                }
+            }
+            MarketEventDetails::Fill(fill) => {
+                let performance = makers.entry(fill.maker).or_default();
+                if fill.base_lots_remaining == 0 {
+                    performance.orders.remove(&fill.order_sequence_number);
+                }
+                let (_, quote) =
+                    get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+                performance.volume += quote;
+            }
+            MarketEventDetails::Evict(evict) => {
+                if let Some(performance) = makers.get_mut(&evict.maker) {
+                    performance.orders.remove(&evict.order_sequence_number);
+                }
+            }
+            _ => {}
+        }
+    }
+    for performance in makers.values_mut() {
+        performance.accrue(last_timestamp, now, rules.max_spread_bps);
+    }
+
+    if makers.is_empty() {
+        println!(
+            "No maker activity found in the last {} seconds",
+            window_seconds
+        );
+        return Ok(());
+    }
+
+    let total_volume = makers.values().map(|p| p.volume).sum::<f64>();
+    let mut scores = makers
+        .iter()
+        .map(|(maker, performance)| {
+            let uptime = performance.quoting_seconds / window_seconds as f64;
+            let spread_score = if performance.quoting_seconds > 0.0 {
+                performance.spread_score_seconds / performance.quoting_seconds
+            } else {
+                0.0
+            };
+            let volume_share = if total_volume > 0.0 {
+                performance.volume / total_volume
+            } else {
+                0.0
+            };
+            let score = rules.uptime_weight * uptime
+                + rules.spread_weight * spread_score
+                + rules.volume_weight * volume_share;
+            (*maker, uptime, spread_score, performance.volume, score)
+        })
+        .collect::<Vec<_>>();
+    scores.sort_by(|a, b| b.4.total_cmp(&a.4));
+    let total_score = scores.iter().map(|s| s.4).sum::<f64>();
+    let window_rewards = rules.reward_per_day * window_seconds as f64 / 86_400.0;
+
+    println!(
+        "Estimated rewards for {} over the last {} seconds: {} {}",
+        market_pubkey,
+        window_seconds,
+        window_rewards,
+        rules.reward_symbol.as_deref().unwrap_or("")
+    );
+    let rows = scores
+        .iter()
+        .filter(|(maker_pubkey, ..)| maker.map(|m| m == maker_pubkey).unwrap_or(true))
+        .map(|(maker_pubkey, uptime, spread_score, volume, score)| {
+            let claimable = if total_score > 0.0 {
+                window_rewards * score / total_score
+            } else {
+                0.0
+            };
+            vec![
+                maker_pubkey.to_string(),
+                format!("{:.2}%", uptime * 100.0),
+                format!("{:.4}", spread_score),
+                format!("{:.4}", volume),
+                format!("{:.4}", score),
+                format!("{:.6}", claimable),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(
+        &[
+            "Maker",
+            "Uptime",
+            "Spread Score",
+            "Volume (quote)",
+            "Score",
+            "Claimable Estimate",
+        ],
+        &rows,
+        wide,
+    );
+
+    Ok(())
+}
@@ -8,10 +8,14 @@ use phoenix_seat_manager::instruction_builders::{
 };
 use solana_sdk::pubkey::Pubkey;
 
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::print_info;
+
 pub async fn process_evict_seat(
     client: &EllipsisClient,
     market_pubkey: &Pubkey,
     trader_to_evict: &Option<Pubkey>,
+    ix_only: bool,
 ) -> anyhow::Result<()> {
     let market_bytes = client.get_account_data(market_pubkey).await?;
     let (header_bytes, _market_bytes) = market_bytes.split_at(size_of::<MarketHeader>());
@@ -36,7 +40,10 @@ pub async fn process_evict_seat(
     };
 
     if let Some(evict_trader_ix) = maybe_evict_trader_ix {
-        println!("Evicting trader: {}", evict_trader_ix.accounts[13].pubkey);
+        if ix_only {
+            return print_instructions_json(&[evict_trader_ix]);
+        }
+        print_info!("Evicting trader: {}", evict_trader_ix.accounts[13].pubkey);
         let tx = client
             .sign_send_instructions(vec![evict_trader_ix], vec![])
             .await?;
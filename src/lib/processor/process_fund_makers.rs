@@ -0,0 +1,292 @@
+use std::mem::size_of;
+
+use anyhow::Context;
+use phoenix::program::deposit::DepositParams;
+use phoenix::program::instruction_builders::create_deposit_funds_instruction;
+use phoenix::program::{load_with_dispatch, MarketHeader};
+use phoenix_sdk::sdk_client::*;
+use serde::{Deserialize, Serialize};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+use solana_sdk::signer::Signer;
+
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::helpers::market_helpers::decimal_to_atoms;
+use crate::helpers::output_helpers::format_atoms;
+use crate::print_summary;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MakerFundingTarget {
+    pub wallet: String,
+    /// Optional keypair of the maker. When present, the transferred tokens are also deposited
+    /// into the maker's seat on the market.
+    pub keypair_path: Option<String>,
+    /// Target free base balance on the market, in base units. Makers that are only sent a
+    /// transfer count their wallet balance toward it as well.
+    pub base_target: f64,
+    /// Target free quote balance on the market, in quote units, counted the same way
+    pub quote_target: f64,
+}
+
+struct MakerFundingPlan {
+    wallet: Pubkey,
+    keypair: Option<Keypair>,
+    has_seat: bool,
+    base_lots: u64,
+    quote_lots: u64,
+}
+
+pub async fn process_fund_makers(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    treasury: &Keypair,
+    makers_file: &str,
+    execute: bool,
+    ix_only: bool,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    let targets: Vec<MakerFundingTarget> =
+        serde_json::from_str(&std::fs::read_to_string(&*shellexpand::tilde(makers_file))?)?;
+
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (header_bytes, market_bytes) = market_account_data.split_at(size_of::<MarketHeader>());
+    let header: &MarketHeader = bytemuck::try_from_bytes(header_bytes)
+        .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
+    let market = load_with_dispatch(&header.market_size_params, market_bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to load market. Error {:?}", e))?
+        .inner;
+
+    // Compute the lots each maker is missing to reach its targets
+    let mut plans = vec![];
+    for target in targets.iter() {
+        let wallet: Pubkey = target.wallet.parse()?;
+        let keypair = match &target.keypair_path {
+            Some(path) => Some(
+                read_keypair_file(&*shellexpand::tilde(path))
+                    .map_err(|e| anyhow::anyhow!("Failed to read keypair {}: {}", path, e))?,
+            ),
+            None => None,
+        };
+        if let Some(keypair) = &keypair {
+            if keypair.pubkey() != wallet {
+                return Err(anyhow::anyhow!(
+                    "Keypair {} does not match wallet {}",
+                    keypair.pubkey(),
+                    wallet
+                ));
+            }
+        }
+        let (base_lots_free, quote_lots_free, has_seat) = match market.get_trader_state(&wallet) {
+            Some(state) => (
+                u64::from(state.base_lots_free),
+                u64::from(state.quote_lots_free),
+                true,
+            ),
+            None => (0, 0, false),
+        };
+
+        // A float's Display form is the shortest decimal that reads back as it, so this is the
+        // amount as written in the makers file.
+        let base_target_atoms =
+            decimal_to_atoms(&target.base_target.to_string(), meta.base_decimals)
+                .with_context(|| format!("Invalid base target for {}", wallet))?;
+        let quote_target_atoms =
+            decimal_to_atoms(&target.quote_target.to_string(), meta.quote_decimals)
+                .with_context(|| format!("Invalid quote target for {}", wallet))?;
+
+        // Deposited funds land in the seat, so only its free balance counts toward the targets.
+        // Transfers without a deposit land in the wallet's token accounts, so those count too,
+        // otherwise every rerun would fund the same maker again.
+        let (base_held_atoms, quote_held_atoms) = if keypair.is_some() && has_seat {
+            (0, 0)
+        } else {
+            (
+                get_token_balance(
+                    sdk,
+                    &spl_associated_token_account::get_associated_token_address(
+                        &wallet,
+                        &meta.base_mint,
+                    ),
+                )
+                .await,
+                get_token_balance(
+                    sdk,
+                    &spl_associated_token_account::get_associated_token_address(
+                        &wallet,
+                        &meta.quote_mint,
+                    ),
+                )
+                .await,
+            )
+        };
+        let base_held_atoms = base_held_atoms + base_lots_free * meta.base_atoms_per_base_lot;
+        let quote_held_atoms = quote_held_atoms + quote_lots_free * meta.quote_atoms_per_quote_lot;
+        let base_shortfall_atoms = base_target_atoms.saturating_sub(base_held_atoms);
+        let quote_shortfall_atoms = quote_target_atoms.saturating_sub(quote_held_atoms);
+
+        plans.push(MakerFundingPlan {
+            wallet,
+            keypair,
+            has_seat,
+            base_lots: (base_shortfall_atoms + meta.base_atoms_per_base_lot - 1)
+                / meta.base_atoms_per_base_lot,
+            quote_lots: (quote_shortfall_atoms + meta.quote_atoms_per_quote_lot - 1)
+                / meta.quote_atoms_per_quote_lot,
+        });
+    }
+
+    let treasury_base_ata = spl_associated_token_account::get_associated_token_address(
+        &treasury.pubkey(),
+        &meta.base_mint,
+    );
+    let treasury_quote_ata = spl_associated_token_account::get_associated_token_address(
+        &treasury.pubkey(),
+        &meta.quote_mint,
+    );
+    let treasury_base_balance = get_token_balance(sdk, &treasury_base_ata).await;
+    let treasury_quote_balance = get_token_balance(sdk, &treasury_quote_ata).await;
+
+    let total_base_atoms: u64 = plans
+        .iter()
+        .map(|p| p.base_lots * meta.base_atoms_per_base_lot)
+        .sum();
+    let total_quote_atoms: u64 = plans
+        .iter()
+        .map(|p| p.quote_lots * meta.quote_atoms_per_quote_lot)
+        .sum();
+
+    print_summary!(ix_only, "Treasury: {}", treasury.pubkey());
+    print_summary!(
+        ix_only,
+        "Treasury base balance: {}",
+        format_atoms(treasury_base_balance, meta.base_decimals)
+    );
+    print_summary!(
+        ix_only,
+        "Treasury quote balance: {}",
+        format_atoms(treasury_quote_balance, meta.quote_decimals)
+    );
+    print_summary!(ix_only, "--------------------------------");
+    for plan in plans.iter() {
+        print_summary!(
+            ix_only,
+            "{}: base +{}, quote +{}, {}",
+            plan.wallet,
+            format_atoms(
+                plan.base_lots * meta.base_atoms_per_base_lot,
+                meta.base_decimals
+            ),
+            format_atoms(
+                plan.quote_lots * meta.quote_atoms_per_quote_lot,
+                meta.quote_decimals
+            ),
+            match (&plan.keypair, plan.has_seat) {
+                (Some(_), true) => "transfer and deposit",
+                (Some(_), false) => "transfer only (no seat on market)",
+                (None, _) => "transfer only (no maker keypair)",
+            }
+        );
+    }
+    print_summary!(ix_only, "--------------------------------");
+    print_summary!(
+        ix_only,
+        "Total: base {}, quote {}",
+        format_atoms(total_base_atoms, meta.base_decimals),
+        format_atoms(total_quote_atoms, meta.quote_decimals)
+    );
+
+    if total_base_atoms > treasury_base_balance || total_quote_atoms > treasury_quote_balance {
+        return Err(anyhow::anyhow!(
+            "Treasury balances are insufficient to fund all makers"
+        ));
+    }
+
+    if !execute && !ix_only {
+        println!("Dry run only. Pass --execute to send the transfers.");
+        return Ok(());
+    }
+
+    // With --ix-only, the instructions for every maker are printed as one JSON array
+    let mut ix_only_instructions = vec![];
+    for plan in plans.iter() {
+        if plan.base_lots == 0 && plan.quote_lots == 0 {
+            continue;
+        }
+        let mut instructions = vec![];
+        let mut signers = vec![treasury];
+        for (mint, treasury_ata, amount) in [
+            (
+                &meta.base_mint,
+                &treasury_base_ata,
+                plan.base_lots * meta.base_atoms_per_base_lot,
+            ),
+            (
+                &meta.quote_mint,
+                &treasury_quote_ata,
+                plan.quote_lots * meta.quote_atoms_per_quote_lot,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let maker_ata =
+                spl_associated_token_account::get_associated_token_address(&plan.wallet, mint);
+            if sdk.client.get_account(&maker_ata).await.is_err() {
+                instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account(
+                        &treasury.pubkey(),
+                        &plan.wallet,
+                        mint,
+                        &spl_token::id(),
+                    ),
+                );
+            }
+            instructions.push(spl_token::instruction::transfer(
+                &spl_token::id(),
+                treasury_ata,
+                &maker_ata,
+                &treasury.pubkey(),
+                &[],
+                amount,
+            )?);
+        }
+        if let (Some(keypair), true) = (&plan.keypair, plan.has_seat) {
+            instructions.push(create_deposit_funds_instruction(
+                market_pubkey,
+                &plan.wallet,
+                &meta.base_mint,
+                &meta.quote_mint,
+                &DepositParams {
+                    quote_lots_to_deposit: plan.quote_lots,
+                    base_lots_to_deposit: plan.base_lots,
+                },
+            ));
+            signers.push(keypair);
+        }
+        if ix_only {
+            ix_only_instructions.extend(instructions);
+            continue;
+        }
+        let tx = sdk
+            .client
+            .sign_send_instructions(instructions, signers)
+            .await?;
+        println!("Funded {}, transaction: {}", plan.wallet, tx);
+    }
+    if ix_only {
+        print_instructions_json(&ix_only_instructions)?;
+    }
+
+    Ok(())
+}
+
+async fn get_token_balance(sdk: &SDKClient, token_account: &Pubkey) -> u64 {
+    match sdk.client.get_account_data(token_account).await {
+        Ok(data) => spl_token::state::Account::unpack(&data)
+            .map(|a| a.amount)
+            .unwrap_or(0),
+        Err(_) => 0,
+    }
+}
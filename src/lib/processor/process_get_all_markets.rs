@@ -1,18 +1,100 @@
-use crate::helpers::{market_helpers::get_all_markets, print_helpers::print_market_summary_data};
+use crate::helpers::log_helpers::new_timed_sdk_client;
+use crate::helpers::{
+    cache_helpers::{read_cache, read_cached_cluster},
+    history_helpers::{get_market_fills_in_range, get_unix_timestamp},
+    market_helpers::{
+        get_all_markets, get_base_and_quote_amounts, get_best_bid_and_ask,
+        get_market_data_and_clock, get_vault_balances, load_market, ticks_to_float_price,
+    },
+    output_helpers::{
+        print_json, print_template, MarketStatsOutput, MarketSummaryOutput, OutputFormat,
+    },
+    print_helpers::{
+        print_market_stats_totals, print_market_summary_data, print_market_summary_markdown,
+        print_market_summary_table, print_market_summary_with_stats,
+    },
+};
+use crate::print_info;
+use crate::processor::process_warm_cache::CachedMarketMetadata;
 use anyhow::anyhow;
+use clap::ValueEnum;
 use ellipsis_client::EllipsisClient;
 use phoenix::program::MarketHeader;
-use phoenix_sdk::sdk_client::SDKClient;
+use phoenix_sdk::sdk_client::*;
 use serde::{Deserialize, Serialize};
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use std::{mem::size_of, str::FromStr};
 
-pub async fn process_get_all_markets(client: &EllipsisClient) -> anyhow::Result<()> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MarketSortKey {
+    Tvl,
+    Spread,
+    Volume,
+}
+
+// Restricts listings to markets whose base and quote tokens match, each given as a mint or a symbol
+// from the Phoenix config
+#[derive(Clone, Debug, Default)]
+pub struct MarketFilter {
+    pub base: Option<String>,
+    pub quote: Option<String>,
+}
+
+impl MarketFilter {
+    pub fn matches(&self, summary: &MarketSummaryOutput) -> bool {
+        let token_matches =
+            |filter: &Option<String>, mint: &String, symbol: &Option<String>| match filter {
+                Some(filter) => {
+                    filter == mint
+                        || matches!(symbol, Some(symbol) if symbol.eq_ignore_ascii_case(filter))
+                }
+                None => true,
+            };
+        token_matches(&self.base, &summary.base_mint, &summary.base_symbol)
+            && token_matches(&self.quote, &summary.quote_mint, &summary.quote_symbol)
+    }
+}
+
+// Print the summary right away, or hold onto it when it has to be printed together with the
+// others or wait for stats
+fn add_market_summary(
+    summary: MarketSummaryOutput,
+    summaries: &mut Vec<MarketSummaryOutput>,
+    filter: &MarketFilter,
+    output: OutputFormat,
+    with_stats: bool,
+) {
+    if !filter.matches(&summary) {
+        return;
+    }
+    match output {
+        // Stats are fetched after the scan, so summaries are collected for every format then
+        OutputFormat::Text | OutputFormat::Csv | OutputFormat::Yaml | OutputFormat::Jsonl
+            if !with_stats =>
+        {
+            print_market_summary_data(&summary)
+        }
+        _ => summaries.push(summary),
+    }
+}
+
+pub async fn process_get_all_markets(
+    client: &EllipsisClient,
+    filter: &MarketFilter,
+    stats: Option<&mut SDKClient>,
+    sort_by: Option<MarketSortKey>,
+    output: OutputFormat,
+    wide: bool,
+) -> anyhow::Result<()> {
     let config = get_phoenix_config(client).await?;
     let accounts = get_all_markets(client).await?;
 
-    println!("Found {} market(s)", accounts.len());
+    if output == OutputFormat::Text && filter.base.is_none() && filter.quote.is_none() {
+        print_info!("Found {} market(s)", accounts.len());
+    }
+    let mut summaries = vec![];
 
     //Deserialize market accounts and print summary information
     for (market_pubkey, mut market_account) in accounts {
@@ -23,11 +105,122 @@ pub async fn process_get_all_markets(client: &EllipsisClient) -> anyhow::Result<
             .map_err(|e| anyhow!("Error getting market header. Error: {:?}", e))?;
 
         let (base_mint_symbol, quote_mint_symbol) = get_base_and_quote_symbols(&config, header);
-        print_market_summary_data(&market_pubkey, header, base_mint_symbol, quote_mint_symbol);
+        let summary =
+            MarketSummaryOutput::new(&market_pubkey, header, base_mint_symbol, quote_mint_symbol);
+        add_market_summary(summary, &mut summaries, filter, output, stats.is_some());
+    }
+    if let Some(sdk) = stats {
+        add_market_stats(sdk, &mut summaries, sort_by).await?;
+    }
+    print_market_summaries(&summaries, output, wide)
+}
+
+fn print_market_summaries(
+    summaries: &[MarketSummaryOutput],
+    output: OutputFormat,
+    wide: bool,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => print_json(&summaries)?,
+        OutputFormat::Table => print_market_summary_table(summaries, wide),
+        OutputFormat::Markdown => print_market_summary_markdown(summaries),
+        OutputFormat::Template => print_template(&summaries)?,
+        OutputFormat::Text | OutputFormat::Csv | OutputFormat::Yaml | OutputFormat::Jsonl => {
+            // Summaries only reach here for the text formats when stats were fetched
+            for summary in summaries.iter() {
+                print_market_summary_with_stats(summary);
+            }
+            print_market_stats_totals(summaries);
+        }
     }
     Ok(())
 }
 
+// Fetch the top of book and vault balances of every market, plus the last day's volume when
+// sorting by it since that requires scanning each market's transaction history. Markets are then
+// sorted with the largest TVL or volume, or the tightest spread, first.
+async fn add_market_stats(
+    sdk: &mut SDKClient,
+    summaries: &mut [MarketSummaryOutput],
+    sort_by: Option<MarketSortKey>,
+) -> anyhow::Result<()> {
+    let with_volume = sort_by == Some(MarketSortKey::Volume);
+    for summary in summaries.iter_mut() {
+        let market_pubkey = Pubkey::from_str(&summary.market)?;
+        summary.stats = Some(get_market_stats(sdk, &market_pubkey, with_volume).await?);
+    }
+    let sort_value = |summary: &MarketSummaryOutput| {
+        let stats = summary.stats.as_ref()?;
+        match sort_by? {
+            MarketSortKey::Tvl => stats.tvl.map(|tvl| -tvl),
+            MarketSortKey::Spread => stats.spread_bps,
+            MarketSortKey::Volume => stats.volume_24h.map(|volume| -volume),
+        }
+    };
+    // Markets without a value, such as an empty book when sorting by spread, go last
+    summaries.sort_by(|a, b| match (sort_value(a), sort_value(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    Ok(())
+}
+
+async fn get_market_stats(
+    sdk: &mut SDKClient,
+    market_pubkey: &Pubkey,
+    with_volume: bool,
+) -> anyhow::Result<MarketStatsOutput> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (header, market) = load_market(&market_account_data)?;
+    let meta = MarketMetadata::from_header(header)?;
+    let (best_bid, best_ask) = get_best_bid_and_ask(market, &clock);
+    let best_bid = best_bid.map(|ticks| ticks_to_float_price(&meta, ticks));
+    let best_ask = best_ask.map(|ticks| ticks_to_float_price(&meta, ticks));
+    let spread_bps = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => Some((ask - bid) / ((ask + bid) / 2.0) * 10_000.0),
+        _ => None,
+    };
+
+    let ((_, base_balance), (_, quote_balance)) =
+        get_vault_balances(sdk, market_pubkey, &meta).await?;
+    let base_balance = base_balance as f64 / 10f64.powi(meta.base_decimals as i32);
+    let quote_balance = quote_balance as f64 / 10f64.powi(meta.quote_decimals as i32);
+    // Base tokens are valued at the midpoint, or at the only side of the book that has orders
+    let base_price = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+        (bid, ask) => bid.or(ask),
+    };
+    let tvl = base_price.map(|price| base_balance * price + quote_balance);
+
+    let volume_24h = if with_volume {
+        // Parsing the market's events requires its metadata in the SDK
+        sdk.add_market(market_pubkey).await?;
+        let end = get_unix_timestamp()?;
+        let fills = get_market_fills_in_range(sdk, market_pubkey, end - 24 * 60 * 60, end).await?;
+        Some(
+            fills
+                .iter()
+                .map(|(_, fill)| {
+                    get_base_and_quote_amounts(&meta, fill.price_in_ticks, fill.base_lots_filled).1
+                })
+                .sum::<f64>(),
+        )
+    } else {
+        None
+    };
+
+    Ok(MarketStatsOutput {
+        best_bid,
+        best_ask,
+        spread_bps,
+        tvl,
+        volume_24h,
+    })
+}
+
 pub fn get_base_and_quote_symbols(
     config: &MasterConfig,
     header: &MarketHeader,
@@ -51,8 +244,17 @@ pub fn get_base_and_quote_symbols(
 pub async fn process_get_all_markets_no_gpa(
     client: &EllipsisClient,
     network_url: &str,
+    filter: &MarketFilter,
+    stats: Option<&mut SDKClient>,
+    sort_by: Option<MarketSortKey>,
+    output: OutputFormat,
+    wide: bool,
 ) -> anyhow::Result<()> {
     let config = get_phoenix_config(client).await?;
+    // Metadata cached by warm-cache saves fetching each market account
+    let cached_markets =
+        read_cache::<Vec<CachedMarketMetadata>>(get_cluster(client).await?, "markets")
+            .unwrap_or_default();
     let markets = config
         .markets
         .iter()
@@ -60,11 +262,28 @@ pub async fn process_get_all_markets_no_gpa(
         .collect::<Vec<String>>()
         .clone();
 
-    println!("Found {} market(s)", markets.len());
+    if output == OutputFormat::Text && filter.base.is_none() && filter.quote.is_none() {
+        print_info!("Found {} market(s)", markets.len());
+    }
+    let mut summaries = vec![];
 
     for market in markets {
+        if let Some(cached) = cached_markets.iter().find(|m| m.market == market) {
+            let summary = MarketSummaryOutput {
+                market: cached.market.clone(),
+                base_symbol: cached.base_symbol.clone(),
+                quote_symbol: cached.quote_symbol.clone(),
+                base_mint: cached.base_mint.clone(),
+                quote_mint: cached.quote_mint.clone(),
+                authority: cached.authority.clone(),
+                stats: None,
+            };
+            add_market_summary(summary, &mut summaries, filter, output, stats.is_some());
+            continue;
+        }
         let market_pubkey = Pubkey::from_str(&market)?;
-        let sdk = SDKClient::new(&client.payer, network_url).await?;
+        let sdk =
+            new_timed_sdk_client(&client.payer, network_url, CommitmentConfig::confirmed()).await?;
 
         let market_account_data = sdk.client.get_account_data(&market_pubkey).await?;
         let (header_bytes, _market_bytes) = market_account_data.split_at(size_of::<MarketHeader>());
@@ -72,9 +291,14 @@ pub async fn process_get_all_markets_no_gpa(
             .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
 
         let (base_mint_symbol, quote_mint_symbol) = get_base_and_quote_symbols(&config, header);
-        print_market_summary_data(&market_pubkey, header, base_mint_symbol, quote_mint_symbol);
+        let summary =
+            MarketSummaryOutput::new(&market_pubkey, header, base_mint_symbol, quote_mint_symbol);
+        add_market_summary(summary, &mut summaries, filter, output, stats.is_some());
     }
-    Ok(())
+    if let Some(sdk) = stats {
+        add_market_stats(sdk, &mut summaries, sort_by).await?;
+    }
+    print_market_summaries(&summaries, output, wide)
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -100,16 +324,39 @@ pub struct MarketConfig {
     pub quote_mint: String,
 }
 
-pub async fn get_phoenix_config(client: &EllipsisClient) -> anyhow::Result<MasterConfig> {
+pub async fn get_cluster(client: &EllipsisClient) -> anyhow::Result<&'static str> {
+    // Use the cluster cached by warm-cache for this RPC URL if it is still fresh
+    match read_cached_cluster(&client.url()).as_deref() {
+        Some("mainnet-beta") => return Ok("mainnet-beta"),
+        Some("devnet") => return Ok("devnet"),
+        Some("localhost") => return Ok("localhost"),
+        _ => {}
+    }
+    fetch_cluster(client).await
+}
+
+pub async fn fetch_cluster(client: &EllipsisClient) -> anyhow::Result<&'static str> {
     let genesis = client.get_genesis_hash().await?;
 
     //hardcoded in the genesis hashes for mainnet and devnet
-    let cluster = match genesis.to_string().as_str() {
+    Ok(match genesis.to_string().as_str() {
         "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d" => "mainnet-beta",
         "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG" => "devnet",
         _ => "localhost",
-    };
+    })
+}
+
+pub async fn get_phoenix_config(client: &EllipsisClient) -> anyhow::Result<MasterConfig> {
+    let cluster = get_cluster(client).await?;
+
+    // Use the config persisted by warm-cache if it is still fresh
+    if let Some(config) = read_cache::<MasterConfig>(cluster, "config") {
+        return Ok(config);
+    }
+    fetch_phoenix_config(cluster).await
+}
 
+pub async fn fetch_phoenix_config(cluster: &str) -> anyhow::Result<MasterConfig> {
     let body = reqwest::get(
         "https://raw.githubusercontent.com/Ellipsis-Labs/phoenix-sdk/master/master_config.json",
     )
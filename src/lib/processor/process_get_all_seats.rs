@@ -0,0 +1,74 @@
+use borsh::BorshDeserialize;
+use clap::ValueEnum;
+use phoenix::program::status::SeatApprovalStatus;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::get_seats_for_market;
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SeatStatusFilter {
+    Approved,
+    NotApproved,
+    Retired,
+}
+
+impl From<SeatStatusFilter> for SeatApprovalStatus {
+    fn from(filter: SeatStatusFilter) -> Self {
+        match filter {
+            SeatStatusFilter::Approved => SeatApprovalStatus::Approved,
+            SeatStatusFilter::NotApproved => SeatApprovalStatus::NotApproved,
+            SeatStatusFilter::Retired => SeatApprovalStatus::Retired,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SeatOutput {
+    pub seat_address: String,
+    pub trader: String,
+    pub status: String,
+}
+
+pub async fn process_get_all_seats(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    status: Option<SeatStatusFilter>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let accounts = get_seats_for_market(sdk, market_pubkey, status.map(Into::into)).await?;
+
+    // Seat layout: 8 byte discriminant, market, trader, then the approval status as a u64
+    let mut seats = accounts
+        .iter()
+        .map(|(seat_address, account)| {
+            let trader = Pubkey::try_from_slice(&account.data[40..72])?;
+            let status = SeatApprovalStatus::from(u64::try_from_slice(&account.data[72..80])?);
+            Ok(SeatOutput {
+                seat_address: seat_address.to_string(),
+                trader: trader.to_string(),
+                status: status.to_string(),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    seats.sort_by(|a, b| a.trader.cmp(&b.trader));
+
+    if output == OutputFormat::Json {
+        return print_json(&seats);
+    }
+    if seats.is_empty() {
+        println!("No seats found");
+        return Ok(());
+    }
+    println!("{0: <44} | {1: <44} | {2: <12}", "Trader", "Seat", "Status");
+    for seat in seats.iter() {
+        println!(
+            "{0: <44} | {1: <44} | {2: <12}",
+            seat.trader, seat.seat_address, seat.status
+        );
+    }
+    println!("{} seat(s)", seats.len());
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use phoenix::program::instruction::PhoenixInstruction;
+use phoenix_sdk::sdk_client::*;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{UiTransactionEncoding, UiTransactionTokenBalance};
+
+use crate::helpers::history_helpers::{get_unix_timestamp, SignatureHistoryConfig, SignaturePager};
+use crate::helpers::instruction_helpers::get_phoenix_instructions;
+use crate::helpers::output_helpers::format_timestamp;
+use crate::print_info;
+
+pub const BALANCE_LEDGER_HEADER: &str = "timestamp,signature,slot,mint,category,amount";
+
+struct LedgerEntry {
+    timestamp: i64,
+    signature: Signature,
+    slot: u64,
+    mint: String,
+    category: &'static str,
+    amount: i128,
+    decimals: u8,
+}
+
+// Attribute every token balance change of a wallet over a time range to a fill, fee, deposit,
+// withdrawal, or transfer, by decoding the transactions responsible for it
+pub async fn process_get_balance_ledger(
+    sdk: &mut SDKClient,
+    wallet: &Pubkey,
+    start: Option<i64>,
+    end: Option<i64>,
+    out: Option<&str>,
+) -> anyhow::Result<()> {
+    let now = get_unix_timestamp()?;
+    let start = start.unwrap_or(now - 86_400);
+    let end = end.unwrap_or(now);
+    let config = SignatureHistoryConfig {
+        min_block_time: Some(start),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut signatures = SignaturePager::new(&sdk.client, wallet, config)?
+        .collect()
+        .await?;
+    signatures.retain(|info| info.block_time.map(|t| t <= end).unwrap_or(false));
+    signatures.reverse();
+    print_info!("Scanning {} transaction(s)", signatures.len());
+
+    let mut entries = vec![];
+    for info in signatures.iter() {
+        entries.extend(get_ledger_entries(sdk, wallet, &info.signature).await?);
+    }
+
+    if let Some(out) = out {
+        let path = shellexpand::tilde(out).to_string();
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "{}", BALANCE_LEDGER_HEADER)?;
+        for entry in entries.iter() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{}",
+                entry.timestamp,
+                entry.signature,
+                entry.slot,
+                entry.mint,
+                entry.category,
+                format_signed_amount(entry.amount, entry.decimals),
+            )?;
+        }
+        println!("Wrote {} ledger entries to {}", entries.len(), path);
+        return Ok(());
+    }
+
+    println!(
+        "{0: <25} | {1: <88} | {2: <44} | {3: <10} | {4: <20}",
+        "Time", "Signature", "Mint", "Category", "Amount"
+    );
+    // Net change per mint and category
+    let mut totals: BTreeMap<(&str, &str), (i128, u8)> = BTreeMap::new();
+    for entry in entries.iter() {
+        println!(
+            "{0: <25} | {1: <88} | {2: <44} | {3: <10} | {4: <20}",
+            format_timestamp(entry.timestamp),
+            entry.signature.to_string(),
+            entry.mint,
+            entry.category,
+            format_signed_amount(entry.amount, entry.decimals),
+        );
+        let total = totals
+            .entry((&entry.mint, entry.category))
+            .or_insert((0, entry.decimals));
+        total.0 += entry.amount;
+    }
+    println!("--------------------------------");
+    for ((mint, category), (amount, decimals)) in totals.iter() {
+        println!(
+            "{0: <44} | {1: <10} | {2: <20}",
+            mint,
+            category,
+            format_signed_amount(*amount, *decimals)
+        );
+    }
+    Ok(())
+}
+
+async fn get_ledger_entries(
+    sdk: &mut SDKClient,
+    wallet: &Pubkey,
+    signature: &Signature,
+) -> anyhow::Result<Vec<LedgerEntry>> {
+    let tx = sdk
+        .client
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    let meta = match tx.transaction.meta.as_ref() {
+        Some(meta) => meta,
+        None => return Ok(vec![]),
+    };
+
+    // Net change of the wallet's token accounts, per mint
+    let mut changes: BTreeMap<String, (i128, u8)> = BTreeMap::new();
+    let mut apply = |balances: Option<Vec<UiTransactionTokenBalance>>, sign: i128| {
+        for balance in balances.unwrap_or_default() {
+            let owner: Option<String> = balance.owner.into();
+            if owner != Some(wallet.to_string()) {
+                continue;
+            }
+            let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+            let change = changes
+                .entry(balance.mint)
+                .or_insert((0, balance.ui_token_amount.decimals));
+            change.0 += sign * amount;
+        }
+    };
+    apply(meta.pre_token_balances.clone().into(), -1);
+    apply(meta.post_token_balances.clone().into(), 1);
+    changes.retain(|_, (amount, _)| *amount != 0);
+    if changes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Categorize by the Phoenix instructions in the transaction, including those invoked through
+    // CPI such as aggregator swaps. Changes without one are transfers
+    let phoenix_instructions = get_phoenix_instructions(&tx.transaction)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|ix| ix.instruction)
+        .collect::<Vec<_>>();
+
+    // Taker fees are split out of fills, so the fill entry is the gross traded amount
+    let mut fees: BTreeMap<String, i128> = BTreeMap::new();
+    let mut is_taker = false;
+    if !phoenix_instructions.is_empty() {
+        let events = sdk
+            .parse_events_from_transaction(signature)
+            .await
+            .unwrap_or_default();
+        is_taker = events
+            .iter()
+            .any(|e| matches!(&e.details, MarketEventDetails::Fill(fill) if fill.taker == *wallet));
+        for event in events.iter().filter(|_| is_taker) {
+            if let MarketEventDetails::FillSummary(fill_summary) = &event.details {
+                if !sdk.markets.contains_key(&event.market) {
+                    sdk.add_market(&event.market).await?;
+                }
+                if let Some(market) = sdk.markets.get(&event.market) {
+                    *fees.entry(market.quote_mint.to_string()).or_default() +=
+                        fill_summary.total_quote_fees as i128;
+                }
+            }
+        }
+    }
+    let category = if is_taker {
+        "fill"
+    } else if phoenix_instructions.iter().any(|ix| {
+        matches!(
+            ix,
+            PhoenixInstruction::DepositFunds
+                | PhoenixInstruction::PlaceLimitOrder
+                | PhoenixInstruction::PlaceMultiplePostOnlyOrders
+        )
+    }) {
+        // Resting orders placed without free funds lock tokens moved in from the wallet
+        "deposit"
+    } else if !phoenix_instructions.is_empty() {
+        // Withdrawals and cancels not made with free funds return tokens to the wallet
+        "withdrawal"
+    } else {
+        "transfer"
+    };
+
+    let timestamp = tx.block_time.unwrap_or_default();
+    let mut entries = vec![];
+    for (mint, (amount, decimals)) in changes {
+        let fee = fees.get(&mint).copied().unwrap_or(0);
+        entries.push(LedgerEntry {
+            timestamp,
+            signature: *signature,
+            slot: tx.slot,
+            mint: mint.clone(),
+            category,
+            amount: amount + fee,
+            decimals,
+        });
+        if fee != 0 {
+            entries.push(LedgerEntry {
+                timestamp,
+                signature: *signature,
+                slot: tx.slot,
+                mint,
+                category: "fee",
+                amount: -fee,
+                decimals,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+fn format_signed_amount(amount: i128, decimals: u8) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    format!(
+        "{}{}",
+        sign,
+        get_decimal_string(amount.unsigned_abs() as u64, decimals as u32)
+    )
+}
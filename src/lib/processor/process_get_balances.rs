@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+
+use super::process_get_all_markets::get_phoenix_config;
+use crate::helpers::market_helpers::load_market;
+use crate::helpers::output_helpers::{format_atoms, print_json, OutputFormat};
+
+// Maximum number of accounts per getMultipleAccounts request
+pub(crate) const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MarketBalanceOutput {
+    pub market: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_free: String,
+    pub base_locked: String,
+    pub quote_free: String,
+    pub quote_locked: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TokenBalanceOutput {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub wallet: String,
+    pub free: String,
+    pub locked: String,
+    pub total: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BalancesOutput {
+    pub wallet: String,
+    pub tokens: Vec<TokenBalanceOutput>,
+    pub markets: Vec<MarketBalanceOutput>,
+}
+
+#[derive(Default)]
+struct TokenAtoms {
+    decimals: u32,
+    wallet: u64,
+    free: u64,
+    locked: u64,
+}
+
+// Consolidate everything a wallet holds on Phoenix: its free and locked balances on every market
+// in the config, plus the associated token account balance of each token those markets trade
+pub async fn process_get_balances(
+    sdk: &SDKClient,
+    wallet: &Pubkey,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let config = get_phoenix_config(&sdk.client).await?;
+    let market_pubkeys = config
+        .markets
+        .iter()
+        .map(|market| Pubkey::from_str(&market.market))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut tokens: BTreeMap<Pubkey, TokenAtoms> = BTreeMap::new();
+    let mut markets = vec![];
+    for chunk in market_pubkeys.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let accounts = sdk.client.get_multiple_accounts(chunk).await?;
+        for (market_pubkey, account) in chunk.iter().zip(accounts) {
+            let account = match account {
+                Some(account) => account,
+                None => continue,
+            };
+            let (header, market) = load_market(&account.data)?;
+            let meta = MarketMetadata::from_header(header)?;
+            tokens.entry(meta.base_mint).or_default().decimals = meta.base_decimals;
+            tokens.entry(meta.quote_mint).or_default().decimals = meta.quote_decimals;
+            let state = match market.get_trader_state(wallet) {
+                Some(state) => state,
+                None => continue,
+            };
+            let base_free = u64::from(state.base_lots_free) * meta.base_atoms_per_base_lot;
+            let base_locked = u64::from(state.base_lots_locked) * meta.base_atoms_per_base_lot;
+            let quote_free = u64::from(state.quote_lots_free) * meta.quote_atoms_per_quote_lot;
+            let quote_locked = u64::from(state.quote_lots_locked) * meta.quote_atoms_per_quote_lot;
+            if base_free + base_locked + quote_free + quote_locked == 0 {
+                continue;
+            }
+            let base = tokens.entry(meta.base_mint).or_default();
+            base.free += base_free;
+            base.locked += base_locked;
+            let quote = tokens.entry(meta.quote_mint).or_default();
+            quote.free += quote_free;
+            quote.locked += quote_locked;
+            markets.push(MarketBalanceOutput {
+                market: market_pubkey.to_string(),
+                base_mint: meta.base_mint.to_string(),
+                quote_mint: meta.quote_mint.to_string(),
+                base_free: format_atoms(base_free, meta.base_decimals),
+                base_locked: format_atoms(base_locked, meta.base_decimals),
+                quote_free: format_atoms(quote_free, meta.quote_decimals),
+                quote_locked: format_atoms(quote_locked, meta.quote_decimals),
+            });
+        }
+    }
+
+    let mints = tokens.keys().copied().collect::<Vec<_>>();
+    for chunk in mints.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let atas = chunk
+            .iter()
+            .map(|mint| spl_associated_token_account::get_associated_token_address(wallet, mint))
+            .collect::<Vec<_>>();
+        let accounts = sdk.client.get_multiple_accounts(&atas).await?;
+        for (mint, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                if let Ok(token_account) = spl_token::state::Account::unpack(&account.data) {
+                    tokens.entry(*mint).or_default().wallet = token_account.amount;
+                }
+            }
+        }
+    }
+
+    let symbol = |mint: &Pubkey| {
+        config
+            .tokens
+            .iter()
+            .find(|t| t.mint == mint.to_string())
+            .map(|t| t.symbol.clone())
+    };
+    let balances = BalancesOutput {
+        wallet: wallet.to_string(),
+        tokens: tokens
+            .iter()
+            .filter(|(_, atoms)| atoms.wallet + atoms.free + atoms.locked > 0)
+            .map(|(mint, atoms)| TokenBalanceOutput {
+                mint: mint.to_string(),
+                symbol: symbol(mint),
+                wallet: format_atoms(atoms.wallet, atoms.decimals),
+                free: format_atoms(atoms.free, atoms.decimals),
+                locked: format_atoms(atoms.locked, atoms.decimals),
+                total: format_atoms(atoms.wallet + atoms.free + atoms.locked, atoms.decimals),
+            })
+            .collect(),
+        markets,
+    };
+
+    if output == OutputFormat::Json {
+        return print_json(&balances);
+    }
+    if balances.tokens.is_empty() {
+        println!("No balances found for {}", wallet);
+        return Ok(());
+    }
+    if !balances.markets.is_empty() {
+        println!(
+            "{0: <44} | {1: <15} | {2: <15} | {3: <15} | {4: <15}",
+            "Market", "Base Free", "Base Locked", "Quote Free", "Quote Locked"
+        );
+        for market in balances.markets.iter() {
+            println!(
+                "{0: <44} | {1: <15} | {2: <15} | {3: <15} | {4: <15}",
+                market.market,
+                market.base_free,
+                market.base_locked,
+                market.quote_free,
+                market.quote_locked
+            );
+        }
+        println!();
+    }
+    println!(
+        "{0: <44} | {1: <8} | {2: <15} | {3: <15} | {4: <15} | {5: <15}",
+        "Mint", "Symbol", "Wallet", "Free", "Locked", "Total"
+    );
+    for token in balances.tokens.iter() {
+        println!(
+            "{0: <44} | {1: <8} | {2: <15} | {3: <15} | {4: <15} | {5: <15}",
+            token.mint,
+            token.symbol.as_deref().unwrap_or("-"),
+            token.wallet,
+            token.free,
+            token.locked,
+            token.total
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    get_best_bid_and_ask, get_market_data_and_clock, load_market, ticks_to_float_price,
+};
+use crate::helpers::output_helpers::{is_raw_units, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BboOutput {
+    pub market: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub mid: Option<f64>,
+    pub spread_bps: Option<f64>,
+}
+
+// Prices come from the market header rather than the SDK's metadata cache, so the whole command
+// is a single RPC call for the market and clock accounts
+pub async fn process_get_bbo(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (header, market) = load_market(&market_account_data)?;
+    let meta = MarketMetadata::from_header(header)?;
+    let (best_bid, best_ask) = get_best_bid_and_ask(market, &clock);
+
+    let price = |price_in_ticks: u64| {
+        if is_raw_units() {
+            price_in_ticks as f64
+        } else {
+            ticks_to_float_price(&meta, price_in_ticks)
+        }
+    };
+    let (mid, spread_bps) = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => {
+            let mid = (bid + ask) as f64 / 2.0;
+            (
+                Some(mid * price(1)),
+                Some(ask.saturating_sub(bid) as f64 / mid * 10_000.0),
+            )
+        }
+        _ => (None, None),
+    };
+    let bbo = BboOutput {
+        market: market_pubkey.to_string(),
+        best_bid: best_bid.map(price),
+        best_ask: best_ask.map(price),
+        mid,
+        spread_bps,
+    };
+
+    if output == OutputFormat::Json {
+        return print_json(&bbo);
+    }
+    let format = |value: Option<f64>| value.map_or("-".to_string(), |v| v.to_string());
+    println!("Best bid: {}", format(bbo.best_bid));
+    println!("Best ask: {}", format(bbo.best_ask));
+    println!("Mid: {}", format(bbo.mid));
+    println!(
+        "Spread: {}",
+        bbo.spread_bps
+            .map_or("-".to_string(), |spread| format!("{:.2} bps", spread))
+    );
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::{Ladder, LadderOrder};
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{
+    scan_market_events_for_signatures, SignatureHistoryConfig, SignaturePager,
+};
+use crate::helpers::market_helpers::{get_market_data_and_clock, load_market};
+use crate::helpers::output_helpers::{print_json, BookOutput, OutputFormat};
+use crate::helpers::print_helpers::print_book;
+use crate::print_info;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookAtSlotOutput {
+    pub slot: u64,
+    pub replayed_from_slot: u64,
+    pub transactions_replayed: usize,
+    pub book: BookOutput,
+}
+
+// Resting orders on each side, keyed by price and sequence number, with their size in base lots
+#[derive(Default)]
+struct Orders {
+    bids: BTreeMap<(u64, u64), u64>,
+    asks: BTreeMap<(u64, u64), u64>,
+}
+
+impl Orders {
+    fn side(&mut self, side: Side) -> &mut BTreeMap<(u64, u64), u64> {
+        match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        }
+    }
+}
+
+// Reconstruct the book as it stood at the end of `slot`. The market only stores its current state,
+// so this starts from the current orders and undoes the market's events newer than `slot`, newest
+// first: placements are removed, and fills, reductions, evictions and expiries are added back.
+// Orders that had expired by `slot` but were not yet removed from the book are still included.
+pub async fn process_get_book_at_slot(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    slot: u64,
+    precision: Option<usize>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    if slot > clock.slot {
+        return Err(anyhow::anyhow!(
+            "Slot {} is ahead of the current slot {}",
+            slot,
+            clock.slot
+        ));
+    }
+    let (_, market) = load_market(&market_account_data)?;
+    let mut orders = Orders::default();
+    for side in [Side::Bid, Side::Ask] {
+        let book = orders.side(side);
+        for (order_id, order) in market.get_book(side).iter() {
+            book.insert(
+                (
+                    order_id.price_in_ticks.as_u64(),
+                    order_id.order_sequence_number,
+                ),
+                order.num_base_lots.as_u64(),
+            );
+        }
+    }
+
+    // Transactions landing after the market account was read are not reflected in it, so they
+    // are skipped rather than undone
+    let config = SignatureHistoryConfig {
+        min_slot: Some(slot + 1),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut pager = SignaturePager::new(&sdk.client, market_pubkey, config)?;
+    let mut signatures = vec![];
+    while let Some(page) = pager.next_page().await? {
+        signatures.extend(page);
+    }
+    // Without a transaction at or before the slot, the history may have been cut short and the
+    // replay would silently miss events
+    if !pager.reached_lower_bound() {
+        return Err(anyhow::anyhow!(
+            "The RPC node's transaction history for the market does not reach back to slot {}",
+            slot
+        ));
+    }
+    signatures.retain(|info| info.slot > slot && info.slot <= clock.slot);
+    let signatures = signatures
+        .iter()
+        .map(|info| info.signature)
+        .collect::<Vec<_>>();
+    let scan = scan_market_events_for_signatures(sdk, market_pubkey, &signatures).await?;
+    if let Some(signature) = scan.unparsed.first() {
+        return Err(anyhow::anyhow!(
+            "Failed to parse {} transaction(s) since slot {}, e.g. {}. The book can't be reconstructed without them",
+            scan.unparsed.len(),
+            slot,
+            signature
+        ));
+    }
+    let events = scan.events;
+    print_info!(
+        "Undoing {} event(s) from slot {} back to slot {}",
+        events.len(),
+        clock.slot,
+        slot
+    );
+    for event in events.iter().rev() {
+        match &event.details {
+            MarketEventDetails::Place(place) => undo_order_change(
+                &mut orders,
+                place.order_sequence_number,
+                place.price_in_ticks,
+                -(place.base_lots_placed as i128),
+            ),
+            MarketEventDetails::Fill(fill) => undo_order_change(
+                &mut orders,
+                fill.order_sequence_number,
+                fill.price_in_ticks,
+                fill.base_lots_filled as i128,
+            ),
+            // Orders removed for expiring are reported by the SDK as a Reduce
+            MarketEventDetails::Reduce(reduce) => undo_order_change(
+                &mut orders,
+                reduce.order_sequence_number,
+                reduce.price_in_ticks,
+                reduce.base_lots_removed as i128,
+            ),
+            MarketEventDetails::Evict(evict) => undo_order_change(
+                &mut orders,
+                evict.order_sequence_number,
+                evict.price_in_ticks,
+                evict.base_lots_evicted as i128,
+            ),
+            _ => {}
+        }
+    }
+
+    let book = orders_to_ladder(&orders);
+    if output == OutputFormat::Json {
+        return print_json(&BookAtSlotOutput {
+            slot,
+            replayed_from_slot: clock.slot,
+            transactions_replayed: signatures.len(),
+            book: BookOutput::from_ladder(sdk, market_pubkey, &book)?,
+        });
+    }
+    println!(
+        "Book at slot {} (replayed {} transaction(s) back from slot {})",
+        slot,
+        signatures.len(),
+        clock.slot
+    );
+    if book.bids.is_empty() && book.asks.is_empty() {
+        println!("Book is empty");
+    } else {
+        print_book(sdk, market_pubkey, &book, None, precision, false)?;
+    }
+    Ok(())
+}
+
+// Apply the inverse of an event's change to an order's size, dropping the order once it has none
+fn undo_order_change(
+    orders: &mut Orders,
+    order_sequence_number: u64,
+    price_in_ticks: u64,
+    base_lots: i128,
+) {
+    let book = orders.side(Side::from_order_sequence_number(order_sequence_number));
+    let key = (price_in_ticks, order_sequence_number);
+    let size = book.get(&key).copied().unwrap_or(0) as i128 + base_lots;
+    if size > 0 {
+        book.insert(key, size as u64);
+    } else {
+        book.remove(&key);
+    }
+}
+
+// Aggregate orders into price levels, best price first on each side
+fn orders_to_ladder(orders: &Orders) -> Ladder {
+    let levels = |book: &BTreeMap<(u64, u64), u64>| {
+        let mut levels: BTreeMap<u64, u64> = BTreeMap::new();
+        for ((price_in_ticks, _), size) in book.iter() {
+            *levels.entry(*price_in_ticks).or_default() += size;
+        }
+        levels
+            .into_iter()
+            .map(|(price_in_ticks, size_in_base_lots)| LadderOrder {
+                price_in_ticks,
+                size_in_base_lots,
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut bids = levels(&orders.bids);
+    bids.reverse();
+    Ladder {
+        bids,
+        asks: levels(&orders.asks),
+    }
+}
@@ -8,25 +8,32 @@ use phoenix::{
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::{clock::Clock, commitment_config::CommitmentConfig, pubkey::Pubkey, sysvar};
 
+use crate::helpers::output_helpers::{
+    is_csv_metadata, print_json, BookOutput, ExportMetadata, OutputFormat,
+};
 use crate::helpers::print_helpers::{print_book_with_trader, LadderLevelEntry};
 
 pub async fn process_get_book_levels(
     market_pubkey: &Pubkey,
     sdk: &SDKClient,
     levels: u64,
+    template: Option<&str>,
+    precision: Option<usize>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut ask_entries: Vec<LadderLevelEntry> = Vec::with_capacity(levels as usize);
     let mut bid_entries: Vec<LadderLevelEntry> = Vec::with_capacity(levels as usize);
 
     // Get market account
-    let mut market_and_clock = sdk
+    let response = sdk
         .client
         .get_multiple_accounts_with_commitment(
             &[*market_pubkey, sysvar::clock::id()],
             CommitmentConfig::confirmed(),
         )
-        .await?
-        .value;
+        .await?;
+    let slot = response.context.slot;
+    let mut market_and_clock = response.value;
 
     let market_account_data = market_and_clock
         .remove(0)
@@ -120,7 +127,32 @@ pub async fn process_get_book_levels(
         }
     }
 
-    print_book_with_trader(sdk, market_pubkey, &bid_entries, &ask_entries)?;
+    match output {
+        OutputFormat::Json => {
+            return print_json(
+                &BookOutput::from_entries(sdk, market_pubkey, &bid_entries, &ask_entries)?
+                    .with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?),
+            );
+        }
+        OutputFormat::Csv => {
+            let mut book =
+                BookOutput::from_entries(sdk, market_pubkey, &bid_entries, &ask_entries)?;
+            if is_csv_metadata() {
+                book = book.with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?);
+            }
+            book.print_csv(Some(&sdk.trader));
+            return Ok(());
+        }
+        _ => {}
+    }
+    print_book_with_trader(
+        sdk,
+        market_pubkey,
+        &bid_entries,
+        &ask_entries,
+        template,
+        precision,
+    )?;
 
     Ok(())
 }
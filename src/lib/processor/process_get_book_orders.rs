@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{get_market_data_and_clock, load_market};
+use crate::helpers::output_helpers::{display_base_size, display_price, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookOrderOutput {
+    pub side: String,
+    pub price: f64,
+    pub price_in_ticks: u64,
+    pub size: f64,
+    pub maker: String,
+    pub order_sequence_number: u64,
+}
+
+// Print every resting order in the book (L3) rather than the aggregated levels of the Ladder,
+// asks from the highest price down and then bids, each level in time priority
+pub async fn process_get_book_orders(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    trader: Option<&Pubkey>,
+    min_price: Option<f64>,
+    max_price: Option<f64>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+
+    // Orders only reference their maker by seat index
+    let makers = market
+        .get_registered_traders()
+        .iter()
+        .filter_map(|(pubkey, _)| Some((market.get_trader_index(pubkey)? as u64, *pubkey)))
+        .collect::<HashMap<_, _>>();
+    let trader_index = match trader {
+        Some(trader) => Some(
+            market
+                .get_trader_index(trader)
+                .ok_or_else(|| anyhow::anyhow!("Trader {} has no seat on this market", trader))?
+                as u64,
+        ),
+        None => None,
+    };
+
+    let mut orders = vec![];
+    for side in [Side::Ask, Side::Bid] {
+        let mut side_orders = vec![];
+        for (order_id, order) in market.get_book(side).iter() {
+            if order.is_expired(clock.slot, clock.unix_timestamp as u64)
+                || matches!(trader_index, Some(index) if order.trader_index != index)
+            {
+                continue;
+            }
+            let price = display_price(sdk, market_pubkey, order_id.price_in_ticks.as_u64())?;
+            if matches!(min_price, Some(min) if price < min)
+                || matches!(max_price, Some(max) if price > max)
+            {
+                continue;
+            }
+            side_orders.push(BookOrderOutput {
+                side: format!("{:?}", side),
+                price,
+                price_in_ticks: order_id.price_in_ticks.as_u64(),
+                size: display_base_size(sdk, market_pubkey, order.num_base_lots.as_u64())?,
+                maker: makers
+                    .get(&order.trader_index)
+                    .map(|maker| maker.to_string())
+                    .unwrap_or_default(),
+                order_sequence_number: order_id.order_sequence_number,
+            });
+        }
+        // The book iterates from the best price outward, so asks are re-sorted to print top down.
+        // The sort is stable, so orders within a level keep their time priority
+        if side == Side::Ask {
+            side_orders.sort_by(|a, b| b.price_in_ticks.cmp(&a.price_in_ticks));
+        }
+        orders.extend(side_orders);
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&orders);
+    }
+    if orders.is_empty() {
+        println!("No orders match");
+        return Ok(());
+    }
+    println!(
+        "{0: <4} | {1: <15} | {2: <15} | {3: <44} | {4: <20}",
+        "Side", "Price", "Size", "Maker", "Sequence Number"
+    );
+    for order in orders.iter() {
+        println!(
+            "{0: <4} | {1: <15} | {2: <15} | {3: <44} | {4: <20}",
+            order.side, order.price, order.size, order.maker, order.order_sequence_number
+        );
+    }
+    Ok(())
+}
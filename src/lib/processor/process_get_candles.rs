@@ -0,0 +1,118 @@
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_market_fills_in_range, get_unix_timestamp};
+use crate::helpers::market_helpers::get_base_and_quote_amounts;
+use crate::helpers::output_helpers::{
+    display_base_size, display_price, format_timestamp, print_json, OutputFormat,
+};
+
+pub const CANDLES_CSV_HEADER: &str = "start,open,high,low,close,base_volume,quote_volume,trades";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CandleOutput {
+    pub start: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    pub trades: u64,
+}
+
+// Aggregate the market's fills between `from` and `to` into OHLCV candles `interval_seconds`
+// wide, aligned to multiples of the interval. Intervals without fills produce no candle.
+pub async fn process_get_candles(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    interval_seconds: u64,
+    from: Option<i64>,
+    to: Option<i64>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if interval_seconds == 0 {
+        return Err(anyhow::anyhow!("--interval must be at least one second"));
+    }
+    let to = match to {
+        Some(to) => to,
+        None => get_unix_timestamp()?,
+    };
+    let from = from.unwrap_or(to - 86_400);
+    let interval = interval_seconds as i64;
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let mut candles: Vec<CandleOutput> = vec![];
+    for (timestamp, fill) in get_market_fills_in_range(sdk, market_pubkey, from, to).await? {
+        let start = timestamp - timestamp.rem_euclid(interval);
+        let price = display_price(sdk, market_pubkey, fill.price_in_ticks)?;
+        let size = display_base_size(sdk, market_pubkey, fill.base_lots_filled)?;
+        // Quote volume is always in quote units, even with --raw-units
+        let (_, quote_volume) =
+            get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+        match candles.last_mut() {
+            Some(candle) if candle.start == start => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.base_volume += size;
+                candle.quote_volume += quote_volume;
+                candle.trades += 1;
+            }
+            _ => candles.push(CandleOutput {
+                start,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                base_volume: size,
+                quote_volume,
+                trades: 1,
+            }),
+        }
+    }
+
+    match output {
+        OutputFormat::Json => return print_json(&candles),
+        OutputFormat::Csv => {
+            println!("{}", CANDLES_CSV_HEADER);
+            for candle in candles.iter() {
+                println!(
+                    "{},{},{},{},{},{},{},{}",
+                    candle.start,
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    candle.base_volume,
+                    candle.quote_volume,
+                    candle.trades
+                );
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+    if candles.is_empty() {
+        println!("No fills in range");
+        return Ok(());
+    }
+    println!(
+        "{0: <25} | {1: <15} | {2: <15} | {3: <15} | {4: <15} | {5: <15} | {6: <15} | {7: <8}",
+        "Start", "Open", "High", "Low", "Close", "Base Volume", "Quote Volume", "Trades"
+    );
+    for candle in candles.iter() {
+        println!(
+            "{0: <25} | {1: <15} | {2: <15} | {3: <15} | {4: <15} | {5: <15} | {6: <15} | {7: <8}",
+            format_timestamp(candle.start),
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.base_volume,
+            candle.quote_volume,
+            candle.trades
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::helpers::print_helpers::*;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+pub async fn process_get_events(
+    signature: &Signature,
+    sdk: &mut SDKClient,
+    market_pubkey: Option<&Pubkey>,
+    format: &EventLogFormat<'_>,
+) -> anyhow::Result<()> {
+    let mut events = sdk
+        .parse_events_from_transaction(signature)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse events from transaction"))?;
+    if let Some(market_pubkey) = market_pubkey {
+        events.retain(|event| event.market == *market_pubkey);
+    }
+    if events.is_empty() {
+        println!("No Phoenix events found");
+        return Ok(());
+    }
+    log_market_events(sdk, events, None, format).await
+}
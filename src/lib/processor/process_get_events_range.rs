@@ -0,0 +1,119 @@
+use phoenix_sdk::sdk_client::*;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::helpers::history_helpers::{remove_checkpoint, SignatureHistoryConfig, SignaturePager};
+use crate::helpers::print_helpers::{log_market_events, EventLogFormat};
+use crate::helpers::progress_helpers::ProgressReporter;
+use crate::print_info;
+
+// Report progress every this many transactions parsed
+const PROGRESS_INTERVAL: usize = 100;
+
+pub struct EventRange {
+    pub from_slot: Option<u64>,
+    pub to_slot: Option<u64>,
+    pub before: Option<Signature>,
+    pub until: Option<Signature>,
+    pub checkpoint_file: Option<String>,
+}
+
+// Dump every event on the market within a slot or signature range, oldest first. The signatures
+// in the range are collected first, then each transaction is parsed and its events printed as it
+// goes. Progress goes to stderr and, as JSON events, to the progress file if one is given. With a
+// checkpoint file, signatures collected before an interruption are not fetched again. Transactions
+// that fail to parse are skipped with a warning and counted in the final report.
+pub async fn process_get_events_range(
+    market_pubkey: &Pubkey,
+    sdk: &mut SDKClient,
+    range: &EventRange,
+    format: &EventLogFormat<'_>,
+    progress_file: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut progress = ProgressReporter::new(progress_file, "get-events-range")?;
+    progress.emit("start", json!({ "market": market_pubkey.to_string() }))?;
+
+    let config = SignatureHistoryConfig {
+        before: range.before,
+        until: range.until,
+        min_slot: range.from_slot,
+        checkpoint_file: range.checkpoint_file.clone(),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut pager = SignaturePager::new(&sdk.client, market_pubkey, config)?;
+    let mut signatures = vec![];
+    while let Some(page) = pager.next_page().await? {
+        signatures.extend(
+            page.into_iter()
+                .filter(|info| range.to_slot.map(|to| info.slot <= to).unwrap_or(true)),
+        );
+        if let Some(oldest) = signatures.last() {
+            print_info!(
+                "Found {} transaction(s), back to slot {}",
+                signatures.len(),
+                oldest.slot
+            );
+            progress.emit(
+                "signatures",
+                json!({ "count": signatures.len(), "slot": oldest.slot }),
+            )?;
+        }
+    }
+
+    let total = signatures.len();
+    let mut event_count = 0;
+    let mut skipped = 0;
+    for (i, info) in signatures.iter().rev().enumerate() {
+        match sdk.parse_events_from_transaction(&info.signature).await {
+            Some(events) => {
+                let events = events
+                    .into_iter()
+                    .filter(|event| event.market == *market_pubkey)
+                    .collect::<Vec<_>>();
+                event_count += events.len();
+                log_market_events(sdk, events, None, format).await?;
+            }
+            None => {
+                tracing::warn!(
+                    signature = %info.signature,
+                    "Failed to parse events from transaction, skipping it"
+                );
+                skipped += 1;
+            }
+        }
+        let parsed = i + 1;
+        if parsed % PROGRESS_INTERVAL == 0 || parsed == total {
+            print_info!(
+                "Parsed {}/{} transaction(s), slot {}, {} event(s), {} skipped",
+                parsed,
+                total,
+                info.slot,
+                event_count,
+                skipped
+            );
+            progress.emit(
+                "heartbeat",
+                json!({
+                    "parsed": parsed,
+                    "total": total,
+                    "slot": info.slot,
+                    "events": event_count,
+                    "skipped": skipped,
+                }),
+            )?;
+        }
+    }
+    progress.emit(
+        "done",
+        json!({ "transactions": total, "events": event_count, "skipped": skipped }),
+    )?;
+    if skipped > 0 {
+        tracing::warn!(
+            "Skipped {} of {} transaction(s) whose events could not be parsed",
+            skipped,
+            total
+        );
+    }
+    remove_checkpoint(range.checkpoint_file.as_deref())
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use super::process_record_fee_snapshot::FEE_SNAPSHOT_HEADER;
+use crate::helpers::archive_helpers::read_archive_to_string;
+
+pub struct FeeSnapshot {
+    pub timestamp: i64,
+    pub quote_symbol: String,
+    pub uncollected_fees: f64,
+    pub collected_fees: f64,
+}
+
+// Read a CSV written by record-fee-snapshot, grouping snapshots by market in time order. The file
+// may be zstd or gzip compressed
+pub fn read_fee_snapshots(path: &str) -> anyhow::Result<BTreeMap<String, Vec<FeeSnapshot>>> {
+    let contents = read_archive_to_string(path)?;
+    let mut snapshots: BTreeMap<String, Vec<FeeSnapshot>> = BTreeMap::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.is_empty() || line == FEE_SNAPSHOT_HEADER {
+            continue;
+        }
+        let columns = line.split(',').collect::<Vec<_>>();
+        if columns.len() != 5 {
+            return Err(anyhow::anyhow!("Malformed fee snapshot on line {}", i + 1));
+        }
+        snapshots
+            .entry(columns[1].to_string())
+            .or_default()
+            .push(FeeSnapshot {
+                timestamp: columns[0].parse()?,
+                quote_symbol: columns[2].to_string(),
+                uncollected_fees: columns[3].parse()?,
+                collected_fees: columns[4].parse()?,
+            });
+    }
+    for market_snapshots in snapshots.values_mut() {
+        market_snapshots.sort_by_key(|s| s.timestamp);
+    }
+    Ok(snapshots)
+}
+
+pub fn process_get_fee_accrual(snapshot_file: &str) -> anyhow::Result<()> {
+    let snapshots = read_fee_snapshots(snapshot_file)?;
+
+    println!(
+        "{0: <44} | {1: <6} | {2: <9} | {3: <10} | {4: <14} | {5: <14} | {6: <14}",
+        "Market", "Quote", "Snapshots", "Hours", "Accrued", "Accrued/Day", "Uncollected"
+    );
+    for (market, market_snapshots) in snapshots.iter() {
+        let (first, last) = match (market_snapshots.first(), market_snapshots.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue,
+        };
+        // Collected fees only ever grow, so collected plus uncollected is the running total accrued
+        let accrued = (last.collected_fees + last.uncollected_fees)
+            - (first.collected_fees + first.uncollected_fees);
+        let seconds = (last.timestamp - first.timestamp) as f64;
+        println!(
+            "{0: <44} | {1: <6} | {2: <9} | {3: <10.1} | {4: <14.4} | {5: <14} | {6: <14.4}",
+            market,
+            last.quote_symbol,
+            market_snapshots.len(),
+            seconds / 3600.0,
+            accrued,
+            if seconds > 0.0 {
+                format!("{:.4}", accrued / seconds * 86_400.0)
+            } else {
+                "-".to_string()
+            },
+            last.uncollected_fees,
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use phoenix::quantities::WrapperU64;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::load_market;
+use crate::helpers::output_helpers::{
+    format_atoms, format_quote_lots, is_raw_units, print_json, OutputFormat,
+};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FeesOutput {
+    pub market: String,
+    pub taker_fee_bps: u64,
+    pub fee_recipient: String,
+    pub fee_recipient_quote_account: String,
+    pub fee_recipient_quote_balance: Option<String>,
+    pub uncollected_fees: String,
+    pub collected_fees: String,
+    pub cumulative_fees: String,
+}
+
+// Fees accrue on the market as uncollected until they are swept to the fee recipient's quote
+// token account, after which they count as collected. Amounts are in quote units, or in quote lots
+// and the recipient's balance in atoms with --raw-units.
+pub async fn process_get_fees(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (header, market) = load_market(&market_account_data)?;
+
+    let uncollected = market.get_uncollected_fee_amount().as_u64();
+    let collected = market.get_collected_fee_amount().as_u64();
+    let fee_recipient_quote_account = spl_associated_token_account::get_associated_token_address(
+        &header.fee_recipient,
+        &meta.quote_mint,
+    );
+    // The recipient may not have created its token account yet
+    let fee_recipient_quote_balance = match sdk
+        .client
+        .get_account_data(&fee_recipient_quote_account)
+        .await
+    {
+        Ok(data) => Some(format_atoms(
+            spl_token::state::Account::unpack(&data)?.amount,
+            meta.quote_decimals,
+        )),
+        Err(_) => None,
+    };
+
+    let fees = FeesOutput {
+        market: market_pubkey.to_string(),
+        taker_fee_bps: market.get_taker_fee_bps(),
+        fee_recipient: header.fee_recipient.to_string(),
+        fee_recipient_quote_account: fee_recipient_quote_account.to_string(),
+        fee_recipient_quote_balance,
+        uncollected_fees: format_quote_lots(sdk, market_pubkey, uncollected)?,
+        collected_fees: format_quote_lots(sdk, market_pubkey, collected)?,
+        cumulative_fees: format_quote_lots(sdk, market_pubkey, uncollected + collected)?,
+    };
+    if output == OutputFormat::Json {
+        return print_json(&fees);
+    }
+    println!("Taker fees in basis points: {}", fees.taker_fee_bps);
+    println!("Fee recipient: {}", fees.fee_recipient);
+    println!(
+        "Fee recipient quote token account: {}",
+        fees.fee_recipient_quote_account
+    );
+    match &fees.fee_recipient_quote_balance {
+        Some(balance) => println!("Fee recipient quote token balance: {}", balance),
+        None => println!("Fee recipient quote token account does not exist"),
+    }
+    let unit = if is_raw_units() {
+        "quote lots"
+    } else {
+        "quote units"
+    };
+    println!("Uncollected fees, in {}: {}", unit, fees.uncollected_fees);
+    println!("Collected fees, in {}: {}", unit, fees.collected_fees);
+    println!("Cumulative fees, in {}: {}", unit, fees.cumulative_fees);
+    Ok(())
+}
@@ -1,14 +1,59 @@
 use crate::helpers::market_helpers::*;
-use crate::helpers::print_helpers::print_book;
+use crate::helpers::output_helpers::{
+    is_csv_metadata, print_json, BookOutput, ExportMetadata, OutputFormat,
+};
+use crate::helpers::print_helpers::{print_book, print_depth_chart};
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::pubkey::Pubkey;
 
-pub async fn process_get_full_book(market_pubkey: &Pubkey, sdk: &SDKClient) -> anyhow::Result<()> {
-    let book = get_book_levels(market_pubkey, &sdk.client, u64::MAX).await?;
+pub async fn process_get_full_book(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    depth: Option<u64>,
+    grouping: Option<BookGrouping>,
+    chart: bool,
+    cumulative: bool,
+    template: Option<&str>,
+    precision: Option<usize>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let depth = depth.unwrap_or(u64::MAX);
+    let (book, slot) = match grouping {
+        // Group the whole book so the depth limit applies to buckets rather than raw levels
+        Some(grouping) => {
+            let (book, slot) =
+                get_book_levels_with_slot(market_pubkey, &sdk.client, u64::MAX).await?;
+            let mut book = group_ladder(&book, grouping);
+            book.bids.truncate(depth as usize);
+            book.asks.truncate(depth as usize);
+            (book, slot)
+        }
+        None => get_book_levels_with_slot(market_pubkey, &sdk.client, depth).await?,
+    };
+    match output {
+        OutputFormat::Json => {
+            return print_json(
+                &BookOutput::from_ladder(sdk, market_pubkey, &book)?
+                    .with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?),
+            );
+        }
+        OutputFormat::Csv => {
+            let mut book = BookOutput::from_ladder(sdk, market_pubkey, &book)?;
+            if is_csv_metadata() {
+                book = book.with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?);
+            }
+            book.print_csv(None);
+            return Ok(());
+        }
+        _ => {}
+    }
+    if chart {
+        return print_depth_chart(sdk, market_pubkey, &book, precision);
+    }
     if book.bids.is_empty() && book.asks.is_empty() {
         println!("Book is empty");
     } else {
-        print_book(sdk, market_pubkey, &book)?;
+        print_book(sdk, market_pubkey, &book, template, precision, cumulative)?;
     }
     Ok(())
 }
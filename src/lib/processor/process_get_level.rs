@@ -0,0 +1,63 @@
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    float_price_to_ticks, get_all_registered_traders, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{display_price, format_base_lots};
+
+pub async fn process_get_level(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    price: f64,
+    side: Side,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let traders = get_all_registered_traders(market);
+
+    let price_in_ticks = float_price_to_ticks(&meta, price);
+    println!(
+        "{:?} level at {} ({} ticks)",
+        side,
+        display_price(sdk, market_pubkey, price_in_ticks)?,
+        price_in_ticks
+    );
+    println!(
+        "{0: <8} | {1: <44} | {2: <20} | {3: <12} | {4: <12}",
+        "Position", "Maker", "ID", "Quantity", "Ahead"
+    );
+
+    let mut position = 0;
+    let mut ahead = 0;
+    for (order_id, order) in market.get_book(side).iter() {
+        if order_id.price_in_ticks.as_u64() != price_in_ticks
+            || order.is_expired(clock.slot, clock.unix_timestamp as u64)
+        {
+            continue;
+        }
+        position += 1;
+        let lots = order.num_base_lots.as_u64();
+        println!(
+            "{0: <8} | {1: <44} | {2: <20} | {3: <12} | {4: <12}",
+            position,
+            traders
+                .get(&order.trader_index)
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            order_id.order_sequence_number,
+            format_base_lots(sdk, market_pubkey, lots)?,
+            format_base_lots(sdk, market_pubkey, ahead)?,
+        );
+        ahead += lots;
+    }
+    if position == 0 {
+        println!("No orders at this level");
+    }
+    Ok(())
+}
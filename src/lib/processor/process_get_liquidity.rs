@@ -0,0 +1,71 @@
+use phoenix::state::markets::LadderOrder;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    get_base_and_quote_amounts, get_book_levels, get_ladder_levels_within_bps,
+};
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LiquidityBandOutput {
+    pub bps: u64,
+    pub bid_base: f64,
+    pub bid_quote: f64,
+    pub ask_base: f64,
+    pub ask_quote: f64,
+}
+
+// Report the base size and quote notional resting within each band of basis points around the
+// midpoint, on each side of the full ladder
+pub async fn process_get_liquidity(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    bands: &[u64],
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let ladder = get_book_levels(market_pubkey, &sdk.client, u64::MAX).await?;
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let totals = |levels: &[LadderOrder]| -> (f64, f64) {
+        let mut base = 0.0;
+        let mut quote = 0.0;
+        for level in levels {
+            let (size, notional) =
+                get_base_and_quote_amounts(meta, level.price_in_ticks, level.size_in_base_lots);
+            base += size;
+            quote += notional;
+        }
+        (base, quote)
+    };
+
+    let mut liquidity = vec![];
+    for bps in bands {
+        let (bids, asks) = get_ladder_levels_within_bps(&ladder, *bps)
+            .ok_or_else(|| anyhow::anyhow!("Book is one-sided, so the midpoint is undefined"))?;
+        let (bid_base, bid_quote) = totals(bids);
+        let (ask_base, ask_quote) = totals(asks);
+        liquidity.push(LiquidityBandOutput {
+            bps: *bps,
+            bid_base,
+            bid_quote,
+            ask_base,
+            ask_quote,
+        });
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&liquidity);
+    }
+    println!(
+        "{0: <8} | {1: <20} | {2: <20} | {3: <20} | {4: <20}",
+        "Bps", "Bid Base", "Bid Quote", "Ask Base", "Ask Quote"
+    );
+    for band in liquidity.iter() {
+        println!(
+            "{0: <8} | {1: <20} | {2: <20} | {3: <20} | {4: <20}",
+            band.bps, band.bid_base, band.bid_quote, band.ask_base, band.ask_quote
+        );
+    }
+    Ok(())
+}
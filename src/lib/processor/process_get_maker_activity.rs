@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_market_events_in_window;
+
+#[derive(Default)]
+struct MakerActivity {
+    places: u64,
+    cancels: u64,
+    fills: u64,
+}
+
+pub async fn process_get_maker_activity(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    window_seconds: u64,
+) -> anyhow::Result<()> {
+    let events = get_market_events_in_window(sdk, market_pubkey, window_seconds).await?;
+
+    let mut activity: HashMap<Pubkey, MakerActivity> = HashMap::new();
+    for event in events.iter() {
+        match &event.details {
+            MarketEventDetails::Place(place) => {
+                activity.entry(place.maker).or_default().places += 1;
+            }
+            MarketEventDetails::Reduce(reduce) => {
+                activity.entry(reduce.maker).or_default().cancels += 1;
+            }
+            MarketEventDetails::Fill(fill) => {
+                activity.entry(fill.maker).or_default().fills += 1;
+            }
+            _ => {}
+        }
+    }
+
+    if activity.is_empty() {
+        println!(
+            "No maker activity found in the last {} seconds",
+            window_seconds
+        );
+        return Ok(());
+    }
+
+    let mut makers = activity.into_iter().collect::<Vec<_>>();
+    makers.sort_by(|a, b| b.1.places.cmp(&a.1.places));
+
+    println!(
+        "{0: <44} | {1: <10} | {2: <10} | {3: <10} | {4: <15} | {5: <12}",
+        "Maker", "Places", "Cancels", "Fills", "Order/Trade", "Cancel Rate"
+    );
+    for (maker, stats) in makers.iter() {
+        println!(
+            "{0: <44} | {1: <10} | {2: <10} | {3: <10} | {4: <15} | {5: <12}",
+            maker.to_string(),
+            stats.places,
+            stats.cancels,
+            stats.fills,
+            if stats.fills > 0 {
+                format!("{:.2}", stats.places as f64 / stats.fills as f64)
+            } else {
+                "∞".to_string()
+            },
+            if stats.places > 0 {
+                format!("{:.2}%", stats.cancels as f64 / stats.places as f64 * 100.0)
+            } else {
+                "-".to_string()
+            }
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_market_events_in_window;
+use crate::helpers::market_helpers::get_base_and_quote_amounts;
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct MakerLeaderboardEntry {
+    pub rank: usize,
+    pub maker: String,
+    pub fills: u64,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    pub share_of_volume: f64,
+}
+
+// Rank the market's makers by the quote volume of their orders filled over the last
+// `window_seconds`. Phoenix charges fees to takers only and pays makers no rebates, so filled
+// volume is the whole of a maker's contribution.
+pub async fn process_get_maker_leaderboard(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    window_seconds: u64,
+    limit: usize,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let events = get_market_events_in_window(sdk, market_pubkey, window_seconds).await?;
+    let fills = events
+        .iter()
+        .filter_map(|event| match &event.details {
+            MarketEventDetails::Fill(fill) => Some(fill),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut makers: HashMap<Pubkey, MakerLeaderboardEntry> = HashMap::new();
+    let mut total_quote_volume = 0.0;
+    for fill in fills.iter() {
+        let (base, quote) =
+            get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+        let entry = makers
+            .entry(fill.maker)
+            .or_insert_with(|| MakerLeaderboardEntry {
+                maker: fill.maker.to_string(),
+                ..MakerLeaderboardEntry::default()
+            });
+        entry.fills += 1;
+        entry.base_volume += base;
+        entry.quote_volume += quote;
+        total_quote_volume += quote;
+    }
+    let mut leaderboard = makers.into_values().collect::<Vec<_>>();
+    leaderboard.sort_by(|a, b| b.quote_volume.total_cmp(&a.quote_volume));
+    leaderboard.truncate(limit);
+    for (i, entry) in leaderboard.iter_mut().enumerate() {
+        entry.rank = i + 1;
+        entry.share_of_volume = if total_quote_volume > 0.0 {
+            entry.quote_volume / total_quote_volume
+        } else {
+            0.0
+        };
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&leaderboard);
+    }
+    if leaderboard.is_empty() {
+        println!("No fills in the last {} second(s)", window_seconds);
+        return Ok(());
+    }
+    println!(
+        "Top makers over the last {} second(s) ({} fill(s))",
+        window_seconds,
+        fills.len()
+    );
+    println!(
+        "{0: <4} | {1: <44} | {2: <8} | {3: <20} | {4: <20} | {5: <8}",
+        "Rank", "Maker", "Fills", "Base Volume", "Quote Volume", "Share"
+    );
+    for entry in leaderboard.iter() {
+        println!(
+            "{0: <4} | {1: <44} | {2: <8} | {3: <20} | {4: <20} | {5: <8}",
+            entry.rank,
+            entry.maker,
+            entry.fills,
+            entry.base_volume,
+            entry.quote_volume,
+            format!("{:.2}%", entry.share_of_volume * 100.0)
+        );
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::helpers::output_helpers::OutputFormat;
 use crate::helpers::print_helpers::*;
 use phoenix::program::{load_with_dispatch, MarketHeader};
 use phoenix_sdk::sdk_client::*;
@@ -6,7 +7,12 @@ use std::mem::size_of;
 
 use super::process_get_all_markets::{get_base_and_quote_symbols, get_phoenix_config};
 
-pub async fn process_get_market(market_pubkey: &Pubkey, sdk: &SDKClient) -> anyhow::Result<()> {
+pub async fn process_get_market(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    export_toml: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let market_metadata = sdk.get_market_metadata(market_pubkey).await?;
     let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
     let (header_bytes, market_bytes) = market_account_data.split_at(size_of::<MarketHeader>());
@@ -35,6 +41,8 @@ pub async fn process_get_market(market_pubkey: &Pubkey, sdk: &SDKClient) -> anyh
         taker_fees,
         base_mint_symbol,
         quote_mint_symbol,
+        export_toml,
+        output,
     )
     .await
 }
@@ -1,19 +1,45 @@
 use phoenix::program::{status::MarketStatus, MarketHeader};
 use phoenix_sdk::sdk_client::*;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 use std::mem::size_of;
 
+use crate::helpers::output_helpers::{print_json, MarketStatusOutput, OutputFormat};
+
 pub async fn process_get_market_status(
     market_pubkey: &Pubkey,
     sdk: &SDKClient,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
-    // Get market account
-    let mut market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    // Get market account along with the slot it was read at. The market doesn't record the slot
+    // it was last modified in, but its sequence number increases with every state change, so
+    // comparing it across reads shows whether the market has moved.
+    let response = sdk
+        .client
+        .get_account_with_commitment(market_pubkey, CommitmentConfig::confirmed())
+        .await?;
+    let slot = response.context.slot;
+    let mut market_account_data = response
+        .value
+        .ok_or_else(|| anyhow::Error::msg("Market account not found"))?
+        .data;
     let (header_bytes, _) = market_account_data.split_at_mut(size_of::<MarketHeader>());
     let header: &MarketHeader = bytemuck::try_from_bytes(header_bytes)
         .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
 
     let status = MarketStatus::from(header.status);
-    println!("Market status: {}", status);
+    match output {
+        OutputFormat::Json => print_json(&MarketStatusOutput {
+            market: market_pubkey.to_string(),
+            status: status.to_string(),
+            sequence_number: header.market_sequence_number,
+            slot,
+        })?,
+        _ => {
+            println!("Market status: {}", status);
+            println!("Sequence number: {}", header.market_sequence_number);
+            println!("Slot: {}", slot);
+        }
+    }
     Ok(())
 }
@@ -9,13 +9,24 @@ use solana_sdk::pubkey::Pubkey;
 use solana_sdk::sysvar;
 use std::mem::size_of;
 
+use crate::helpers::market_helpers::{
+    get_all_markets, get_market_data_and_clock, get_raw_base_units_per_base_lot, load_market,
+};
+use crate::helpers::output_helpers::{
+    display_base_size, display_price, is_raw_units, print_json, OpenOrderOutput, OutputFormat,
+};
 use crate::helpers::print_helpers::get_precision;
+use crate::print_info;
 
 pub async fn process_get_open_orders(
     market_pubkey: &Pubkey,
     trader_pubkey: &Pubkey,
     sdk: &SDKClient,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
+    if output == OutputFormat::Json {
+        return print_json(&get_open_orders(market_pubkey, trader_pubkey, sdk).await?);
+    }
     let meta = sdk.get_market_metadata(market_pubkey).await?;
     // Get market account
     let mut market_and_clock = sdk
@@ -47,18 +58,28 @@ pub async fn process_get_open_orders(
     // Derserialize data and load into correct type
     let market = load_with_dispatch(&header.market_size_params, market_bytes)?.inner;
 
-    let raw_base_units_per_base_lot =
-        meta.base_atoms_per_base_lot as f64 / meta.base_atoms_per_raw_base_unit as f64;
+    // With --raw-units, quantities stay in base lots
+    let raw_base_units_per_base_lot = if is_raw_units() {
+        1.0
+    } else {
+        get_raw_base_units_per_base_lot(&meta)
+    };
 
     let trader_index = market
         .get_trader_index(trader_pubkey)
         .ok_or_else(|| anyhow::anyhow!("Trader not found"))?;
     let book_bids = market.get_book(Side::Bid);
     let book_asks = market.get_book(Side::Ask);
-    let price_precision: usize = get_precision(
-        10_u64.pow(meta.quote_decimals) / meta.tick_size_in_quote_atoms_per_base_unit,
-    );
-    let size_precision: usize = get_precision(meta.num_base_lots_per_base_unit);
+    let (price_precision, size_precision) = if is_raw_units() {
+        (0, 0)
+    } else {
+        (
+            get_precision(
+                10_u64.pow(meta.quote_decimals) / meta.tick_size_in_quote_atoms_per_base_unit,
+            ),
+            get_precision(meta.num_base_lots_per_base_unit),
+        )
+    };
 
     println!("Open Bids");
     let mut open_bids = vec![];
@@ -114,6 +135,81 @@ pub async fn process_get_open_orders(
     Ok(())
 }
 
+// List the trader's orders on every market they hold a seat on
+pub async fn process_get_open_orders_all_markets(
+    trader_pubkey: &Pubkey,
+    sdk: &mut SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut market_pubkeys = vec![];
+    for (market_pubkey, account) in get_all_markets(&sdk.client).await? {
+        let (_, market) = load_market(&account.data)?;
+        if market.get_trader_index(trader_pubkey).is_some() {
+            market_pubkeys.push(market_pubkey);
+        }
+    }
+    if output == OutputFormat::Json {
+        let mut orders = vec![];
+        for market_pubkey in market_pubkeys.iter() {
+            sdk.add_market(market_pubkey).await?;
+            orders.extend(get_open_orders(market_pubkey, trader_pubkey, sdk).await?);
+        }
+        return print_json(&orders);
+    }
+    print_info!(
+        "Trader {} has a seat on {} market(s)",
+        trader_pubkey,
+        market_pubkeys.len()
+    );
+    for market_pubkey in market_pubkeys.iter() {
+        sdk.add_market(market_pubkey).await?;
+        println!();
+        println!("Market: {}", market_pubkey);
+        process_get_open_orders(market_pubkey, trader_pubkey, sdk, output).await?;
+    }
+    Ok(())
+}
+
+pub async fn get_open_orders(
+    market_pubkey: &Pubkey,
+    trader_pubkey: &Pubkey,
+    sdk: &SDKClient,
+) -> anyhow::Result<Vec<OpenOrderOutput>> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let trader_index = market
+        .get_trader_index(trader_pubkey)
+        .ok_or_else(|| anyhow::anyhow!("Trader not found"))?;
+
+    let mut orders = vec![];
+    for side in [Side::Bid, Side::Ask] {
+        for (order_id, order) in market.get_book(side).iter() {
+            if order.trader_index as u32 != trader_index
+                || order.is_expired(clock.slot, clock.unix_timestamp as u64)
+            {
+                continue;
+            }
+            orders.push(OpenOrderOutput {
+                market: market_pubkey.to_string(),
+                side: format!("{:?}", side),
+                order_sequence_number: order_id.order_sequence_number,
+                price_in_ticks: order_id.price_in_ticks.as_u64(),
+                price: display_price(sdk, market_pubkey, order_id.price_in_ticks.as_u64())?,
+                quantity: display_base_size(sdk, market_pubkey, order.num_base_lots.as_u64())?,
+                slots_remaining: (order.last_valid_slot >= clock.slot)
+                    .then(|| 1 + order.last_valid_slot - clock.slot),
+                seconds_remaining: (order.last_valid_unix_timestamp_in_seconds
+                    >= clock.unix_timestamp as u64)
+                    .then(|| {
+                        1 + order.last_valid_unix_timestamp_in_seconds - clock.unix_timestamp as u64
+                    }),
+            });
+        }
+    }
+    Ok(orders)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn format_open_orders(
     sdk: &SDKClient,
@@ -131,7 +227,7 @@ fn format_open_orders(
         order_id.price_in_ticks,
         format!(
             "{:.1$}",
-            sdk.ticks_to_float_price(market_pubkey, order_id.price_in_ticks.as_u64())?,
+            display_price(sdk, market_pubkey, order_id.price_in_ticks.as_u64())?,
             price_precision
         ),
         format!(
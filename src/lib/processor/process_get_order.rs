@@ -0,0 +1,131 @@
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_market_events_in_window;
+use crate::helpers::market_helpers::{
+    get_all_registered_traders, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{display_price, format_base_lots, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct OrderOutput {
+    pub order_sequence_number: u64,
+    pub side: String,
+    pub status: String,
+    pub price: Option<f64>,
+    pub maker: Option<String>,
+    pub original_size: Option<String>,
+    pub remaining_size: Option<String>,
+}
+
+// Locate an order by its sequence number. Resting orders are read from the book. The book keeps
+// no record of orders that have left it, nor of the size an order was placed with, so those come
+// from the market's events when `window_seconds` is given.
+pub async fn process_get_order(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    order_sequence_number: u64,
+    window_seconds: Option<u64>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let side = Side::from_order_sequence_number(order_sequence_number);
+    let mut order = OrderOutput {
+        order_sequence_number,
+        side: format!("{:?}", side),
+        status: "not found".to_string(),
+        price: None,
+        maker: None,
+        original_size: None,
+        remaining_size: None,
+    };
+
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let resting = market
+        .get_book(side)
+        .iter()
+        .find(|(order_id, _)| order_id.order_sequence_number == order_sequence_number);
+    let is_resting = resting.is_some();
+    if let Some((order_id, resting_order)) = resting {
+        order.status = if resting_order.is_expired(clock.slot, clock.unix_timestamp as u64) {
+            "expired".to_string()
+        } else {
+            "open".to_string()
+        };
+        order.price = Some(display_price(
+            sdk,
+            market_pubkey,
+            order_id.price_in_ticks.as_u64(),
+        )?);
+        order.maker = get_all_registered_traders(market)
+            .get(&resting_order.trader_index)
+            .map(|maker| maker.to_string());
+        order.remaining_size = Some(format_base_lots(
+            sdk,
+            market_pubkey,
+            resting_order.num_base_lots.as_u64(),
+        )?);
+    }
+
+    if let Some(window_seconds) = window_seconds {
+        let events = get_market_events_in_window(sdk, market_pubkey, window_seconds).await?;
+        for event in events.iter() {
+            match &event.details {
+                MarketEventDetails::Place(place)
+                    if place.order_sequence_number == order_sequence_number =>
+                {
+                    order.price = Some(display_price(sdk, market_pubkey, place.price_in_ticks)?);
+                    order.maker = Some(place.maker.to_string());
+                    order.original_size = Some(format_base_lots(
+                        sdk,
+                        market_pubkey,
+                        place.base_lots_placed,
+                    )?);
+                }
+                MarketEventDetails::Fill(fill)
+                    if fill.order_sequence_number == order_sequence_number
+                        && fill.base_lots_remaining == 0
+                        && !is_resting =>
+                {
+                    order.status = "filled".to_string();
+                }
+                MarketEventDetails::Reduce(reduce)
+                    if reduce.order_sequence_number == order_sequence_number
+                        && reduce.base_lots_remaining == 0
+                        && !is_resting =>
+                {
+                    order.status = "cancelled".to_string();
+                }
+                _ => {}
+            }
+        }
+    }
+    if !is_resting && order.status == "not found" && order.original_size.is_some() {
+        // Placed within the window but no longer resting, e.g. an immediate-or-cancel order
+        order.status = "closed".to_string();
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&order);
+    }
+    let format = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    println!("Order: {}", order.order_sequence_number);
+    println!("Side: {}", order.side);
+    println!("Status: {}", order.status);
+    println!(
+        "Price: {}",
+        format(&order.price.map(|price| price.to_string()))
+    );
+    println!("Maker: {}", format(&order.maker));
+    println!("Original size: {}", format(&order.original_size));
+    println!("Remaining size: {}", format(&order.remaining_size));
+    if !is_resting && window_seconds.is_none() {
+        println!("Order is not resting. Pass --window to look for its events in recent history");
+    }
+    Ok(())
+}
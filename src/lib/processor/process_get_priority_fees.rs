@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::get_market_header;
+use crate::print_info;
+
+const PERCENTILES: [usize; 5] = [25, 50, 75, 90, 99];
+
+pub async fn process_get_priority_fees(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    samples: u64,
+    interval_seconds: u64,
+) -> anyhow::Result<()> {
+    // Orders on a market write-lock the market account and both vaults
+    let header = get_market_header(sdk, market_pubkey).await?;
+    let accounts = [
+        *market_pubkey,
+        header.base_params.vault_key,
+        header.quote_params.vault_key,
+    ];
+
+    // Consecutive samples overlap, so fees are keyed by slot
+    let mut fees_by_slot = BTreeMap::new();
+    for i in 0..samples {
+        let recent_fees = sdk.client.get_recent_prioritization_fees(&accounts).await?;
+        for fee in recent_fees {
+            fees_by_slot.insert(fee.slot, fee.prioritization_fee);
+        }
+        print_info!(
+            "Sample {}/{}: {} slot(s) collected",
+            i + 1,
+            samples,
+            fees_by_slot.len()
+        );
+        if i + 1 < samples {
+            tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+        }
+    }
+
+    let mut fees = fees_by_slot.into_values().collect::<Vec<u64>>();
+    if fees.is_empty() {
+        println!("No prioritization fee data returned");
+        return Ok(());
+    }
+    fees.sort_unstable();
+
+    println!(
+        "Priority fees for market {} (micro-lamports per compute unit)",
+        market_pubkey
+    );
+    println!("Slots sampled: {}", fees.len());
+    println!(
+        "Slots with a nonzero fee: {}",
+        fees.iter().filter(|&&fee| fee > 0).count()
+    );
+    for percentile in PERCENTILES {
+        let index = ((fees.len() - 1) * percentile) / 100;
+        println!("p{}: {}", percentile, fees[index]);
+    }
+    println!("Max: {}", fees[fees.len() - 1]);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{get_market_data_and_clock, load_market};
+use crate::helpers::output_helpers::{display_price, format_base_lots};
+
+pub async fn process_get_queue_position(
+    market_pubkey: &Pubkey,
+    trader_pubkey: &Pubkey,
+    sdk: &SDKClient,
+) -> anyhow::Result<()> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+
+    let trader_index = market
+        .get_trader_index(trader_pubkey)
+        .ok_or_else(|| anyhow::anyhow!("Trader not found"))? as u64;
+
+    let format_lots = |lots: u64| format_base_lots(sdk, market_pubkey, lots);
+
+    for side in [Side::Bid, Side::Ask] {
+        println!("{:?}s", side);
+        println!(
+            "{0: <20} | {1: <12} | {2: <12} | {3: <16} | {4: <16}",
+            "ID", "Price", "Quantity", "Ahead at level", "Ahead in book"
+        );
+        // Orders are iterated in priority order: best price first, then oldest first
+        let mut ahead_in_book = 0;
+        let mut ahead_at_level = 0;
+        let mut current_price = None;
+        for (order_id, order) in market.get_book(side).iter() {
+            if order.is_expired(clock.slot, clock.unix_timestamp as u64) {
+                continue;
+            }
+            let price_in_ticks = order_id.price_in_ticks.as_u64();
+            if current_price != Some(price_in_ticks) {
+                current_price = Some(price_in_ticks);
+                ahead_at_level = 0;
+            }
+            let lots = order.num_base_lots.as_u64();
+            if order.trader_index == trader_index {
+                println!(
+                    "{0: <20} | {1: <12} | {2: <12} | {3: <16} | {4: <16}",
+                    order_id.order_sequence_number,
+                    display_price(sdk, market_pubkey, price_in_ticks)?,
+                    format_lots(lots)?,
+                    format_lots(ahead_at_level)?,
+                    format_lots(ahead_in_book)?,
+                );
+            }
+            ahead_at_level += lots;
+            ahead_in_book += lots;
+        }
+        println!();
+    }
+    Ok(())
+}
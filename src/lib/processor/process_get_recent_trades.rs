@@ -0,0 +1,96 @@
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_unix_timestamp, SignatureHistoryConfig, SignaturePager};
+use crate::helpers::output_helpers::{
+    display_base_size, display_price, format_timestamp, print_json, OutputFormat,
+};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TradeOutput {
+    pub timestamp: i64,
+    pub signature: String,
+    pub price: f64,
+    pub size: f64,
+    pub aggressor_side: String,
+}
+
+// Print the market's last `count` fills, newest first. The market's history is paged only until
+// enough fills are found, the lookback window is exhausted, or max_transactions have been scanned,
+// so quiet markets don't page through their whole history.
+pub async fn process_get_recent_trades(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    count: usize,
+    lookback_seconds: u64,
+    max_transactions: usize,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if count == 0 {
+        return Err(anyhow::anyhow!("--count must be at least 1"));
+    }
+    let config = SignatureHistoryConfig {
+        min_block_time: Some(get_unix_timestamp()? - lookback_seconds as i64),
+        limit: Some(max_transactions),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut pager = SignaturePager::new(&sdk.client, market_pubkey, config)?;
+    let mut trades = vec![];
+    'pages: while let Some(page) = pager.next_page().await? {
+        for info in page.iter() {
+            let events = match sdk.parse_events_from_transaction(&info.signature).await {
+                Some(events) => events,
+                None => continue,
+            };
+            // Fills within a transaction are in execution order, so walk them backwards to keep
+            // the tape newest first
+            for event in events.iter().rev() {
+                let fill = match &event.details {
+                    MarketEventDetails::Fill(fill) if event.market == *market_pubkey => fill,
+                    _ => continue,
+                };
+                trades.push(TradeOutput {
+                    timestamp: info.block_time.unwrap_or_default(),
+                    signature: info.signature.to_string(),
+                    price: display_price(sdk, market_pubkey, fill.price_in_ticks)?,
+                    size: display_base_size(sdk, market_pubkey, fill.base_lots_filled)?,
+                    // The filled side is the resting order's, so the taker traded the opposite side
+                    aggressor_side: format!("{:?}", fill.side_filled.opposite()),
+                });
+                if trades.len() >= count {
+                    break 'pages;
+                }
+            }
+        }
+    }
+
+    if trades.len() < count {
+        tracing::info!(
+            found = trades.len(),
+            "Found fewer fills than --count within the lookback window and transaction limit"
+        );
+    }
+    if output == OutputFormat::Json {
+        return print_json(&trades);
+    }
+    if trades.is_empty() {
+        println!("No trades found");
+        return Ok(());
+    }
+    println!(
+        "{0: <25} | {1: <4} | {2: <15} | {3: <15} | {4: <88}",
+        "Time", "Side", "Price", "Size", "Signature"
+    );
+    for trade in trades.iter() {
+        println!(
+            "{0: <25} | {1: <4} | {2: <15} | {3: <15} | {4: <88}",
+            format_timestamp(trade.timestamp),
+            trade.aggressor_side,
+            trade.price,
+            trade.size,
+            trade.signature
+        );
+    }
+    Ok(())
+}
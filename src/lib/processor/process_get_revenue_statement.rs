@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use super::process_get_fee_accrual::{read_fee_snapshots, FeeSnapshot};
+
+pub const REVENUE_STATEMENT_HEADER: &str =
+    "market,quote_symbol,period_start,period_end,opening_outstanding,accrued,collected,closing_outstanding";
+
+struct RevenueStatementRow<'a> {
+    market: &'a str,
+    opening: &'a FeeSnapshot,
+    closing: &'a FeeSnapshot,
+}
+
+impl RevenueStatementRow<'_> {
+    // Collected plus uncollected is the running total accrued, so accruals over the period
+    // are its change between the opening and closing snapshots
+    fn accrued(&self) -> f64 {
+        (self.closing.collected_fees + self.closing.uncollected_fees)
+            - (self.opening.collected_fees + self.opening.uncollected_fees)
+    }
+
+    fn collected(&self) -> f64 {
+        self.closing.collected_fees - self.opening.collected_fees
+    }
+}
+
+pub fn process_get_revenue_statement(
+    snapshot_file: &str,
+    start: Option<i64>,
+    end: Option<i64>,
+    out: Option<&str>,
+) -> anyhow::Result<()> {
+    let snapshots = read_fee_snapshots(snapshot_file)?;
+
+    let mut rows = vec![];
+    for (market, market_snapshots) in snapshots.iter() {
+        // The opening balance is the latest snapshot at or before the start of the period,
+        // falling back to the first snapshot inside it
+        let opening = match start {
+            Some(start) => market_snapshots
+                .iter()
+                .rev()
+                .find(|s| s.timestamp <= start)
+                .or_else(|| market_snapshots.iter().find(|s| s.timestamp >= start)),
+            None => market_snapshots.first(),
+        };
+        let closing = match end {
+            Some(end) => market_snapshots.iter().rev().find(|s| s.timestamp <= end),
+            None => market_snapshots.last(),
+        };
+        if let (Some(opening), Some(closing)) = (opening, closing) {
+            if opening.timestamp < closing.timestamp {
+                rows.push(RevenueStatementRow {
+                    market,
+                    opening,
+                    closing,
+                });
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        println!("Not enough snapshots to cover the requested period");
+        return Ok(());
+    }
+
+    if let Some(out) = out {
+        let path = shellexpand::tilde(out).to_string();
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "{}", REVENUE_STATEMENT_HEADER)?;
+        for row in rows.iter() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{}",
+                row.market,
+                row.closing.quote_symbol,
+                row.opening.timestamp,
+                row.closing.timestamp,
+                row.opening.uncollected_fees,
+                row.accrued(),
+                row.collected(),
+                row.closing.uncollected_fees,
+            )?;
+        }
+        println!(
+            "Wrote revenue statement for {} market(s) to {}",
+            rows.len(),
+            path
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{0: <44} | {1: <6} | {2: <14} | {3: <14} | {4: <14} | {5: <14}",
+        "Market", "Quote", "Opening", "Accrued", "Collected", "Outstanding"
+    );
+    // (opening, accrued, collected, outstanding) per quote token
+    let mut totals: BTreeMap<&str, (f64, f64, f64, f64)> = BTreeMap::new();
+    for row in rows.iter() {
+        println!(
+            "{0: <44} | {1: <6} | {2: <14.4} | {3: <14.4} | {4: <14.4} | {5: <14.4}",
+            row.market,
+            row.closing.quote_symbol,
+            row.opening.uncollected_fees,
+            row.accrued(),
+            row.collected(),
+            row.closing.uncollected_fees,
+        );
+        let total = totals.entry(&row.closing.quote_symbol).or_default();
+        total.0 += row.opening.uncollected_fees;
+        total.1 += row.accrued();
+        total.2 += row.collected();
+        total.3 += row.closing.uncollected_fees;
+    }
+    println!("--------------------------------");
+    for (quote_symbol, (opening, accrued, collected, outstanding)) in totals.iter() {
+        println!(
+            "{0: <44} | {1: <6} | {2: <14.4} | {3: <14.4} | {4: <14.4} | {5: <14.4}",
+            "Total", quote_symbol, opening, accrued, collected, outstanding
+        );
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
 use crate::helpers::market_helpers::*;
+use crate::helpers::output_helpers::{
+    format_base_lots, format_quote_lots, print_json, OutputFormat, SeatInfoOutput,
+};
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::pubkey::Pubkey;
 
@@ -6,16 +9,48 @@ pub async fn process_get_seat_info(
     market_pubkey: &Pubkey,
     trader_pubkey: &Pubkey,
     sdk: &SDKClient,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let (seat_address, _) = Pubkey::find_program_address(
         &[b"seat", market_pubkey.as_ref(), trader_pubkey.as_ref()],
         &phoenix::ID,
     );
-    println!("Seat address: {}", seat_address);
     let status = get_seat_status(sdk, &seat_address).await;
-    match status {
-        Ok(status) => println!("Seat status: {}", status),
-        _ => println!("Seat status not found"),
+
+    // Locked funds back resting orders, so a trader with any is already quoting on the market
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let locked = market.get_trader_state(trader_pubkey).map(|state| {
+        (
+            u64::from(state.base_lots_locked),
+            u64::from(state.quote_lots_locked),
+        )
+    });
+    let seat_info = SeatInfoOutput {
+        seat_address: seat_address.to_string(),
+        status: status.ok().map(|status| status.to_string()),
+        base_locked: locked
+            .map(|(base_lots, _)| format_base_lots(sdk, market_pubkey, base_lots))
+            .transpose()?,
+        quote_locked: locked
+            .map(|(_, quote_lots)| format_quote_lots(sdk, market_pubkey, quote_lots))
+            .transpose()?,
+        has_locked_funds: matches!(locked, Some((base_lots, quote_lots)) if base_lots > 0 || quote_lots > 0),
+    };
+    if output == OutputFormat::Json {
+        return print_json(&seat_info);
+    }
+    println!("Seat address: {}", seat_info.seat_address);
+    match &seat_info.status {
+        Some(status) => println!("Seat status: {}", status),
+        None => println!("Seat status not found"),
+    }
+    match (&seat_info.base_locked, &seat_info.quote_locked) {
+        (Some(base), Some(quote)) => {
+            println!("Base locked: {}", base);
+            println!("Quote locked: {}", quote);
+        }
+        _ => println!("Trader is not registered on the market"),
     }
     Ok(())
 }
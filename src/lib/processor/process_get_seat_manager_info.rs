@@ -6,16 +6,26 @@ use phoenix_seat_manager::{get_seat_manager_address, seat_manager::SeatManager};
 use solana_sdk::pubkey::Pubkey;
 
 use crate::helpers::market_helpers::get_seat_manager_data_with_market;
+use crate::helpers::output_helpers::{print_json, OutputFormat, SeatManagerOutput};
+use crate::helpers::print_helpers::{print_table, truncate_key};
 
 pub async fn process_get_seat_manager_info(
     client: &EllipsisClient,
     market_pubkey: &Pubkey,
+    output: OutputFormat,
+    wide: bool,
 ) -> anyhow::Result<()> {
     let seat_manager_address = get_seat_manager_address(market_pubkey).0;
     let market_data = client.get_account_data(market_pubkey).await?;
     let market_header =
         bytemuck::from_bytes::<MarketHeader>(market_data.split_at(size_of::<MarketHeader>()).0);
     if market_header.authority != seat_manager_address {
+        if output == OutputFormat::Json {
+            return Err(anyhow::anyhow!(
+                "Authority for Market {} is not the seat manager",
+                market_pubkey
+            ));
+        }
         println!(
             "Authority for Market {} is not the seat manager.",
             market_pubkey
@@ -25,7 +35,51 @@ pub async fn process_get_seat_manager_info(
         return Ok(());
     }
     let seat_manager_info = get_seat_manager_data_with_market(client, market_pubkey).await?;
-    print_seat_manager_struct(&seat_manager_info, &seat_manager_address);
+    match output {
+        OutputFormat::Text
+        | OutputFormat::Csv
+        | OutputFormat::Yaml
+        | OutputFormat::Jsonl
+        | OutputFormat::Markdown
+        | OutputFormat::Template => {
+            print_seat_manager_struct(&seat_manager_info, &seat_manager_address)
+        }
+        OutputFormat::Table => {
+            print_table(
+                &["Seat Manager", "Market", "Authority", "Successor", "Makers"],
+                &[vec![
+                    truncate_key(&seat_manager_address.to_string(), wide),
+                    truncate_key(&seat_manager_info.market.to_string(), wide),
+                    truncate_key(&seat_manager_info.authority.to_string(), wide),
+                    truncate_key(&seat_manager_info.successor.to_string(), wide),
+                    seat_manager_info.num_makers.to_string(),
+                ]],
+                wide,
+            );
+            let dmms = seat_manager_info
+                .designated_market_makers
+                .iter()
+                .filter(|&&dmm| dmm != Pubkey::default())
+                .map(|dmm| vec![truncate_key(&dmm.to_string(), wide)])
+                .collect::<Vec<_>>();
+            if !dmms.is_empty() {
+                print_table(&["Designated Market Maker"], &dmms, wide);
+            }
+        }
+        OutputFormat::Json => print_json(&SeatManagerOutput {
+            seat_manager: seat_manager_address.to_string(),
+            market: seat_manager_info.market.to_string(),
+            authority: seat_manager_info.authority.to_string(),
+            successor: seat_manager_info.successor.to_string(),
+            num_makers: seat_manager_info.num_makers as u64,
+            designated_market_makers: seat_manager_info
+                .designated_market_makers
+                .iter()
+                .filter(|&&dmm| dmm != Pubkey::default())
+                .map(|dmm| dmm.to_string())
+                .collect(),
+        })?,
+    }
     Ok(())
 }
 
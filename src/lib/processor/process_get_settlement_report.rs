@@ -0,0 +1,152 @@
+use std::io::Write;
+use std::time::Duration;
+
+use phoenix::program::status::MarketStatus;
+use phoenix::quantities::WrapperU64;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::{get_vault_reconciliation, load_market};
+use crate::helpers::output_helpers::{format_atoms, format_base_lots, format_quote_lots};
+
+pub const SETTLEMENT_REPORT_HEADER: &str =
+    "trader,base_free,base_locked,quote_free,quote_locked,withdrawable";
+
+struct OutstandingBalance {
+    trader: Pubkey,
+    base_free: u64,
+    base_locked: u64,
+    quote_free: u64,
+    quote_locked: u64,
+}
+
+impl OutstandingBalance {
+    // Locked funds back resting orders, which must be cancelled before they can be withdrawn
+    fn is_withdrawable(&self) -> bool {
+        self.base_locked == 0 && self.quote_locked == 0
+    }
+}
+
+// Settlement checklist for a market being wound down: list every trader with an outstanding
+// balance, flag balances still locked in orders, and check the vaults cover what is owed. With
+// a watch interval, keep reporting progress until both vaults are empty.
+pub async fn process_get_settlement_report(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    out: Option<&str>,
+    watch_interval: Option<u64>,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let format_base = |lots: u64| format_base_lots(sdk, market_pubkey, lots);
+    let format_quote = |lots: u64| format_quote_lots(sdk, market_pubkey, lots);
+
+    let (status, balances) = get_outstanding_balances(sdk, market_pubkey).await?;
+    println!("Market status: {}", status);
+    if !matches!(status, MarketStatus::Closed | MarketStatus::Tombstoned) {
+        tracing::warn!("The market is not closed, so balances may still change from trading");
+    }
+
+    if let Some(out) = out {
+        let path = shellexpand::tilde(out).to_string();
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "{}", SETTLEMENT_REPORT_HEADER)?;
+        for balance in balances.iter() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{}",
+                balance.trader,
+                format_base(balance.base_free)?,
+                format_base(balance.base_locked)?,
+                format_quote(balance.quote_free)?,
+                format_quote(balance.quote_locked)?,
+                balance.is_withdrawable(),
+            )?;
+        }
+        println!("Wrote {} trader balance(s) to {}", balances.len(), path);
+    } else if !balances.is_empty() {
+        println!(
+            "{0: <44} | {1: <15} | {2: <15} | {3: <15} | {4: <15} | {5: <16}",
+            "Trader", "Base Free", "Base Locked", "Quote Free", "Quote Locked", "Status"
+        );
+        for balance in balances.iter() {
+            println!(
+                "{0: <44} | {1: <15} | {2: <15} | {3: <15} | {4: <15} | {5: <16}",
+                balance.trader.to_string(),
+                format_base(balance.base_free)?,
+                format_base(balance.base_locked)?,
+                format_quote(balance.quote_free)?,
+                format_quote(balance.quote_locked)?,
+                if balance.is_withdrawable() {
+                    "withdrawable"
+                } else {
+                    "locked in orders"
+                },
+            );
+        }
+    }
+
+    let locked = balances.iter().filter(|b| !b.is_withdrawable()).count();
+    println!(
+        "{} trader(s) with outstanding balances, {} with funds locked in orders",
+        balances.len(),
+        locked
+    );
+    let reconciliation = get_vault_reconciliation(sdk, market_pubkey).await?;
+    println!(
+        "Base vault: {} (owed {})",
+        format_atoms(reconciliation.base_vault_balance, meta.base_decimals),
+        format_atoms(reconciliation.base_expected, meta.base_decimals),
+    );
+    println!(
+        "Quote vault: {} (owed {}, including uncollected fees)",
+        format_atoms(reconciliation.quote_vault_balance, meta.quote_decimals),
+        format_atoms(reconciliation.quote_expected, meta.quote_decimals),
+    );
+    if reconciliation.base_drift() < 0 || reconciliation.quote_drift() < 0 {
+        tracing::warn!("The vaults hold less than is owed to traders");
+    }
+
+    let interval = match watch_interval {
+        Some(interval) => interval,
+        None => return Ok(()),
+    };
+    loop {
+        let reconciliation = get_vault_reconciliation(sdk, market_pubkey).await?;
+        if reconciliation.base_vault_balance == 0 && reconciliation.quote_vault_balance == 0 {
+            println!("Settlement complete: both vaults are empty");
+            return Ok(());
+        }
+        let (_, balances) = get_outstanding_balances(sdk, market_pubkey).await?;
+        println!(
+            "{} slot {}: {} trader(s) remaining, base vault {}, quote vault {}",
+            get_unix_timestamp()?,
+            reconciliation.slot,
+            balances.len(),
+            format_atoms(reconciliation.base_vault_balance, meta.base_decimals),
+            format_atoms(reconciliation.quote_vault_balance, meta.quote_decimals),
+        );
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn get_outstanding_balances(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+) -> anyhow::Result<(MarketStatus, Vec<OutstandingBalance>)> {
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (header, market) = load_market(&market_account_data)?;
+    let balances = market
+        .get_registered_traders()
+        .iter()
+        .map(|(trader, state)| OutstandingBalance {
+            trader: *trader,
+            base_free: state.base_lots_free.as_u64(),
+            base_locked: state.base_lots_locked.as_u64(),
+            quote_free: state.quote_lots_free.as_u64(),
+            quote_locked: state.quote_lots_locked.as_u64(),
+        })
+        .filter(|b| b.base_free + b.base_locked + b.quote_free + b.quote_locked > 0)
+        .collect();
+    Ok((MarketStatus::from(header.status), balances))
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    get_best_bid_and_ask, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+use crate::print_info;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SpreadOutput {
+    pub market: String,
+    pub samples: u64,
+    pub one_sided_samples: u64,
+    pub failed_samples: u64,
+    pub min_bps: Option<f64>,
+    pub median_bps: Option<f64>,
+    pub max_bps: Option<f64>,
+}
+
+// Sample the top of book `samples` times and summarize the spread. Samples taken while the book
+// is one-sided have no spread and are only counted, as are samples whose fetch failed.
+pub async fn process_get_spread(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    samples: u64,
+    interval_seconds: u64,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if samples == 0 {
+        return Err(anyhow::anyhow!("--samples must be at least 1"));
+    }
+    if output != OutputFormat::Json {
+        print_info!(
+            "Sampling the spread of market {} {} time(s) every {} second(s)",
+            market_pubkey,
+            samples,
+            interval_seconds
+        );
+    }
+    let mut spreads = vec![];
+    let mut failed_samples = 0;
+    for i in 0..samples {
+        if i > 0 {
+            tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+        }
+        let (market_account_data, clock) =
+            match get_market_data_and_clock(&sdk.client, market_pubkey).await {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to fetch the market, skipping sample");
+                    failed_samples += 1;
+                    continue;
+                }
+            };
+        let (_, market) = load_market(&market_account_data)?;
+        if let (Some(bid), Some(ask)) = get_best_bid_and_ask(market, &clock) {
+            let mid = (bid + ask) as f64 / 2.0;
+            spreads.push(ask.saturating_sub(bid) as f64 / mid * 10_000.0);
+        }
+    }
+    if failed_samples == samples {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch the market in every sample"
+        ));
+    }
+    spreads.sort_by(|a, b| a.total_cmp(b));
+
+    let median = match spreads.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(spreads[n / 2]),
+        n => Some((spreads[n / 2 - 1] + spreads[n / 2]) / 2.0),
+    };
+    let summary = SpreadOutput {
+        market: market_pubkey.to_string(),
+        samples,
+        one_sided_samples: samples - failed_samples - spreads.len() as u64,
+        failed_samples,
+        min_bps: spreads.first().copied(),
+        median_bps: median,
+        max_bps: spreads.last().copied(),
+    };
+
+    if output == OutputFormat::Json {
+        return print_json(&summary);
+    }
+    if summary.failed_samples > 0 {
+        println!(
+            "Failed to fetch the market in {} sample(s)",
+            summary.failed_samples
+        );
+    }
+    if spreads.is_empty() {
+        println!("Book was one-sided in every sample");
+        return Ok(());
+    }
+    let format = |spread: Option<f64>| spread.map_or("-".to_string(), |s| format!("{:.2}", s));
+    println!(
+        "Spread (bps) over {} sample(s): min {} | median {} | max {}",
+        spreads.len(),
+        format(summary.min_bps),
+        format(summary.median_bps),
+        format(summary.max_bps)
+    );
+    if summary.one_sided_samples > 0 {
+        println!(
+            "Book was one-sided in {} sample(s)",
+            summary.one_sided_samples
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::str::FromStr;
+
+use phoenix::program::cancel_multiple_orders::{CancelMultipleOrdersByIdParams, CancelOrderParams};
+use phoenix::program::instruction_builders::create_cancel_multiple_orders_by_id_instruction;
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use super::process_get_all_markets::get_phoenix_config;
+use crate::helpers::history_helpers::scan_market_events_in_window;
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::helpers::market_helpers::{
+    get_best_bid_and_ask, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{display_price, format_base_lots};
+use crate::print_summary;
+
+// Cancel at most this many orders per transaction
+const MAX_CANCELS_PER_TRANSACTION: usize = 20;
+
+pub async fn process_get_stale_orders(
+    sdk: &mut SDKClient,
+    market_pubkey: Option<Pubkey>,
+    max_distance_bps: Option<u64>,
+    max_age_orders: Option<u64>,
+    max_order_age: Option<u64>,
+    cancel: bool,
+    ix_only: bool,
+) -> anyhow::Result<()> {
+    if max_distance_bps.is_none() && max_age_orders.is_none() && max_order_age.is_none() {
+        return Err(anyhow::anyhow!(
+            "Specify at least one of --max-distance-bps, --max-age-orders, or --max-order-age"
+        ));
+    }
+
+    let markets = match market_pubkey {
+        Some(market_pubkey) => vec![market_pubkey],
+        None => get_phoenix_config(&sdk.client)
+            .await?
+            .markets
+            .iter()
+            .map(|m| Pubkey::from_str(&m.market))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let trader = sdk.trader;
+    let mut total_stale = 0;
+    // With --ix-only, the cancels for every market are printed as one JSON array
+    let mut ix_only_instructions = vec![];
+    for market_pubkey in markets.iter() {
+        if !sdk.markets.contains_key(market_pubkey) {
+            sdk.add_market(market_pubkey).await?;
+        }
+        let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+        let (market_account_data, clock) =
+            get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+        let (_, market) = load_market(&market_account_data)?;
+
+        let trader_index = match market.get_trader_index(&trader) {
+            Some(trader_index) => trader_index as u64,
+            None => continue,
+        };
+
+        let (best_bid, best_ask) = get_best_bid_and_ask(market, &clock);
+        let mid = match (best_bid, best_ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) as f64 / 2.0),
+            _ => None,
+        };
+        let market_sequence_number = market.get_sequence_number();
+
+        // Resting orders don't record when they were placed. Order sequence numbers only grow, so
+        // an order is older than `max_order_age` if it comes before the oldest order placed on the
+        // market within that window. Without any placement in the window, no order is judged
+        // stale by age, so missing history can never make a fresh order look old.
+        let oldest_in_window = match max_order_age {
+            Some(max_order_age) => {
+                let scan = scan_market_events_in_window(sdk, market_pubkey, max_order_age).await?;
+                if !scan.unparsed.is_empty() && cancel {
+                    return Err(anyhow::anyhow!(
+                        "Not cancelling: {} transaction(s) on market {} within --max-order-age failed to parse, e.g. {}",
+                        scan.unparsed.len(),
+                        market_pubkey,
+                        scan.unparsed[0]
+                    ));
+                }
+                let oldest = scan
+                    .events
+                    .iter()
+                    .filter_map(|event| match &event.details {
+                        MarketEventDetails::Place(place) => {
+                            Some(unsigned_sequence_number(place.order_sequence_number))
+                        }
+                        _ => None,
+                    })
+                    .min();
+                if oldest.is_none() {
+                    tracing::warn!(
+                        market = %market_pubkey,
+                        "No orders placed within --max-order-age, so no order is judged stale by age"
+                    );
+                }
+                oldest
+            }
+            None => None,
+        };
+
+        let mut stale_orders = vec![];
+        for side in [Side::Bid, Side::Ask] {
+            for (order_id, order) in market.get_book(side).iter() {
+                if order.trader_index != trader_index
+                    || order.is_expired(clock.slot, clock.unix_timestamp as u64)
+                {
+                    continue;
+                }
+                let price_in_ticks = order_id.price_in_ticks.as_u64();
+                let distance_bps =
+                    mid.map(|mid| (price_in_ticks as f64 - mid).abs() / mid * 10_000.0);
+                let sequence_number = unsigned_sequence_number(order_id.order_sequence_number);
+                let age = market_sequence_number.saturating_sub(sequence_number);
+
+                let too_far = matches!(
+                    (max_distance_bps, distance_bps),
+                    (Some(max), Some(distance)) if distance > max as f64
+                );
+                let too_old = matches!(max_age_orders, Some(max) if age > max);
+                let too_long_resting =
+                    matches!(oldest_in_window, Some(oldest) if sequence_number < oldest);
+                if too_far || too_old || too_long_resting {
+                    stale_orders.push((
+                        side,
+                        *order_id,
+                        order.num_base_lots.as_u64(),
+                        distance_bps,
+                        age,
+                    ));
+                }
+            }
+        }
+
+        if stale_orders.is_empty() {
+            continue;
+        }
+        total_stale += stale_orders.len();
+
+        print_summary!(ix_only, "--------------------------------");
+        print_summary!(ix_only, "Market: {}", market_pubkey);
+        print_summary!(
+            ix_only,
+            "{0: <6} | {1: <20} | {2: <12} | {3: <12} | {4: <14} | {5: <12}",
+            "Side",
+            "ID",
+            "Price",
+            "Quantity",
+            "Distance (bps)",
+            "Age (orders)"
+        );
+        for (side, order_id, num_base_lots, distance_bps, age) in stale_orders.iter() {
+            print_summary!(
+                ix_only,
+                "{0: <6} | {1: <20} | {2: <12} | {3: <12} | {4: <14} | {5: <12}",
+                format!("{:?}", side),
+                order_id.order_sequence_number,
+                display_price(sdk, market_pubkey, order_id.price_in_ticks.as_u64())?,
+                format_base_lots(sdk, market_pubkey, *num_base_lots)?,
+                distance_bps
+                    .map(|d| format!("{:.1}", d))
+                    .unwrap_or_else(|| "-".to_string()),
+                age
+            );
+        }
+
+        if cancel {
+            for chunk in stale_orders.chunks(MAX_CANCELS_PER_TRANSACTION) {
+                let params = CancelMultipleOrdersByIdParams {
+                    orders: chunk
+                        .iter()
+                        .map(|(side, order_id, ..)| CancelOrderParams {
+                            side: *side,
+                            price_in_ticks: order_id.price_in_ticks.as_u64(),
+                            order_sequence_number: order_id.order_sequence_number,
+                        })
+                        .collect(),
+                };
+                let ix = create_cancel_multiple_orders_by_id_instruction(
+                    market_pubkey,
+                    &trader,
+                    &meta.base_mint,
+                    &meta.quote_mint,
+                    &params,
+                );
+                if ix_only {
+                    ix_only_instructions.push(ix);
+                    continue;
+                }
+                let tx = sdk.client.sign_send_instructions(vec![ix], vec![]).await?;
+                println!("Cancelled {} order(s), transaction: {}", chunk.len(), tx);
+            }
+        }
+    }
+
+    print_summary!(ix_only, "Found {} stale order(s)", total_stale);
+    if cancel && ix_only {
+        print_instructions_json(&ix_only_instructions)?;
+    }
+    Ok(())
+}
+
+// Bid sequence numbers are stored bitwise negated so that older bids sort first
+fn unsigned_sequence_number(order_sequence_number: u64) -> u64 {
+    match Side::from_order_sequence_number(order_sequence_number) {
+        Side::Bid => !order_sequence_number,
+        Side::Ask => order_sequence_number,
+    }
+}
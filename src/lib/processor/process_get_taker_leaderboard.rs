@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_market_events_in_window;
+use crate::helpers::market_helpers::get_base_and_quote_amounts;
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TakerSortKey {
+    Volume,
+    Fees,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct TakerLeaderboardEntry {
+    pub rank: usize,
+    pub taker: String,
+    pub fills: u64,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    pub quote_fees: f64,
+    pub share_of_fees: f64,
+}
+
+// Rank the market's takers by the quote volume they traded over the last `window_seconds`, or by
+// the quote fees they paid. Fees come from FillSummary events, which don't name the taker, so each
+// is attributed to the taker of the fills just before it in the same transaction.
+pub async fn process_get_taker_leaderboard(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    window_seconds: u64,
+    sort_by: TakerSortKey,
+    limit: usize,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let events = get_market_events_in_window(sdk, market_pubkey, window_seconds).await?;
+
+    let mut takers: HashMap<Pubkey, TakerLeaderboardEntry> = HashMap::new();
+    let mut last_fill = None;
+    let mut total_quote_fees = 0.0;
+    for event in events.iter() {
+        match &event.details {
+            MarketEventDetails::Fill(fill) => {
+                let (base, quote) =
+                    get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+                let entry = takers
+                    .entry(fill.taker)
+                    .or_insert_with(|| TakerLeaderboardEntry {
+                        taker: fill.taker.to_string(),
+                        ..TakerLeaderboardEntry::default()
+                    });
+                entry.fills += 1;
+                entry.base_volume += base;
+                entry.quote_volume += quote;
+                last_fill = Some((event.signature, fill.taker));
+            }
+            MarketEventDetails::FillSummary(fill_summary) => {
+                let taker = match last_fill.take() {
+                    Some((signature, taker)) if signature == event.signature => taker,
+                    _ => continue,
+                };
+                let fees = sdk.quote_atoms_to_quote_units_as_float(
+                    market_pubkey,
+                    fill_summary.total_quote_fees,
+                )?;
+                if let Some(entry) = takers.get_mut(&taker) {
+                    entry.quote_fees += fees;
+                }
+                total_quote_fees += fees;
+            }
+            _ => {}
+        }
+    }
+    let mut leaderboard = takers.into_values().collect::<Vec<_>>();
+    leaderboard.sort_by(|a, b| match sort_by {
+        TakerSortKey::Volume => b.quote_volume.total_cmp(&a.quote_volume),
+        TakerSortKey::Fees => b.quote_fees.total_cmp(&a.quote_fees),
+    });
+    leaderboard.truncate(limit);
+    for (i, entry) in leaderboard.iter_mut().enumerate() {
+        entry.rank = i + 1;
+        entry.share_of_fees = if total_quote_fees > 0.0 {
+            entry.quote_fees / total_quote_fees
+        } else {
+            0.0
+        };
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&leaderboard);
+    }
+    if leaderboard.is_empty() {
+        println!("No fills in the last {} second(s)", window_seconds);
+        return Ok(());
+    }
+    println!(
+        "Top takers over the last {} second(s) ({} total quote fees)",
+        window_seconds, total_quote_fees
+    );
+    println!(
+        "{0: <4} | {1: <44} | {2: <8} | {3: <20} | {4: <20} | {5: <20} | {6: <8}",
+        "Rank", "Taker", "Fills", "Base Volume", "Quote Volume", "Quote Fees", "Share"
+    );
+    for entry in leaderboard.iter() {
+        println!(
+            "{0: <4} | {1: <44} | {2: <8} | {3: <20} | {4: <20} | {5: <20} | {6: <8}",
+            entry.rank,
+            entry.taker,
+            entry.fills,
+            entry.base_volume,
+            entry.quote_volume,
+            entry.quote_fees,
+            format!("{:.2}%", entry.share_of_fees * 100.0)
+        );
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
 use crate::helpers::market_helpers::*;
+use crate::helpers::output_helpers::{
+    is_csv_metadata, print_json, BookOutput, ExportMetadata, OutputFormat,
+};
 use crate::helpers::print_helpers::print_book;
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::pubkey::Pubkey;
@@ -6,12 +9,32 @@ use solana_sdk::pubkey::Pubkey;
 pub async fn process_get_top_of_book(
     market_pubkey: &Pubkey,
     sdk: &SDKClient,
+    template: Option<&str>,
+    precision: Option<usize>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
-    let book = get_book_levels(market_pubkey, &sdk.client, 1).await?;
+    let (book, slot) = get_book_levels_with_slot(market_pubkey, &sdk.client, 1).await?;
+    match output {
+        OutputFormat::Json => {
+            return print_json(
+                &BookOutput::from_ladder(sdk, market_pubkey, &book)?
+                    .with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?),
+            );
+        }
+        OutputFormat::Csv => {
+            let mut book = BookOutput::from_ladder(sdk, market_pubkey, &book)?;
+            if is_csv_metadata() {
+                book = book.with_metadata(ExportMetadata::fetch(&sdk.client, slot).await?);
+            }
+            book.print_csv(None);
+            return Ok(());
+        }
+        _ => {}
+    }
     if book.bids.is_empty() && book.asks.is_empty() {
         println!("Book is empty");
     } else {
-        print_book(sdk, market_pubkey, &book)?;
+        print_book(sdk, market_pubkey, &book, template, precision, false)?;
     }
 
     Ok(())
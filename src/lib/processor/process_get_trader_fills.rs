@@ -0,0 +1,138 @@
+use std::io::Write;
+
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_unix_timestamp, SignatureHistoryConfig, SignaturePager};
+use crate::helpers::output_helpers::{
+    display_base_size, display_price, format_timestamp, print_json, OutputFormat,
+};
+use crate::print_info;
+
+pub const TRADER_FILLS_HEADER: &str =
+    "timestamp,signature,market,role,side,price,size,counterparty";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TraderFillOutput {
+    pub timestamp: i64,
+    pub signature: String,
+    pub market: String,
+    pub role: String,
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub counterparty: String,
+}
+
+// Find a trader's fills over a time range by parsing the Phoenix events of each transaction in the
+// history of `address`. Paging the trader's own history finds the fills of their taker orders.
+// Resting orders are filled by other traders' transactions, so maker fills are only found when
+// paging a market's history instead.
+pub async fn process_get_trader_fills(
+    sdk: &mut SDKClient,
+    trader: &Pubkey,
+    address: &Pubkey,
+    start: Option<i64>,
+    end: Option<i64>,
+    out: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let now = get_unix_timestamp()?;
+    let start = start.unwrap_or(now - 86_400);
+    let end = end.unwrap_or(now);
+    let config = SignatureHistoryConfig {
+        min_block_time: Some(start),
+        ..SignatureHistoryConfig::default()
+    };
+    let mut signatures = SignaturePager::new(&sdk.client, address, config)?
+        .collect()
+        .await?;
+    signatures.retain(|info| info.block_time.map(|t| t <= end).unwrap_or(false));
+    signatures.reverse();
+    print_info!("Scanning {} transaction(s)", signatures.len());
+
+    let mut fills = vec![];
+    for info in signatures.iter() {
+        let events = match sdk.parse_events_from_transaction(&info.signature).await {
+            Some(events) => events,
+            None => continue,
+        };
+        for event in events.iter() {
+            let fill = match &event.details {
+                MarketEventDetails::Fill(fill)
+                    if fill.maker == *trader || fill.taker == *trader =>
+                {
+                    fill
+                }
+                _ => continue,
+            };
+            if !sdk.markets.contains_key(&event.market) {
+                sdk.add_market(&event.market).await?;
+            }
+            // The filled side is the resting order's, so takers traded the opposite side
+            let (role, side, counterparty) = if fill.maker == *trader {
+                ("maker", fill.side_filled, fill.taker)
+            } else {
+                ("taker", fill.side_filled.opposite(), fill.maker)
+            };
+            fills.push(TraderFillOutput {
+                timestamp: info.block_time.unwrap_or_default(),
+                signature: info.signature.to_string(),
+                market: event.market.to_string(),
+                role: role.to_string(),
+                side: format!("{:?}", side),
+                price: display_price(sdk, &event.market, fill.price_in_ticks)?,
+                size: display_base_size(sdk, &event.market, fill.base_lots_filled)?,
+                counterparty: counterparty.to_string(),
+            });
+        }
+    }
+
+    if let Some(out) = out {
+        let path = shellexpand::tilde(out).to_string();
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "{}", TRADER_FILLS_HEADER)?;
+        for fill in fills.iter() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{}",
+                fill.timestamp,
+                fill.signature,
+                fill.market,
+                fill.role,
+                fill.side,
+                fill.price,
+                fill.size,
+                fill.counterparty
+            )?;
+        }
+        println!("Wrote {} fill(s) to {}", fills.len(), path);
+        return Ok(());
+    }
+    if output == OutputFormat::Json {
+        return print_json(&fills);
+    }
+    if fills.is_empty() {
+        println!("No fills found");
+        return Ok(());
+    }
+    println!(
+        "{0: <25} | {1: <88} | {2: <44} | {3: <5} | {4: <4} | {5: <15} | {6: <15}",
+        "Time", "Signature", "Market", "Role", "Side", "Price", "Size"
+    );
+    for fill in fills.iter() {
+        println!(
+            "{0: <25} | {1: <88} | {2: <44} | {3: <5} | {4: <4} | {5: <15} | {6: <15}",
+            format_timestamp(fill.timestamp),
+            fill.signature,
+            fill.market,
+            fill.role,
+            fill.side,
+            fill.price,
+            fill.size
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use phoenix::program::status::SeatApprovalStatus;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::process_get_all_markets::{get_base_and_quote_symbols, get_phoenix_config};
+use super::process_get_balances::MAX_ACCOUNTS_PER_REQUEST;
+use crate::helpers::market_helpers::load_market;
+use crate::helpers::output_helpers::{is_raw_units, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TraderMarketOutput {
+    pub market: String,
+    pub base_symbol: Option<String>,
+    pub quote_symbol: Option<String>,
+    pub seat_status: Option<String>,
+    pub registered: bool,
+    pub base_free: String,
+    pub base_locked: String,
+    pub quote_free: String,
+    pub quote_locked: String,
+}
+
+// Find every market in the Phoenix config where the trader has a seat or a nonzero trader state.
+// Each market is fetched alongside the trader's seat for it, so a chunk of markets takes one call.
+pub async fn process_get_trader_markets(
+    sdk: &SDKClient,
+    trader: &Pubkey,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let config = get_phoenix_config(&sdk.client).await?;
+    let market_pubkeys = config
+        .markets
+        .iter()
+        .map(|market| Pubkey::from_str(&market.market))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut markets = vec![];
+    for chunk in market_pubkeys.chunks(MAX_ACCOUNTS_PER_REQUEST / 2) {
+        let mut keys = chunk.to_vec();
+        keys.extend(chunk.iter().map(|market_pubkey| {
+            Pubkey::find_program_address(
+                &[b"seat", market_pubkey.as_ref(), trader.as_ref()],
+                &phoenix::ID,
+            )
+            .0
+        }));
+        let mut accounts = sdk.client.get_multiple_accounts(&keys).await?;
+        let seats = accounts.split_off(chunk.len());
+        for ((market_pubkey, account), seat) in chunk.iter().zip(accounts).zip(seats) {
+            let account = match account {
+                Some(account) => account,
+                None => continue,
+            };
+            // Seat layout: 8 byte discriminant, market, trader, then the approval status as a u64
+            let seat_status = match seat {
+                Some(seat) => Some(SeatApprovalStatus::from(u64::try_from_slice(
+                    &seat.data[72..80],
+                )?)),
+                None => None,
+            };
+            let (header, market) = load_market(&account.data)?;
+            let meta = MarketMetadata::from_header(header)?;
+            let state = market.get_trader_state(trader);
+            let lots = state.map_or((0, 0, 0, 0), |state| {
+                (
+                    u64::from(state.base_lots_free),
+                    u64::from(state.base_lots_locked),
+                    u64::from(state.quote_lots_free),
+                    u64::from(state.quote_lots_locked),
+                )
+            });
+            let (base_free, base_locked, quote_free, quote_locked) = lots;
+            if seat_status.is_none() && base_free + base_locked + quote_free + quote_locked == 0 {
+                continue;
+            }
+            let (base_symbol, quote_symbol) = get_base_and_quote_symbols(&config, header);
+            // Balances are in lots on chain, so --raw-units prints the lots rather than atoms
+            let base = |lots: u64| {
+                if is_raw_units() {
+                    return lots.to_string();
+                }
+                get_decimal_string(lots * meta.base_atoms_per_base_lot, meta.base_decimals)
+            };
+            let quote = |lots: u64| {
+                if is_raw_units() {
+                    return lots.to_string();
+                }
+                get_decimal_string(lots * meta.quote_atoms_per_quote_lot, meta.quote_decimals)
+            };
+            markets.push(TraderMarketOutput {
+                market: market_pubkey.to_string(),
+                base_symbol,
+                quote_symbol,
+                seat_status: seat_status.map(|status| status.to_string()),
+                registered: state.is_some(),
+                base_free: base(base_free),
+                base_locked: base(base_locked),
+                quote_free: quote(quote_free),
+                quote_locked: quote(quote_locked),
+            });
+        }
+    }
+
+    if output == OutputFormat::Json {
+        return print_json(&markets);
+    }
+    if markets.is_empty() {
+        println!(
+            "{} has no seats or balances on any market in the config",
+            trader
+        );
+        return Ok(());
+    }
+    println!(
+        "{0: <44} | {1: <12} | {2: <12} | {3: <15} | {4: <15} | {5: <15} | {6: <15}",
+        "Market", "Pair", "Seat", "Base Free", "Base Locked", "Quote Free", "Quote Locked"
+    );
+    for market in markets.iter() {
+        let pair = match (&market.base_symbol, &market.quote_symbol) {
+            (Some(base), Some(quote)) => format!("{}/{}", base, quote),
+            _ => "-".to_string(),
+        };
+        println!(
+            "{0: <44} | {1: <12} | {2: <12} | {3: <15} | {4: <15} | {5: <15} | {6: <15}",
+            market.market,
+            pair,
+            market.seat_status.as_deref().unwrap_or("None"),
+            market.base_free,
+            market.base_locked,
+            market.quote_free,
+            market.quote_locked
+        );
+    }
+    println!("{} market(s)", markets.len());
+    Ok(())
+}
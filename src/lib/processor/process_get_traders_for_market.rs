@@ -1,15 +1,46 @@
+use crate::helpers::output_helpers::{
+    is_csv_metadata, print_json, print_traders_csv, ExportMetadata, OutputFormat,
+    TraderStateOutput, TradersOutput,
+};
 use crate::helpers::print_helpers::*;
+use crate::print_info;
+use clap::ValueEnum;
 use phoenix::program::{load_with_dispatch, MarketHeader};
+use phoenix::quantities::WrapperU64;
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::pubkey::Pubkey;
+use std::cmp::Reverse;
 use std::mem::size_of;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TraderSortKey {
+    Trader,
+    BaseLocked,
+    BaseFree,
+    QuoteLocked,
+    QuoteFree,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn process_get_traders_for_market(
     market_pubkey: &Pubkey,
     sdk: &SDKClient,
+    sort_by: Option<TraderSortKey>,
+    offset: usize,
+    limit: Option<usize>,
+    output: OutputFormat,
+    wide: bool,
 ) -> anyhow::Result<()> {
     // Get market account
-    let mut market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let response = sdk
+        .client
+        .get_account_with_commitment(market_pubkey, sdk.client.commitment())
+        .await?;
+    let slot = response.context.slot;
+    let mut market_account_data = response
+        .value
+        .ok_or_else(|| anyhow::Error::msg("Market account not found"))?
+        .data;
     let (header_bytes, market_bytes) = market_account_data.split_at_mut(size_of::<MarketHeader>());
     let header: &MarketHeader = bytemuck::try_from_bytes(header_bytes)
         .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
@@ -19,13 +50,72 @@ pub async fn process_get_traders_for_market(
         .map_err(|e| anyhow::anyhow!("Failed to load market. Error {:?}", e))?
         .inner;
 
-    println!(
-        "Found {} trader(s). Printing traders with locked or free lots",
-        market.get_registered_traders().len()
+    let mut traders = market
+        .get_registered_traders()
+        .iter()
+        .filter(|(_, state)| {
+            state.base_lots_locked != 0
+                || state.base_lots_free != 0
+                || state.quote_lots_locked != 0
+                || state.quote_lots_free != 0
+        })
+        .map(|(pubkey, state)| (*pubkey, *state))
+        .collect::<Vec<_>>();
+    let num_traders = traders.len();
+    // Sort the full set before paging through it. Balances sort largest first
+    match sort_by {
+        Some(TraderSortKey::Trader) | None => traders.sort_by_key(|(pubkey, _)| *pubkey),
+        Some(TraderSortKey::BaseLocked) => {
+            traders.sort_by_key(|(_, state)| Reverse(state.base_lots_locked.as_u64()))
+        }
+        Some(TraderSortKey::BaseFree) => {
+            traders.sort_by_key(|(_, state)| Reverse(state.base_lots_free.as_u64()))
+        }
+        Some(TraderSortKey::QuoteLocked) => {
+            traders.sort_by_key(|(_, state)| Reverse(state.quote_lots_locked.as_u64()))
+        }
+        Some(TraderSortKey::QuoteFree) => {
+            traders.sort_by_key(|(_, state)| Reverse(state.quote_lots_free.as_u64()))
+        }
+    }
+    let traders = traders
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect::<Vec<_>>();
+
+    if output != OutputFormat::Text {
+        let traders = traders
+            .iter()
+            .map(|(pubkey, state)| TraderStateOutput::new(sdk, market_pubkey, pubkey, state))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        match output {
+            OutputFormat::Csv => {
+                let metadata = if is_csv_metadata() {
+                    Some(ExportMetadata::fetch(&sdk.client, slot).await?)
+                } else {
+                    None
+                };
+                print_traders_csv(&traders, metadata.as_ref())
+            }
+            OutputFormat::Table => print_trader_table(&traders, wide),
+            _ => print_json(&TradersOutput {
+                traders,
+                metadata: ExportMetadata::fetch(&sdk.client, slot).await?,
+            })?,
+        }
+        return Ok(());
+    }
+
+    print_info!(
+        "Found {} trader(s) with locked or free lots. Printing {} from offset {}",
+        num_traders,
+        traders.len(),
+        offset
     );
 
     // Print trader information
-    for (pubkey, state) in market.get_registered_traders().iter() {
+    for (pubkey, state) in traders.iter() {
         print_trader_state(sdk, market_pubkey, pubkey, state)?;
     }
 
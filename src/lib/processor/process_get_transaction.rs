@@ -5,11 +5,13 @@ use solana_sdk::signature::Signature;
 pub async fn process_get_transaction(
     signature: &Signature,
     sdk: &mut SDKClient,
+    client_order_id: Option<u128>,
+    format: &EventLogFormat<'_>,
 ) -> anyhow::Result<()> {
     let events = sdk
         .parse_events_from_transaction(signature)
         .await
         .ok_or_else(|| anyhow::anyhow!("Failed to parse events from transaction"))?;
-    log_market_events(sdk, events).await?;
+    log_market_events(sdk, events, client_order_id, format).await?;
     Ok(())
 }
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, mem::size_of, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem::size_of,
+    str::FromStr,
+};
 
 use anyhow::anyhow;
 use ellipsis_client::EllipsisClient;
@@ -6,15 +10,24 @@ use phoenix::{
     program::{load_with_dispatch, MarketHeader},
     quantities::WrapperU64,
 };
-use phoenix_sdk::sdk_client::SDKClient;
 use serde_json::Value;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 
 use super::process_get_all_markets::{get_base_and_quote_symbols, get_phoenix_config};
+use crate::helpers::log_helpers::new_timed_sdk_client;
+use crate::helpers::output_helpers::{
+    print_json, print_template, ExportMetadata, OutputFormat, UncollectedRevenueOutput,
+};
+use crate::helpers::print_helpers::print_markdown_table;
+use crate::print_info;
 
 pub async fn process_get_uncollected_revenue(
     client: &EllipsisClient,
     network_url: &str,
+    stablecoins: &[String],
+    live_stablecoin_prices: bool,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let config = get_phoenix_config(client).await?;
     let markets = config
@@ -24,22 +37,29 @@ pub async fn process_get_uncollected_revenue(
         .collect::<Vec<String>>()
         .clone();
 
-    let mut sdk = SDKClient::new(&client.payer, network_url).await?;
-
-    let usdtprice = get_price("USDT", "USDC").await?;
-    let solprice = get_price("SOL", "USDC").await?;
+    let mut sdk =
+        new_timed_sdk_client(&client.payer, network_url, CommitmentConfig::confirmed()).await?;
 
-    println!("Retrieving current balances...");
-    let mut total_usdc = 0f32;
-    let mut total_usdt = 0f32;
-    let mut total_sol = 0f32;
-    let mut total = 0f32;
+    if output == OutputFormat::Text {
+        print_info!("Retrieving current balances...");
+    }
+    let mut totals: BTreeMap<String, f32> = BTreeMap::new();
+    // The exported slot is the latest one any market account was read at
+    let mut slot = 0;
     for market_key in markets {
         let market_pubkey = &Pubkey::from_str(&market_key)?;
         sdk.add_market(&market_pubkey).await?;
         let market_metadata = sdk.get_market_metadata(market_pubkey).await?;
 
-        let market_account_data = sdk.client.get_account_data(&market_pubkey).await?;
+        let response = sdk
+            .client
+            .get_account_with_commitment(market_pubkey, CommitmentConfig::confirmed())
+            .await?;
+        slot = slot.max(response.context.slot);
+        let market_account_data = response
+            .value
+            .ok_or_else(|| anyhow!("Market account {market_key} not found"))?
+            .data;
         let (header_bytes, market_bytes) = market_account_data.split_at(size_of::<MarketHeader>());
         let header: &MarketHeader = bytemuck::try_from_bytes(header_bytes)
             .map_err(|e| anyhow::anyhow!("Error getting market header. Error: {:?}", e))?;
@@ -49,45 +69,79 @@ pub async fn process_get_uncollected_revenue(
             .inner;
 
         let (_, quote_mint_symbol) = get_base_and_quote_symbols(&config, header);
-        let quote_mint_symbol = quote_mint_symbol.unwrap();
-        let quote_mint_symbol = quote_mint_symbol.as_str();
+        let quote_mint_symbol = quote_mint_symbol.ok_or_else(|| {
+            anyhow!("The {market_key} market is using a quote token missing from the config.")
+        })?;
 
         let amt = market.get_uncollected_fee_amount().as_u64() as f32
             / 10f32.powi(market_metadata.quote_decimals as i32);
-        match quote_mint_symbol {
-            "USDC" => {
-                total_usdc += amt;
-                total += amt;
-            }
-            "USDT" => {
-                total_usdt += amt;
-                total += usdtprice * amt;
-            }
-            "SOL" => {
-                total_sol += amt;
-                total += solprice * amt;
-            }
-            _ => return Err(anyhow!(
-                "The {market_key} market is using an unsupported quote token: {quote_mint_symbol}."
-            )),
+        *totals.entry(quote_mint_symbol).or_default() += amt;
+    }
+
+    // Stablecoins count 1:1 towards the USD total unless live prices are requested
+    let is_stablecoin = |symbol: &str| stablecoins.iter().any(|s| s.eq_ignore_ascii_case(symbol));
+    let priced_symbols = totals
+        .keys()
+        .filter(|symbol| live_stablecoin_prices || !is_stablecoin(symbol))
+        .map(|symbol| symbol.as_str())
+        .collect::<Vec<_>>();
+    let prices = if priced_symbols.is_empty() {
+        HashMap::new()
+    } else {
+        get_prices(&priced_symbols, "USD").await?
+    };
+
+    let total = totals
+        .iter()
+        .map(|(symbol, amt)| amt * prices.get(symbol).copied().unwrap_or(1.0))
+        .sum::<f32>();
+    if matches!(output, OutputFormat::Json | OutputFormat::Template) {
+        let revenue = UncollectedRevenueOutput {
+            uncollected_fees: totals,
+            total_usd: total,
+            metadata: ExportMetadata::fetch(client, slot).await?,
+        };
+        if output == OutputFormat::Template {
+            return print_template(&revenue);
         }
+        return print_json(&revenue);
+    }
+    if output == OutputFormat::Markdown {
+        let mut rows = totals
+            .iter()
+            .map(|(symbol, amt)| vec![symbol.clone(), amt.to_string()])
+            .collect::<Vec<_>>();
+        rows.push(vec!["**Total (USD)**".to_string(), format!("**{total}**")]);
+        print_markdown_table(&["Token", "Uncollected Fees"], &rows);
+        return Ok(());
     }
-    println!("USDC: {total_usdc}");
-    println!("USDT: {total_usdt}");
-    println!("SOL: {total_sol}");
-    println!("Total (USDC): {total}");
+    for (symbol, amt) in totals.iter() {
+        println!("{symbol}: {amt}");
+    }
+    println!("Total (USD): {total}");
     Ok(())
 }
 
-async fn get_price(symbol_a: &str, symbol_b: &str) -> anyhow::Result<f32> {
+// Get the price of several symbols in terms of `quote` with a single request. Coinbase's
+// exchange rates are quoted as units of each symbol per unit of `quote`, so prices are inverted.
+async fn get_prices(symbols: &[&str], quote: &str) -> anyhow::Result<HashMap<String, f32>> {
     let body = reqwest::get(format!(
-        "https://api.coinbase.com/v2/prices/{symbol_a}-{symbol_b}/spot"
+        "https://api.coinbase.com/v2/exchange-rates?currency={quote}"
     ))
-    .await.map_err(|_| anyhow!("Failed to get price data, looks like Coinbase is down.."))?
+    .await
+    .map_err(|_| anyhow!("Failed to get price data, looks like Coinbase is down.."))?
     .json::<HashMap<String, Value>>()
     .await?;
-    let price = &body["data"]["amount"].as_str().unwrap(); //fails if coinbase changes their format
-    price
-        .parse::<f32>()
-        .map_err(|e| anyhow!("Failed to get price, Error {e}"))
+    let rates = &body["data"]["rates"]; //fails if coinbase changes their format
+    symbols
+        .iter()
+        .map(|symbol| {
+            let rate = rates[*symbol]
+                .as_str()
+                .ok_or_else(|| anyhow!("No {quote} exchange rate for {symbol}"))?
+                .parse::<f32>()
+                .map_err(|e| anyhow!("Failed to get price, Error {e}"))?;
+            Ok((symbol.to_string(), 1.0 / rate))
+        })
+        .collect()
 }
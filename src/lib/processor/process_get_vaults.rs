@@ -0,0 +1,78 @@
+use colored::Colorize;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::color_helpers::get_theme;
+use crate::helpers::market_helpers::get_vault_reconciliation;
+use crate::helpers::output_helpers::{format_atoms, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VaultOutput {
+    pub vault: String,
+    pub balance: String,
+    pub expected: String,
+    pub drift_atoms: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VaultsOutput {
+    pub market: String,
+    pub slot: u64,
+    pub base: VaultOutput,
+    pub quote: VaultOutput,
+    pub mismatch: bool,
+}
+
+// Print the market's vaults and their balances, checked against the market state. Locked trader
+// balances back the resting orders, so trader states plus uncollected fees are everything the
+// vaults should hold.
+pub async fn process_get_vaults(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    let reconciliation = get_vault_reconciliation(sdk, market_pubkey).await?;
+    let base_drift = reconciliation.base_drift();
+    let quote_drift = reconciliation.quote_drift();
+    let vaults = VaultsOutput {
+        market: market_pubkey.to_string(),
+        slot: reconciliation.slot,
+        base: VaultOutput {
+            vault: reconciliation.base_vault.to_string(),
+            balance: format_atoms(reconciliation.base_vault_balance, meta.base_decimals),
+            expected: format_atoms(reconciliation.base_expected, meta.base_decimals),
+            drift_atoms: base_drift.to_string(),
+        },
+        quote: VaultOutput {
+            vault: reconciliation.quote_vault.to_string(),
+            balance: format_atoms(reconciliation.quote_vault_balance, meta.quote_decimals),
+            expected: format_atoms(reconciliation.quote_expected, meta.quote_decimals),
+            drift_atoms: quote_drift.to_string(),
+        },
+        mismatch: base_drift != 0 || quote_drift != 0,
+    };
+
+    if output == OutputFormat::Json {
+        return print_json(&vaults);
+    }
+    println!("Market: {} (slot {})", vaults.market, vaults.slot);
+    println!(
+        "{0: <6} | {1: <44} | {2: <20} | {3: <20} | {4: <20}",
+        "Vault", "Address", "Balance", "Expected", "Drift (atoms)"
+    );
+    for (name, vault) in [("Base", &vaults.base), ("Quote", &vaults.quote)] {
+        println!(
+            "{0: <6} | {1: <44} | {2: <20} | {3: <20} | {4: <20}",
+            name, vault.vault, vault.balance, vault.expected, vault.drift_atoms
+        );
+    }
+    if vaults.mismatch {
+        println!(
+            "{}",
+            "Vault balances do not match the market state".color(get_theme().warning)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_market_fills_in_range, get_unix_timestamp};
+use crate::helpers::market_helpers::get_base_and_quote_amounts;
+use crate::helpers::output_helpers::{print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct VolumeOutput {
+    pub market: String,
+    pub window_seconds: u64,
+    pub fills: u64,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    pub taker_buy_base_volume: f64,
+    pub taker_buy_quote_volume: f64,
+    pub taker_sell_base_volume: f64,
+    pub taker_sell_quote_volume: f64,
+    pub unique_makers: usize,
+    pub unique_takers: usize,
+}
+
+// Sum the market's fill volume over the last `window_seconds`. Every fill has a maker and a taker
+// on opposite sides of the same volume, so the volume is split by the side the taker traded.
+pub async fn process_get_volume(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    window_seconds: u64,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let end = get_unix_timestamp()?;
+    let fills =
+        get_market_fills_in_range(sdk, market_pubkey, end - window_seconds as i64, end).await?;
+
+    let mut volume = VolumeOutput {
+        market: market_pubkey.to_string(),
+        window_seconds,
+        ..VolumeOutput::default()
+    };
+    let mut makers = HashSet::new();
+    let mut takers = HashSet::new();
+    for (_, fill) in fills.iter() {
+        let (base, quote) =
+            get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+        volume.fills += 1;
+        volume.base_volume += base;
+        volume.quote_volume += quote;
+        // The filled side is the resting order's, so a filled ask was bought by the taker
+        match fill.side_filled {
+            Side::Ask => {
+                volume.taker_buy_base_volume += base;
+                volume.taker_buy_quote_volume += quote;
+            }
+            Side::Bid => {
+                volume.taker_sell_base_volume += base;
+                volume.taker_sell_quote_volume += quote;
+            }
+        }
+        makers.insert(fill.maker);
+        takers.insert(fill.taker);
+    }
+    volume.unique_makers = makers.len();
+    volume.unique_takers = takers.len();
+
+    if output == OutputFormat::Json {
+        return print_json(&volume);
+    }
+    println!(
+        "Volume over the last {} second(s) ({} fill(s))",
+        window_seconds, volume.fills
+    );
+    println!(
+        "{0: <12} | {1: <20} | {2: <20}",
+        "", "Base Volume", "Quote Volume"
+    );
+    for (label, base, quote) in [
+        ("Total", volume.base_volume, volume.quote_volume),
+        (
+            "Taker buys",
+            volume.taker_buy_base_volume,
+            volume.taker_buy_quote_volume,
+        ),
+        (
+            "Taker sells",
+            volume.taker_sell_base_volume,
+            volume.taker_sell_quote_volume,
+        ),
+    ] {
+        println!("{0: <12} | {1: <20} | {2: <20}", label, base, quote);
+    }
+    println!("Unique makers: {}", volume.unique_makers);
+    println!("Unique takers: {}", volume.unique_takers);
+    Ok(())
+}
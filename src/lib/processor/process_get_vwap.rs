@@ -0,0 +1,131 @@
+use phoenix::state::markets::LadderOrder;
+use phoenix_sdk::sdk_client::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::{get_market_fills_in_range, get_unix_timestamp};
+use crate::helpers::market_helpers::{
+    get_base_and_quote_amounts, get_book_levels, get_raw_base_units_per_base_lot,
+    ticks_to_float_price,
+};
+use crate::helpers::output_helpers::{is_raw_units, print_json, OutputFormat};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BookVwapOutput {
+    pub size: f64,
+    // Average price of a buy sweeping the asks, and of a sell sweeping the bids
+    pub buy_vwap: Option<f64>,
+    pub sell_vwap: Option<f64>,
+    // Size that could actually be filled, if the book is thinner than the requested size
+    pub buy_fillable: f64,
+    pub sell_fillable: f64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VwapOutput {
+    pub market: String,
+    pub window_seconds: u64,
+    pub fills: u64,
+    pub vwap: Option<f64>,
+    pub book: Option<BookVwapOutput>,
+}
+
+// Compute the volume-weighted average price of the market's fills over the last `window_seconds`.
+// With `for_size`, also compute the average price a market order of that many base units would
+// get from the current book on each side. With --raw-units, prices are reported in ticks and sizes
+// in base lots, though `for_size` is still given in base units.
+pub async fn process_get_vwap(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    window_seconds: u64,
+    for_size: Option<f64>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let raw_base_units_per_base_lot = get_raw_base_units_per_base_lot(meta);
+    // Prices are linear in ticks, so dividing by the price of one tick gives ticks
+    let (price_unit, size_unit) = if is_raw_units() {
+        (ticks_to_float_price(meta, 1), raw_base_units_per_base_lot)
+    } else {
+        (1.0, 1.0)
+    };
+
+    let end = get_unix_timestamp()?;
+    let fills =
+        get_market_fills_in_range(sdk, market_pubkey, end - window_seconds as i64, end).await?;
+    let mut notional = 0.0;
+    let mut volume = 0.0;
+    for (_, fill) in fills.iter() {
+        let (size, quote) =
+            get_base_and_quote_amounts(meta, fill.price_in_ticks, fill.base_lots_filled);
+        notional += quote;
+        volume += size;
+    }
+
+    let book = match for_size {
+        Some(size) => {
+            let ladder = get_book_levels(market_pubkey, &sdk.client, u64::MAX).await?;
+            // Walk the levels best first until the size is filled
+            let sweep = |levels: &[LadderOrder]| -> anyhow::Result<(Option<f64>, f64)> {
+                let mut remaining = size;
+                let mut notional = 0.0;
+                for level in levels {
+                    if remaining <= 0.0 {
+                        break;
+                    }
+                    let filled =
+                        remaining.min(level.size_in_base_lots as f64 * raw_base_units_per_base_lot);
+                    notional +=
+                        sdk.ticks_to_float_price(market_pubkey, level.price_in_ticks)? * filled;
+                    remaining -= filled;
+                }
+                let fillable = size - remaining;
+                Ok(((fillable > 0.0).then(|| notional / fillable), fillable))
+            };
+            let (buy_vwap, buy_fillable) = sweep(&ladder.asks)?;
+            let (sell_vwap, sell_fillable) = sweep(&ladder.bids)?;
+            Some(BookVwapOutput {
+                size: size / size_unit,
+                buy_vwap: buy_vwap.map(|price| price / price_unit),
+                sell_vwap: sell_vwap.map(|price| price / price_unit),
+                buy_fillable: buy_fillable / size_unit,
+                sell_fillable: sell_fillable / size_unit,
+            })
+        }
+        None => None,
+    };
+
+    let vwap = VwapOutput {
+        market: market_pubkey.to_string(),
+        window_seconds,
+        fills: fills.len() as u64,
+        vwap: (volume > 0.0).then(|| notional / volume / price_unit),
+        book,
+    };
+    if output == OutputFormat::Json {
+        return print_json(&vwap);
+    }
+    match vwap.vwap {
+        Some(price) => println!(
+            "VWAP over the last {} second(s) ({} fill(s)): {}",
+            window_seconds, vwap.fills, price
+        ),
+        None => println!("No fills in the last {} second(s)", window_seconds),
+    }
+    if let Some(book) = &vwap.book {
+        for (label, price, fillable) in [
+            ("Buy", book.buy_vwap, book.buy_fillable),
+            ("Sell", book.sell_vwap, book.sell_fillable),
+        ] {
+            match price {
+                Some(price) if fillable < book.size => println!(
+                    "{} {} from the book: {} (only {} available)",
+                    label, book.size, price, fillable
+                ),
+                Some(price) => println!("{} {} from the book: {}", label, book.size, price),
+                None => println!("{} {} from the book: no liquidity", label, book.size),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use borsh::BorshDeserialize;
+use phoenix::program::cancel_multiple_orders::CancelMultipleOrdersByIdParams;
+use phoenix::program::deposit::DepositParams;
+use phoenix::program::instruction::PhoenixInstruction;
+use phoenix::program::new_order::MultipleOrderPacket;
+use phoenix::program::withdraw::WithdrawParams;
+use phoenix::quantities::WrapperU64;
+use phoenix::state::OrderPacket;
+use phoenix_sdk::sdk_client::*;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::helpers::instruction_helpers::get_phoenix_instructions;
+use crate::helpers::output_helpers::{
+    format_base_lots, format_quote_lots, print_json, OutputFormat,
+};
+use crate::helpers::print_helpers::{
+    get_event_client_order_ids, get_event_log_entry, log_market_events, EventLogFormat,
+};
+
+struct DecodedInstruction {
+    index: usize,
+    // Position among the inner instructions of the top level instruction at `index`, for
+    // instructions invoked through CPI
+    inner_index: Option<usize>,
+    name: String,
+    market: Option<Pubkey>,
+    args: Vec<(&'static str, String)>,
+}
+
+// Decode every Phoenix instruction in a transaction, including those invoked through CPI, with
+// amounts in human units, followed by the market events it emitted
+pub async fn process_inspect_tx(
+    signature: &Signature,
+    sdk: &mut SDKClient,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let tx = sdk
+        .client
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    let phoenix_instructions = get_phoenix_instructions(&tx.transaction)
+        .map_err(|e| anyhow::anyhow!("Failed to decode transaction {}: {}", signature, e))?;
+
+    let mut instructions = vec![];
+    for ix in phoenix_instructions {
+        if let Some(market) = &ix.market {
+            if !sdk.markets.contains_key(market) {
+                // Instructions without a market in this position fail to load and are left undecoded
+                let _ = sdk.add_market(market).await;
+            }
+        }
+        let name = format!("{:?}", ix.instruction);
+        let args = match ix.market.filter(|market| sdk.markets.contains_key(market)) {
+            Some(market) => decode_args(sdk, &market, ix.instruction, &ix.args)
+                .unwrap_or_else(|e| vec![("error", format!("Failed to decode arguments: {}", e))]),
+            None => vec![],
+        };
+        instructions.push(DecodedInstruction {
+            index: ix.index,
+            inner_index: ix.inner_index,
+            name,
+            market: ix.market,
+            args,
+        });
+    }
+    let events = sdk
+        .parse_events_from_transaction(signature)
+        .await
+        .unwrap_or_default();
+
+    if output == OutputFormat::Json {
+        let mut event_entries = vec![];
+        let client_order_ids = get_event_client_order_ids(&events);
+        for (event, client_order_ids) in events.iter().zip(client_order_ids) {
+            if !sdk.markets.contains_key(&event.market) {
+                sdk.add_market(&event.market).await?;
+            }
+            if let Some(entry) = get_event_log_entry(sdk, event, client_order_ids)? {
+                event_entries.push(entry.to_json());
+            }
+        }
+        return print_json(&serde_json::json!({
+            "signature": signature.to_string(),
+            "slot": tx.slot,
+            "block_time": tx.block_time,
+            "instructions": instructions
+                .iter()
+                .map(|ix| serde_json::json!({
+                    "index": ix.index,
+                    "inner_index": ix.inner_index,
+                    "name": ix.name,
+                    "market": ix.market.map(|market| market.to_string()),
+                    "args": ix
+                        .args
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), serde_json::Value::String(value.clone())))
+                        .collect::<serde_json::Map<_, _>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "events": event_entries,
+        }));
+    }
+
+    println!("Signature: {}", signature);
+    println!("Slot: {}", tx.slot);
+    if instructions.is_empty() {
+        println!("No Phoenix instructions");
+    }
+    for ix in instructions.iter() {
+        let index = match ix.inner_index {
+            Some(inner_index) => format!("{}.{}", ix.index, inner_index),
+            None => ix.index.to_string(),
+        };
+        match &ix.market {
+            Some(market) => println!("#{} {} on market {}", index, ix.name, market),
+            None => println!("#{} {}", index, ix.name),
+        }
+        for (name, value) in ix.args.iter() {
+            println!("    {}: {}", name, value);
+        }
+    }
+    if !events.is_empty() {
+        println!("Events:");
+        log_market_events(sdk, events, None, &EventLogFormat::Default).await?;
+    }
+    Ok(())
+}
+
+// Decode the arguments of the instructions that move funds or orders. Prices are in quote units
+// per base unit and sizes in base or quote units.
+fn decode_args(
+    sdk: &SDKClient,
+    market: &Pubkey,
+    instruction: PhoenixInstruction,
+    mut data: &[u8],
+) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let price = |price_in_ticks: u64| -> anyhow::Result<String> {
+        Ok(sdk
+            .ticks_to_float_price(market, price_in_ticks)?
+            .to_string())
+    };
+    let args = match instruction {
+        PhoenixInstruction::Swap
+        | PhoenixInstruction::SwapWithFreeFunds
+        | PhoenixInstruction::PlaceLimitOrder
+        | PhoenixInstruction::PlaceLimitOrderWithFreeFunds => {
+            let packet = OrderPacket::deserialize(&mut data)?;
+            let order_type = match packet {
+                OrderPacket::PostOnly { .. } => "PostOnly",
+                OrderPacket::Limit { .. } => "Limit",
+                OrderPacket::ImmediateOrCancel { .. } => "ImmediateOrCancel",
+            };
+            // Swaps and IOC orders may leave the price out to take whatever the book offers
+            let order_price = match packet {
+                OrderPacket::ImmediateOrCancel {
+                    price_in_ticks: None,
+                    ..
+                } => "market".to_string(),
+                _ => price(packet.get_price_in_ticks().as_u64())?,
+            };
+            vec![
+                ("type", order_type.to_string()),
+                ("side", format!("{:?}", packet.side())),
+                ("price", order_price),
+                (
+                    "base_size",
+                    format_base_lots(sdk, market, packet.num_base_lots().as_u64())?,
+                ),
+                (
+                    "quote_size",
+                    format_quote_lots(sdk, market, packet.num_quote_lots().as_u64())?,
+                ),
+                ("client_order_id", packet.client_order_id().to_string()),
+            ]
+        }
+        PhoenixInstruction::PlaceMultiplePostOnlyOrders
+        | PhoenixInstruction::PlaceMultiplePostOnlyOrdersWithFreeFunds => {
+            let packet = MultipleOrderPacket::deserialize(&mut data)?;
+            let mut args = vec![];
+            for (name, orders) in [("bid", &packet.bids), ("ask", &packet.asks)] {
+                for order in orders.iter() {
+                    args.push((
+                        name,
+                        format!(
+                            "{} @ {}",
+                            format_base_lots(sdk, market, order.size_in_base_lots)?,
+                            price(order.price_in_ticks)?
+                        ),
+                    ));
+                }
+            }
+            args
+        }
+        PhoenixInstruction::DepositFunds => {
+            let params = DepositParams::deserialize(&mut data)?;
+            vec![
+                (
+                    "base_amount",
+                    format_base_lots(sdk, market, params.base_lots_to_deposit)?,
+                ),
+                (
+                    "quote_amount",
+                    format_quote_lots(sdk, market, params.quote_lots_to_deposit)?,
+                ),
+            ]
+        }
+        PhoenixInstruction::WithdrawFunds => {
+            // Missing amounts withdraw everything that is free
+            let params = WithdrawParams::deserialize(&mut data)?;
+            vec![
+                (
+                    "base_amount",
+                    match params.base_lots_to_withdraw {
+                        Some(lots) => format_base_lots(sdk, market, lots)?,
+                        None => "all".to_string(),
+                    },
+                ),
+                (
+                    "quote_amount",
+                    match params.quote_lots_to_withdraw {
+                        Some(lots) => format_quote_lots(sdk, market, lots)?,
+                        None => "all".to_string(),
+                    },
+                ),
+            ]
+        }
+        PhoenixInstruction::CancelMultipleOrdersById
+        | PhoenixInstruction::CancelMultipleOrdersByIdWithFreeFunds => {
+            let params = CancelMultipleOrdersByIdParams::deserialize(&mut data)?;
+            params
+                .orders
+                .iter()
+                .map(|order| {
+                    Ok((
+                        "order",
+                        format!(
+                            "{:?} {} @ {}",
+                            order.side,
+                            order.order_sequence_number,
+                            price(order.price_in_ticks)?
+                        ),
+                    ))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        }
+        _ => vec![],
+    };
+    Ok(args)
+}
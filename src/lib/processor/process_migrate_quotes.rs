@@ -0,0 +1,304 @@
+use phoenix::program::instruction_builders::{
+    create_cancel_all_orders_instruction, create_new_order_instruction,
+    create_withdraw_funds_instruction,
+};
+use phoenix::quantities::WrapperU64;
+use phoenix::state::markets::RestingOrder;
+use phoenix::state::{OrderPacket, Side};
+use phoenix_sdk::sdk_client::*;
+use phoenix_sdk::utils::create_claim_seat_ix_if_needed;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::helpers::market_helpers::{get_market_data_and_clock, load_market};
+use crate::helpers::output_helpers::{display_base_size, display_price};
+
+// Orders are placed in batches to stay within the transaction size limit
+const ORDERS_PER_TRANSACTION: usize = 8;
+
+struct QuoteMigration {
+    side: Side,
+    old_price_in_ticks: u64,
+    old_num_base_lots: u64,
+    new_price_in_ticks: u64,
+    new_num_base_lots: u64,
+}
+
+// Move the payer's resting quotes from one market to another market for the same pair, e.g. after a
+// relisting with new tick and lot sizes. Prices are rounded away from the spread and sizes are
+// rounded down, so no translated quote is more aggressive or larger than the original. Quotes that
+// would cross the new book slide to the best non-crossing price instead of failing, since the old
+// quotes are already gone by the time the new ones land.
+pub async fn process_migrate_quotes(
+    old_market_pubkey: &Pubkey,
+    new_market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    execute: bool,
+    ix_only: bool,
+) -> anyhow::Result<()> {
+    let old_meta = sdk.get_market_metadata_from_cache(old_market_pubkey)?;
+    let new_meta = sdk.get_market_metadata_from_cache(new_market_pubkey)?;
+    if old_meta.base_mint != new_meta.base_mint || old_meta.quote_mint != new_meta.quote_mint {
+        return Err(anyhow::anyhow!(
+            "Markets {} and {} do not trade the same base and quote tokens",
+            old_market_pubkey,
+            new_market_pubkey
+        ));
+    }
+
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, old_market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let trader_index = market
+        .get_trader_index(&sdk.trader)
+        .ok_or_else(|| anyhow::anyhow!("Payer {} has no seat on the old market", sdk.trader))?;
+
+    let mut migrations = vec![];
+    for side in [Side::Bid, Side::Ask] {
+        for (order_id, order) in market.get_book(side).iter() {
+            if order.trader_index as u32 != trader_index
+                || order.is_expired(clock.slot, clock.unix_timestamp as u64)
+            {
+                continue;
+            }
+            migrations.push(QuoteMigration {
+                side,
+                old_price_in_ticks: order_id.price_in_ticks.as_u64(),
+                old_num_base_lots: order.num_base_lots.as_u64(),
+                new_price_in_ticks: translate_price_in_ticks(
+                    order_id.price_in_ticks.as_u64(),
+                    (
+                        old_meta.tick_size_in_quote_atoms_per_base_unit,
+                        old_meta.raw_base_units_per_base_unit as u64,
+                    ),
+                    (
+                        new_meta.tick_size_in_quote_atoms_per_base_unit,
+                        new_meta.raw_base_units_per_base_unit as u64,
+                    ),
+                    side,
+                ),
+                new_num_base_lots: translate_base_lots(
+                    order.num_base_lots.as_u64(),
+                    old_meta.base_atoms_per_base_lot,
+                    new_meta.base_atoms_per_base_lot,
+                ),
+            });
+        }
+    }
+    if migrations.is_empty() {
+        println!(
+            "Payer has no resting orders on market {}",
+            old_market_pubkey
+        );
+        return Ok(());
+    }
+
+    if !ix_only {
+        println!(
+            "Migrating {} order(s) from {} to {}",
+            migrations.len(),
+            old_market_pubkey,
+            new_market_pubkey
+        );
+        println!(
+            "{0: <4} | {1: <15} | {2: <15} | {3: <15} | {4: <15}",
+            "Side", "Old Price", "New Price", "Old Size", "New Size"
+        );
+        for migration in migrations.iter() {
+            println!(
+                "{0: <4} | {1: <15} | {2: <15} | {3: <15} | {4: <15}",
+                match migration.side {
+                    Side::Bid => "Bid",
+                    Side::Ask => "Ask",
+                },
+                display_price(sdk, old_market_pubkey, migration.old_price_in_ticks)?,
+                display_price(sdk, new_market_pubkey, migration.new_price_in_ticks)?,
+                display_base_size(sdk, old_market_pubkey, migration.old_num_base_lots)?,
+                if migration.new_num_base_lots == 0 || migration.new_price_in_ticks == 0 {
+                    "below minimum, skipped".to_string()
+                } else {
+                    display_base_size(sdk, new_market_pubkey, migration.new_num_base_lots)?
+                        .to_string()
+                },
+            );
+        }
+    }
+    migrations.retain(|m| m.new_num_base_lots > 0 && m.new_price_in_ticks > 0);
+
+    let cancel_ixs = vec![
+        create_cancel_all_orders_instruction(
+            old_market_pubkey,
+            &sdk.trader,
+            &old_meta.base_mint,
+            &old_meta.quote_mint,
+        ),
+        create_withdraw_funds_instruction(
+            old_market_pubkey,
+            &sdk.trader,
+            &old_meta.base_mint,
+            &old_meta.quote_mint,
+        ),
+    ];
+    let claim_seat_ixs =
+        create_claim_seat_ix_if_needed(&sdk.client, new_market_pubkey, &sdk.trader).await?;
+    let place_ixs = migrations
+        .iter()
+        .map(|migration| {
+            create_new_order_instruction(
+                new_market_pubkey,
+                &sdk.trader,
+                &new_meta.base_mint,
+                &new_meta.quote_mint,
+                &OrderPacket::new_post_only(
+                    migration.side,
+                    migration.new_price_in_ticks,
+                    migration.new_num_base_lots,
+                    0,
+                    false,
+                    false,
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if ix_only {
+        let instructions = [cancel_ixs, claim_seat_ixs, place_ixs].concat();
+        return print_instructions_json(&instructions);
+    }
+    if !execute {
+        println!(
+            "Dry run only. Pass --execute to cancel on the old market and place on the new one."
+        );
+        return Ok(());
+    }
+
+    let tx = sdk
+        .client
+        .sign_send_instructions(cancel_ixs, vec![])
+        .await?;
+    println!("Cancelled orders and withdrew funds on old market: {}", tx);
+    if !claim_seat_ixs.is_empty() {
+        let tx = sdk
+            .client
+            .sign_send_instructions(claim_seat_ixs, vec![])
+            .await?;
+        println!("Claimed seat on new market: {}", tx);
+    }
+    // Keep going after a failed batch so as many quotes as possible are restored, then report the
+    // ones that were not
+    let mut not_placed = vec![];
+    for (batch, batch_migrations) in place_ixs
+        .chunks(ORDERS_PER_TRANSACTION)
+        .zip(migrations.chunks(ORDERS_PER_TRANSACTION))
+    {
+        match sdk
+            .client
+            .sign_send_instructions(batch.to_vec(), vec![])
+            .await
+        {
+            Ok(tx) => println!("Placed {} order(s) on new market: {}", batch.len(), tx),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to place a batch of quotes on the new market");
+                not_placed.extend(batch_migrations);
+            }
+        }
+    }
+    if !not_placed.is_empty() {
+        println!("Quotes not placed on the new market:");
+        println!("{0: <4} | {1: <15} | {2: <15}", "Side", "Price", "Size");
+        for migration in not_placed.iter() {
+            println!(
+                "{0: <4} | {1: <15} | {2: <15}",
+                match migration.side {
+                    Side::Bid => "Bid",
+                    Side::Ask => "Ask",
+                },
+                display_price(sdk, new_market_pubkey, migration.new_price_in_ticks)?,
+                display_base_size(sdk, new_market_pubkey, migration.new_num_base_lots)?
+            );
+        }
+        return Err(anyhow::anyhow!(
+            "{} of {} quote(s) were not placed on the new market",
+            not_placed.len(),
+            migrations.len()
+        ));
+    }
+
+    Ok(())
+}
+
+// Translate a price in ticks to the new market's ticks, given each market's tick size in quote
+// atoms per base unit and raw base units per base unit. Done in integers, since a round trip
+// through a float price can land a tick off. Bids round down and asks round up, away from the spread.
+fn translate_price_in_ticks(
+    price_in_ticks: u64,
+    (old_tick_size, old_raw_base_units_per_base_unit): (u64, u64),
+    (new_tick_size, new_raw_base_units_per_base_unit): (u64, u64),
+    side: Side,
+) -> u64 {
+    // Both sides are scaled to quote atoms per raw base unit times both raw base unit counts
+    let numerator =
+        price_in_ticks as u128 * old_tick_size as u128 * new_raw_base_units_per_base_unit as u128;
+    let denominator = new_tick_size as u128 * old_raw_base_units_per_base_unit as u128;
+    let ticks = match side {
+        Side::Bid => numerator / denominator,
+        Side::Ask => numerator.div_ceil(denominator),
+    };
+    ticks.min(u64::MAX as u128) as u64
+}
+
+// Translate a size in base lots to the new market's lots through base atoms, rounding down
+fn translate_base_lots(
+    num_base_lots: u64,
+    old_base_atoms_per_base_lot: u64,
+    new_base_atoms_per_base_lot: u64,
+) -> u64 {
+    let base_atoms = num_base_lots as u128 * old_base_atoms_per_base_lot as u128;
+    (base_atoms / new_base_atoms_per_base_lot as u128).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_markets_keep_ticks_and_lots() {
+        // A float round trip turns tick 123 into 123.00000000000001 with these parameters
+        for price_in_ticks in [1, 123, 4_567, 1_000_003, u32::MAX as u64] {
+            for side in [Side::Bid, Side::Ask] {
+                assert_eq!(
+                    translate_price_in_ticks(price_in_ticks, (1, 1), (1, 1), side),
+                    price_in_ticks
+                );
+                assert_eq!(
+                    translate_price_in_ticks(price_in_ticks, (1_000, 10), (1_000, 10), side),
+                    price_in_ticks
+                );
+            }
+        }
+        for num_base_lots in [1, 77, 123_456_789] {
+            assert_eq!(
+                translate_base_lots(num_base_lots, 1_000, 1_000),
+                num_base_lots
+            );
+        }
+    }
+
+    #[test]
+    fn test_rounds_away_from_the_spread() {
+        // A 5 atom tick at 25 ticks is 125 atoms, which is 12.5 ticks of 10 atoms
+        assert_eq!(translate_price_in_ticks(25, (5, 1), (10, 1), Side::Bid), 12);
+        assert_eq!(translate_price_in_ticks(25, (5, 1), (10, 1), Side::Ask), 13);
+        // Prices are per base unit, so more raw base units per base unit scale the ticks up
+        assert_eq!(
+            translate_price_in_ticks(25, (10, 1), (10, 1_000), Side::Bid),
+            25_000
+        );
+    }
+
+    #[test]
+    fn test_rounds_lots_down() {
+        assert_eq!(translate_base_lots(25, 10, 100), 2);
+        assert_eq!(translate_base_lots(3, 100, 10), 30);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::helpers::devnet_helpers::*;
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::print_info;
 use ellipsis_client::EllipsisClient;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
@@ -11,6 +13,7 @@ pub async fn process_mint_tokens(
     recipient_pubkey: &Pubkey,
     mint_ticker: String,
     amount: u64,
+    ix_only: bool,
 ) -> anyhow::Result<()> {
     let mut instructions = vec![];
 
@@ -26,7 +29,7 @@ pub async fn process_mint_tokens(
         spl_associated_token_account::get_associated_token_address(recipient_pubkey, &mint_pda);
 
     if client.get_account(&recipient_ata).await.is_err() {
-        println!("Creating ATA");
+        print_info!("Creating ATA");
         instructions.push(
             spl_associated_token_account::instruction::create_associated_token_account(
                 &payer.pubkey(),
@@ -45,6 +48,10 @@ pub async fn process_mint_tokens(
         amount,
     ));
 
+    if ix_only {
+        return print_instructions_json(&instructions);
+    }
+
     client
         .sign_send_instructions(instructions, vec![payer])
         .await?;
@@ -5,6 +5,8 @@ use solana_sdk::signature::Signer;
 use spl_token::state::Mint;
 
 use crate::helpers::devnet_helpers::devnet_token_faucet;
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::print_info;
 
 // Only valid for sandbox devnet markets
 pub async fn process_mint_tokens_for_market(
@@ -13,6 +15,7 @@ pub async fn process_mint_tokens_for_market(
     recipient_pubkey: &Pubkey,
     base_amount: u64,
     quote_amount: u64,
+    ix_only: bool,
 ) -> anyhow::Result<()> {
     // Get base and quote mints from market metadata
     let market_metadata = sdk.get_market_metadata(market_pubkey).await?;
@@ -48,7 +51,7 @@ pub async fn process_mint_tokens_for_market(
         spl_associated_token_account::get_associated_token_address(recipient_pubkey, &base_mint);
 
     if sdk.client.get_account(&recipient_ata_base).await.is_err() {
-        println!("Creating ATA for base token");
+        print_info!("Creating ATA for base token");
         instructions.push(
             spl_associated_token_account::instruction::create_associated_token_account(
                 &sdk.client.payer.pubkey(),
@@ -63,7 +66,7 @@ pub async fn process_mint_tokens_for_market(
         spl_associated_token_account::get_associated_token_address(recipient_pubkey, &quote_mint);
 
     if sdk.client.get_account(&recipient_ata_quote).await.is_err() {
-        println!("Creating ATA for quote token");
+        print_info!("Creating ATA for quote token");
         instructions.push(
             spl_associated_token_account::instruction::create_associated_token_account(
                 &sdk.client.payer.pubkey(),
@@ -89,6 +92,9 @@ pub async fn process_mint_tokens_for_market(
         recipient_pubkey,
         quote_amount,
     ));
+    if ix_only {
+        return print_instructions_json(&instructions);
+    }
     let signature = sdk
         .client
         .sign_send_instructions(instructions, vec![])
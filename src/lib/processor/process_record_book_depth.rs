@@ -0,0 +1,85 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Duration;
+
+use phoenix_sdk::sdk_client::*;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::{get_book_levels, get_ladder_depth_within_bps};
+use crate::helpers::output_helpers::{display_base_size, display_price};
+use crate::helpers::progress_helpers::ProgressReporter;
+use crate::print_info;
+
+pub async fn process_record_book_depth(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    bands: &[u64],
+    interval_seconds: u64,
+    samples: Option<u64>,
+    out: &str,
+    progress_file: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut progress = ProgressReporter::new(progress_file, "record-book-depth")?;
+
+    let path = shellexpand::tilde(out).to_string();
+    let is_new_file = std::fs::metadata(&path).is_err();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if is_new_file {
+        let mut columns = vec!["timestamp".to_string(), "mid".to_string()];
+        for band in bands {
+            columns.push(format!("bid_{}bps", band));
+            columns.push(format!("ask_{}bps", band));
+        }
+        writeln!(file, "{}", columns.join(","))?;
+    }
+
+    print_info!(
+        "Recording depth for market {} every {} second(s) to {}",
+        market_pubkey,
+        interval_seconds,
+        path
+    );
+    progress.emit(
+        "start",
+        json!({ "market": market_pubkey.to_string(), "out": path }),
+    )?;
+    // Samples are only counted once written, so failed fetches and one-sided books don't use up
+    // --samples
+    let mut recorded = 0;
+    loop {
+        let ladder = match get_book_levels(market_pubkey, &sdk.client, u64::MAX).await {
+            Ok(ladder) => ladder,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to fetch the book, skipping sample");
+                progress.emit("error", json!({ "message": e.to_string() }))?;
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+                continue;
+            }
+        };
+        if let (Some(best_bid), Some(best_ask)) = (ladder.bids.first(), ladder.asks.first()) {
+            let mid = (display_price(sdk, market_pubkey, best_bid.price_in_ticks)?
+                + display_price(sdk, market_pubkey, best_ask.price_in_ticks)?)
+                / 2.0;
+            let mut row = vec![get_unix_timestamp()?.to_string(), mid.to_string()];
+            for band in bands {
+                let (bid_lots, ask_lots) =
+                    get_ladder_depth_within_bps(&ladder, *band).unwrap_or((0, 0));
+                row.push(display_base_size(sdk, market_pubkey, bid_lots)?.to_string());
+                row.push(display_base_size(sdk, market_pubkey, ask_lots)?.to_string());
+            }
+            writeln!(file, "{}", row.join(","))?;
+            recorded += 1;
+            progress.emit("heartbeat", json!({ "samples": recorded }))?;
+            if samples.map(|n| recorded >= n).unwrap_or(false) {
+                break;
+            }
+        } else {
+            tracing::warn!("Book is one-sided, skipping sample");
+        }
+        tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+    }
+    progress.emit("stop", json!({ "samples": recorded }))?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use phoenix::quantities::WrapperU64;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use super::process_get_all_markets::{get_base_and_quote_symbols, get_phoenix_config};
+use crate::helpers::archive_helpers::{append_to_archive, Compression};
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::load_market;
+
+pub const FEE_SNAPSHOT_HEADER: &str =
+    "timestamp,market,quote_symbol,uncollected_fees,collected_fees";
+
+// Append the current uncollected and collected fees of every market in the config to a CSV file.
+// Meant to be run on a schedule (e.g. cron) to build up a history for get-fee-accrual.
+pub async fn process_record_fee_snapshot(
+    sdk: &mut SDKClient,
+    out: &str,
+    compression: Option<Compression>,
+) -> anyhow::Result<()> {
+    let config = get_phoenix_config(&sdk.client).await?;
+    let path = shellexpand::tilde(out).to_string();
+    let mut rows = String::new();
+    if std::fs::metadata(&path).is_err() {
+        writeln!(rows, "{}", FEE_SNAPSHOT_HEADER)?;
+    }
+
+    let timestamp = get_unix_timestamp()?;
+    for market_config in config.markets.iter() {
+        let market_pubkey = Pubkey::from_str(&market_config.market)?;
+        if !sdk.markets.contains_key(&market_pubkey) {
+            sdk.add_market(&market_pubkey).await?;
+        }
+        let meta = sdk.get_market_metadata_from_cache(&market_pubkey)?;
+        let market_account_data = sdk.client.get_account_data(&market_pubkey).await?;
+        let (header, market) = load_market(&market_account_data)?;
+        let (_, quote_mint_symbol) = get_base_and_quote_symbols(&config, header);
+
+        let uncollected = sdk.quote_lots_to_quote_atoms(
+            &market_pubkey,
+            market.get_uncollected_fee_amount().as_u64(),
+        )?;
+        let collected = sdk.quote_lots_to_quote_atoms(
+            &market_pubkey,
+            market.get_collected_fee_amount().as_u64(),
+        )?;
+        writeln!(
+            rows,
+            "{},{},{},{},{}",
+            timestamp,
+            market_pubkey,
+            quote_mint_symbol.unwrap_or_default(),
+            get_decimal_string(uncollected, meta.quote_decimals),
+            get_decimal_string(collected, meta.quote_decimals),
+        )?;
+    }
+    append_to_archive(&path, &rows, compression)?;
+    println!(
+        "Recorded fee snapshot for {} market(s) to {}",
+        config.markets.len(),
+        path
+    );
+    Ok(())
+}
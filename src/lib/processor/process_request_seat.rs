@@ -2,8 +2,17 @@ use phoenix::program::instruction_builders::create_request_seat_instruction;
 use phoenix_sdk::sdk_client::*;
 use solana_sdk::pubkey::Pubkey;
 
-pub async fn process_request_seat(market_pubkey: &Pubkey, sdk: &SDKClient) -> anyhow::Result<()> {
+use crate::helpers::instruction_helpers::print_instructions_json;
+
+pub async fn process_request_seat(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    ix_only: bool,
+) -> anyhow::Result<()> {
     let ix = create_request_seat_instruction(&sdk.core.trader, market_pubkey);
+    if ix_only {
+        return print_instructions_json(&[ix]);
+    }
     let tx = sdk.client.sign_send_instructions(vec![ix], vec![]).await;
 
     match tx {
@@ -0,0 +1,264 @@
+use anyhow::Context;
+use phoenix::program::deposit::DepositParams;
+use phoenix::program::instruction_builders::{
+    create_cancel_all_orders_instruction, create_deposit_funds_instruction,
+    create_withdraw_funds_instruction,
+};
+use phoenix_sdk::sdk_client::*;
+use phoenix_sdk::utils::create_claim_seat_ix_if_needed;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::helpers::market_helpers::load_market;
+
+// Migrate the payer's maker seat on a market to a new keypair: cancel all orders and withdraw with
+// the old wallet, move the withdrawn tokens to the new wallet, claim a seat and deposit with the new
+// wallet. Only the amounts the withdrawal added to the old wallet are moved, never tokens it held
+// before. Each step is its own transaction, so a failure reports where the funds were left.
+pub async fn process_rotate_keypair(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    new_keypair: &Keypair,
+    execute: bool,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    let old_trader = sdk.client.payer.pubkey();
+    let new_trader = new_keypair.pubkey();
+    if old_trader == new_trader {
+        return Err(anyhow::anyhow!(
+            "New keypair is the same as the current payer"
+        ));
+    }
+
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let state = market
+        .get_trader_state(&old_trader)
+        .ok_or_else(|| anyhow::anyhow!("Payer {} has no seat on market", old_trader))?;
+    let base_seat_lots = u64::from(state.base_lots_locked) + u64::from(state.base_lots_free);
+    let quote_seat_lots = u64::from(state.quote_lots_locked) + u64::from(state.quote_lots_free);
+    let format_base_lots = |lots: u64| -> anyhow::Result<String> {
+        Ok(get_decimal_string(
+            sdk.base_lots_to_base_atoms(market_pubkey, lots)?,
+            meta.base_decimals,
+        ))
+    };
+    let format_quote_lots = |lots: u64| -> anyhow::Result<String> {
+        Ok(get_decimal_string(
+            sdk.quote_lots_to_quote_atoms(market_pubkey, lots)?,
+            meta.quote_decimals,
+        ))
+    };
+
+    println!("Rotating maker seat on market {}", market_pubkey);
+    println!("Old wallet: {}", old_trader);
+    println!("New wallet: {}", new_trader);
+    println!(
+        "Base locked/free: {} / {}",
+        format_base_lots(u64::from(state.base_lots_locked))?,
+        format_base_lots(u64::from(state.base_lots_free))?
+    );
+    println!(
+        "Quote locked/free: {} / {}",
+        format_quote_lots(u64::from(state.quote_lots_locked))?,
+        format_quote_lots(u64::from(state.quote_lots_free))?
+    );
+    let amounts = format!(
+        "{} base and {} quote",
+        format_base_lots(base_seat_lots)?,
+        format_quote_lots(quote_seat_lots)?
+    );
+    println!("Steps:");
+    println!(
+        "  1. Cancel all orders and withdraw {} from the old wallet's seat",
+        amounts
+    );
+    println!(
+        "  2. Transfer the withdrawn {} to the new wallet. Other tokens in the old wallet stay there",
+        amounts
+    );
+    println!("  3. Claim a seat for the new wallet");
+    println!("  4. Deposit the transferred tokens into the new wallet's seat");
+    println!("  5. Verify the old seat is empty and the new seat is funded");
+    println!("Amounts can differ if orders fill before step 1 lands. The amounts withdrawn are what move.");
+    if !execute {
+        println!("Dry run only. Pass --execute to perform the rotation.");
+        return Ok(());
+    }
+
+    let old_atas = [&meta.base_mint, &meta.quote_mint]
+        .map(|mint| spl_associated_token_account::get_associated_token_address(&old_trader, mint));
+    let balances_before = [
+        get_token_balance(sdk, &old_atas[0]).await?,
+        get_token_balance(sdk, &old_atas[1]).await?,
+    ];
+
+    // 1. Cancel and withdraw with the old wallet
+    let tx = sdk
+        .client
+        .sign_send_instructions(
+            vec![
+                create_cancel_all_orders_instruction(
+                    market_pubkey,
+                    &old_trader,
+                    &meta.base_mint,
+                    &meta.quote_mint,
+                ),
+                create_withdraw_funds_instruction(
+                    market_pubkey,
+                    &old_trader,
+                    &meta.base_mint,
+                    &meta.quote_mint,
+                ),
+            ],
+            vec![],
+        )
+        .await
+        .context("Step 1 failed, nothing was moved")?;
+    println!("Cancelled orders and withdrew funds: {}", tx);
+
+    // 2. Move the tokens the withdrawal added to the old wallet
+    let recovery =
+        "the withdrawn funds are in the old wallet's token accounts. Transfer and deposit \
+        them manually, or deposit them back with the old wallet";
+    let mut amounts = vec![];
+    for (i, old_ata) in old_atas.iter().enumerate() {
+        let amount = get_token_balance(sdk, old_ata)
+            .await
+            .with_context(|| format!("Step 2 failed, {}", recovery))?
+            .saturating_sub(balances_before[i]);
+        amounts.push(amount);
+    }
+    println!(
+        "Withdrew {} base and {} quote",
+        get_decimal_string(amounts[0], meta.base_decimals),
+        get_decimal_string(amounts[1], meta.quote_decimals)
+    );
+    let mut instructions = vec![];
+    // The deposit in step 4 needs both of the new wallet's token accounts, so both are created
+    // whenever anything moves. Creation is idempotent, so existing accounts are left as they are.
+    if amounts.iter().any(|amount| *amount > 0) {
+        for (i, mint) in [&meta.base_mint, &meta.quote_mint].into_iter().enumerate() {
+            let new_ata =
+                spl_associated_token_account::get_associated_token_address(&new_trader, mint);
+            instructions.push(create_associated_token_account_idempotent(
+                &old_trader,
+                &new_trader,
+                mint,
+                &spl_token::id(),
+            ));
+            if amounts[i] > 0 {
+                instructions.push(spl_token::instruction::transfer(
+                    &spl_token::id(),
+                    &old_atas[i],
+                    &new_ata,
+                    &old_trader,
+                    &[],
+                    amounts[i],
+                )?);
+            }
+        }
+    }
+    if instructions.is_empty() {
+        println!("Nothing was withdrawn, so there is nothing to transfer");
+    } else {
+        let tx = sdk
+            .client
+            .sign_send_instructions(instructions, vec![])
+            .await
+            .with_context(|| format!("Step 2 failed, {}", recovery))?;
+        println!("Transferred tokens to new wallet: {}", tx);
+    }
+
+    // 3. Claim a seat for the new wallet
+    let recovery = "the withdrawn funds are in the new wallet's token accounts. Claim a seat and \
+        deposit them with the new wallet manually";
+    let claim_seat_ix = create_claim_seat_ix_if_needed(&sdk.client, market_pubkey, &new_trader)
+        .await
+        .with_context(|| format!("Step 3 failed, {}", recovery))?;
+    if !claim_seat_ix.is_empty() {
+        let tx = sdk
+            .client
+            .sign_send_instructions(claim_seat_ix, vec![new_keypair])
+            .await
+            .with_context(|| format!("Step 3 failed, {}", recovery))?;
+        println!("Claimed seat for new wallet: {}", tx);
+    }
+
+    // 4. Deposit with the new wallet
+    let base_lots = amounts[0] / meta.base_atoms_per_base_lot;
+    let quote_lots = amounts[1] / meta.quote_atoms_per_quote_lot;
+    if base_lots > 0 || quote_lots > 0 {
+        let tx = sdk
+            .client
+            .sign_send_instructions(
+                vec![create_deposit_funds_instruction(
+                    market_pubkey,
+                    &new_trader,
+                    &meta.base_mint,
+                    &meta.quote_mint,
+                    &DepositParams {
+                        quote_lots_to_deposit: quote_lots,
+                        base_lots_to_deposit: base_lots,
+                    },
+                )],
+                vec![new_keypair],
+            )
+            .await
+            .with_context(|| format!("Step 4 failed, {}", recovery))?;
+        println!("Deposited funds with new wallet: {}", tx);
+    }
+
+    // 5. Verify the end state
+    let market_account_data = sdk.client.get_account_data(market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let old_state_empty = market
+        .get_trader_state(&old_trader)
+        .map(|s| {
+            u64::from(s.base_lots_locked)
+                + u64::from(s.base_lots_free)
+                + u64::from(s.quote_lots_locked)
+                + u64::from(s.quote_lots_free)
+                == 0
+        })
+        .unwrap_or(true);
+    let new_state = market.get_trader_state(&new_trader);
+    match new_state {
+        Some(state) if old_state_empty => {
+            println!("Rotation complete");
+            println!(
+                "New wallet base free: {}",
+                format_base_lots(u64::from(state.base_lots_free))?
+            );
+            println!(
+                "New wallet quote free: {}",
+                format_quote_lots(u64::from(state.quote_lots_free))?
+            );
+        }
+        Some(_) => tracing::warn!("The old wallet's seat still holds funds"),
+        None => tracing::warn!(
+            "The new wallet has no seat. If the market is permissioned, request a seat and deposit manually"
+        ),
+    }
+
+    Ok(())
+}
+
+// Balance of a token account, or zero if it doesn't exist yet. RPC errors are returned rather than
+// read as a missing account, since a wrong zero before the withdrawal would move tokens the old
+// wallet already held.
+async fn get_token_balance(sdk: &SDKClient, token_account: &Pubkey) -> anyhow::Result<u64> {
+    match sdk
+        .client
+        .get_multiple_accounts(&[*token_account])
+        .await?
+        .pop()
+        .flatten()
+    {
+        Some(account) => Ok(spl_token::state::Account::unpack(&account.data)?.amount),
+        None => Ok(0),
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use phoenix::state::markets::LadderOrder;
+use phoenix_sdk::sdk_client::*;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+
+use super::process_get_all_markets::get_phoenix_config;
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::{
+    get_ladder_from_book, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{display_base_size, display_price};
+use crate::print_info;
+
+type BookCache = Arc<RwLock<HashMap<String, Value>>>;
+
+pub async fn process_serve_books(
+    sdk: &mut SDKClient,
+    markets: Vec<Pubkey>,
+    port: u16,
+    interval_ms: u64,
+) -> anyhow::Result<()> {
+    let markets = if markets.is_empty() {
+        get_phoenix_config(&sdk.client)
+            .await?
+            .markets
+            .iter()
+            .map(|m| Pubkey::from_str(&m.market))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        markets
+    };
+    for market_pubkey in markets.iter() {
+        if !sdk.markets.contains_key(market_pubkey) {
+            sdk.add_market(market_pubkey).await?;
+        }
+    }
+
+    let cache: BookCache = Arc::new(RwLock::new(HashMap::new()));
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    print_info!(
+        "Serving {} book(s) at http://127.0.0.1:{}/book/<market>",
+        markets.len(),
+        port
+    );
+
+    tokio::try_join!(
+        poll_books(sdk, &markets, cache.clone(), interval_ms),
+        serve(listener, cache.clone())
+    )?;
+    Ok(())
+}
+
+// Refresh every market's book in the cache. The cache is refreshed by polling, so the
+// sequence number in each book tells clients whether the book has changed since their last read.
+async fn poll_books(
+    sdk: &SDKClient,
+    markets: &[Pubkey],
+    cache: BookCache,
+    interval_ms: u64,
+) -> anyhow::Result<()> {
+    loop {
+        for market_pubkey in markets.iter() {
+            match get_book_snapshot(sdk, market_pubkey).await {
+                Ok(book) => {
+                    cache.write().await.insert(market_pubkey.to_string(), book);
+                }
+                Err(e) => {
+                    tracing::warn!(market = %market_pubkey, error = %e, "Failed to refresh book")
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+    }
+}
+
+async fn get_book_snapshot(sdk: &SDKClient, market_pubkey: &Pubkey) -> anyhow::Result<Value> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    let ladder = get_ladder_from_book(market, &clock, |_| false);
+    let levels = |levels: &[LadderOrder]| -> anyhow::Result<Vec<Value>> {
+        levels
+            .iter()
+            .map(|level| {
+                Ok(json!([
+                    display_price(sdk, market_pubkey, level.price_in_ticks)?,
+                    display_base_size(sdk, market_pubkey, level.size_in_base_lots)?
+                ]))
+            })
+            .collect()
+    };
+    Ok(json!({
+        "market": market_pubkey.to_string(),
+        "slot": clock.slot,
+        "sequence_number": market.get_sequence_number(),
+        "updated_at": get_unix_timestamp()?,
+        "bids": levels(&ladder.bids)?,
+        "asks": levels(&ladder.asks)?,
+    }))
+}
+
+async fn serve(listener: TcpListener, cache: BookCache) -> anyhow::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let cache = cache.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, cache).await {
+                tracing::warn!(error = %e, "Failed to handle request");
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, cache: BookCache) -> anyhow::Result<()> {
+    let mut buffer = [0u8; 1024];
+    let n = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..n]);
+    // Only the request line matters, e.g. "GET /book/<market> HTTP/1.1"
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, body) = match (method, path.strip_prefix("/book/")) {
+        ("GET", Some(market)) => match cache.read().await.get(market) {
+            Some(book) => ("200 OK", book.to_string()),
+            None => (
+                "404 Not Found",
+                json!({ "error": format!("Market {} is not being served", market) }).to_string(),
+            ),
+        },
+        ("GET", None) if path == "/books" => {
+            let markets = cache.read().await.keys().cloned().collect::<Vec<_>>();
+            ("200 OK", json!({ "markets": markets }).to_string())
+        }
+        _ => ("404 Not Found", json!({ "error": "Not found" }).to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
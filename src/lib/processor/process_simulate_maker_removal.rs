@@ -0,0 +1,101 @@
+use phoenix::state::markets::Ladder;
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::{
+    get_ladder_depth_within_bps, get_ladder_from_book, get_market_data_and_clock, load_market,
+};
+use crate::helpers::output_helpers::{display_base_size, display_price};
+
+const DEPTH_BANDS_BPS: [u64; 3] = [10, 50, 100];
+
+pub async fn process_simulate_maker_removal(
+    market_pubkey: &Pubkey,
+    maker_pubkey: &Pubkey,
+    sdk: &SDKClient,
+) -> anyhow::Result<()> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+
+    let trader_index = market
+        .get_trader_index(maker_pubkey)
+        .ok_or_else(|| anyhow::anyhow!("Maker not found on market"))? as u64;
+
+    let with_maker = get_ladder_from_book(market, &clock, |_| false);
+    let without_maker =
+        get_ladder_from_book(market, &clock, |order| order.trader_index == trader_index);
+
+    let format_price = |ladder: &Ladder, bid: bool| -> anyhow::Result<String> {
+        let level = if bid {
+            ladder.bids.first()
+        } else {
+            ladder.asks.first()
+        };
+        Ok(match level {
+            Some(level) => display_price(sdk, market_pubkey, level.price_in_ticks)?.to_string(),
+            None => "-".to_string(),
+        })
+    };
+    let format_spread = |ladder: &Ladder| match (ladder.bids.first(), ladder.asks.first()) {
+        (Some(bid), Some(ask)) => {
+            let mid = (bid.price_in_ticks + ask.price_in_ticks) as f64 / 2.0;
+            format!(
+                "{:.2}",
+                (ask.price_in_ticks - bid.price_in_ticks) as f64 / mid * 10_000.0
+            )
+        }
+        _ => "-".to_string(),
+    };
+
+    let mut rows = vec![
+        (
+            "Best bid".to_string(),
+            format_price(&with_maker, true)?,
+            format_price(&without_maker, true)?,
+        ),
+        (
+            "Best ask".to_string(),
+            format_price(&with_maker, false)?,
+            format_price(&without_maker, false)?,
+        ),
+        (
+            "Spread (bps)".to_string(),
+            format_spread(&with_maker),
+            format_spread(&without_maker),
+        ),
+    ];
+    for band in DEPTH_BANDS_BPS {
+        let depth = |ladder: &Ladder| get_ladder_depth_within_bps(ladder, band);
+        let format_depth = |depth: Option<(u64, u64)>, bid: bool| -> anyhow::Result<String> {
+            Ok(match depth {
+                Some((bid_lots, ask_lots)) => format!(
+                    "{:.3}",
+                    display_base_size(sdk, market_pubkey, if bid { bid_lots } else { ask_lots })?
+                ),
+                None => "-".to_string(),
+            })
+        };
+        rows.push((
+            format!("Bid depth within {} bps", band),
+            format_depth(depth(&with_maker), true)?,
+            format_depth(depth(&without_maker), true)?,
+        ));
+        rows.push((
+            format!("Ask depth within {} bps", band),
+            format_depth(depth(&with_maker), false)?,
+            format_depth(depth(&without_maker), false)?,
+        ));
+    }
+
+    println!("Market: {}", market_pubkey);
+    println!("Removed maker: {}", maker_pubkey);
+    println!(
+        "{0: <24} | {1: <16} | {2: <16}",
+        "Metric", "With maker", "Without maker"
+    );
+    for (metric, with, without) in rows {
+        println!("{0: <24} | {1: <16} | {2: <16}", metric, with, without);
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use phoenix::program::instruction_builders::create_new_order_instruction;
+use phoenix::program::status::MarketStatus;
+use phoenix::state::{OrderPacket, SelfTradeBehavior, Side};
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::instruction_helpers::print_instructions_json;
+use crate::helpers::market_helpers::{
+    float_price_to_passive_ticks, get_best_bid_and_ask, get_market_data_and_clock, load_market,
+    ticks_to_float_price,
+};
+use crate::helpers::output_helpers::{display_price, format_base_lots, is_raw_units};
+use crate::{print_info, print_summary};
+
+// Wait until a market is active with liquidity inside the price bound on the opposite side, then
+// immediately send a single immediate-or-cancel order. The limit price caps the worst fill, so the
+// loss is bounded however the book looks when the order lands. The price and size can be given in
+// ticks and base lots instead, which are used as is.
+#[allow(clippy::too_many_arguments)]
+pub async fn process_snipe(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    side: Side,
+    size: Option<f64>,
+    size_in_lots: Option<u64>,
+    limit_price: Option<f64>,
+    price_in_ticks: Option<u64>,
+    interval_ms: u64,
+    ix_only: bool,
+) -> anyhow::Result<()> {
+    let meta = sdk.get_market_metadata_from_cache(market_pubkey)?;
+    let price_in_ticks = match (price_in_ticks, limit_price) {
+        (Some(price_in_ticks), _) => price_in_ticks,
+        // Round the limit away from the market so it never allows a worse fill than requested
+        (None, Some(limit_price)) => float_price_to_passive_ticks(meta, limit_price, side),
+        (None, None) => {
+            return Err(anyhow::anyhow!(
+                "Either --limit-price or --price-in-ticks is required"
+            ))
+        }
+    };
+    let num_base_lots = match (size_in_lots, size) {
+        (Some(size_in_lots), _) => size_in_lots,
+        (None, Some(size)) => sdk.raw_base_units_to_base_lots_rounded_down(market_pubkey, size)?,
+        (None, None) => {
+            return Err(anyhow::anyhow!(
+                "Either --size or --size-in-lots is required"
+            ))
+        }
+    };
+    if price_in_ticks == 0 || num_base_lots == 0 {
+        return Err(anyhow::anyhow!(
+            "Price and size must be at least one tick and one base lot"
+        ));
+    }
+
+    let order_packet = OrderPacket::new_ioc_by_lots(
+        side,
+        price_in_ticks,
+        num_base_lots,
+        SelfTradeBehavior::CancelProvide,
+        None,
+        get_unix_timestamp()? as u128,
+        false,
+    );
+    let ix = create_new_order_instruction(
+        market_pubkey,
+        &sdk.trader,
+        &meta.base_mint,
+        &meta.quote_mint,
+        &order_packet,
+    );
+    let side_name = match side {
+        Side::Bid => "buy",
+        Side::Ask => "sell",
+    };
+    // Echo the order in human units next to the raw values actually sent
+    if is_raw_units() {
+        print_summary!(
+            ix_only,
+            "Order: {} {} base lots at a limit of {} ticks",
+            side_name,
+            num_base_lots,
+            price_in_ticks
+        );
+    } else {
+        print_summary!(
+            ix_only,
+            "Order: {} {} ({} base lots) at a limit of {} ({} ticks)",
+            side_name,
+            format_base_lots(sdk, market_pubkey, num_base_lots)?,
+            num_base_lots,
+            ticks_to_float_price(meta, price_in_ticks),
+            price_in_ticks
+        );
+    }
+    if ix_only {
+        return print_instructions_json(&[ix]);
+    }
+
+    print_info!("Waiting for the limit to cross on market {}", market_pubkey);
+    loop {
+        let (market_account_data, clock) =
+            get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+        let (header, market) = load_market(&market_account_data)?;
+        let status = MarketStatus::from(header.status);
+        let (best_bid, best_ask) = get_best_bid_and_ask(market, &clock);
+        // Only fire when the order would fill at least partially within the bound
+        let crossable = match side {
+            Side::Bid => best_ask.map(|ask| ask <= price_in_ticks).unwrap_or(false),
+            Side::Ask => best_bid.map(|bid| bid >= price_in_ticks).unwrap_or(false),
+        };
+        if matches!(status, MarketStatus::Active) && crossable {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+    }
+
+    let tx = sdk.client.sign_send_instructions(vec![ix], vec![]).await?;
+    println!("Sent IOC order, transaction: {}", tx);
+    let events = match sdk.parse_events_from_transaction(&tx).await {
+        Some(events) => events,
+        None => {
+            println!("Fill status unknown: failed to parse events from the transaction");
+            return Ok(());
+        }
+    };
+    let fills = events
+        .into_iter()
+        .filter_map(|event| match event.details {
+            MarketEventDetails::Fill(fill) if fill.taker == sdk.trader => {
+                Some((fill.price_in_ticks, fill.base_lots_filled))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let base_lots_filled = fills.iter().map(|(_, lots)| lots).sum::<u64>();
+    if base_lots_filled == 0 {
+        println!("Order did not fill");
+        return Ok(());
+    }
+    let average_price_in_ticks = fills
+        .iter()
+        .map(|(price, lots)| *price as f64 * *lots as f64)
+        .sum::<f64>()
+        / base_lots_filled as f64;
+    println!(
+        "Filled {} at an average price of {}",
+        format_base_lots(sdk, market_pubkey, base_lots_filled)?,
+        display_price(sdk, market_pubkey, average_price_in_ticks.round() as u64)?
+    );
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use phoenix_sdk::sdk_client::*;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use super::process_get_all_markets::{fetch_cluster, fetch_phoenix_config};
+use crate::helpers::cache_helpers::{write_cache, write_cached_cluster};
+use crate::helpers::market_helpers::load_market;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedMarketMetadata {
+    pub market: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_symbol: Option<String>,
+    pub quote_symbol: Option<String>,
+    pub authority: String,
+    pub base_decimals: u32,
+    pub quote_decimals: u32,
+    pub base_atoms_per_base_lot: u64,
+    pub quote_atoms_per_quote_lot: u64,
+    pub tick_size_in_quote_atoms_per_base_unit: u64,
+}
+
+pub async fn process_warm_cache(sdk: &mut SDKClient) -> anyhow::Result<()> {
+    let cluster = fetch_cluster(&sdk.client).await?;
+    write_cached_cluster(&sdk.client.url(), cluster)?;
+    // Always refetch so that warming replaces a stale config
+    let config = fetch_phoenix_config(cluster).await?;
+    let path = write_cache(cluster, "config", &config)?;
+    println!("Cached config for {} at {}", cluster, path.display());
+
+    let symbol = |mint: &Pubkey| {
+        config
+            .tokens
+            .iter()
+            .find(|t| t.mint == mint.to_string())
+            .map(|t| t.symbol.clone())
+    };
+
+    let mut markets = vec![];
+    for market in config.markets.iter() {
+        let market_pubkey = Pubkey::from_str(&market.market)?;
+        // The market header is read directly since the SDK's metadata lacks the authority
+        let loaded = async {
+            let market_account_data = sdk.client.get_account_data(&market_pubkey).await?;
+            let (header, _market) = load_market(&market_account_data)?;
+            anyhow::Ok((MarketMetadata::from_header(header)?, header.authority))
+        };
+        let (meta, authority) = match loaded.await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                tracing::warn!(market = %market_pubkey, error = %e, "Failed to load market");
+                continue;
+            }
+        };
+        markets.push(CachedMarketMetadata {
+            market: market.market.clone(),
+            base_mint: meta.base_mint.to_string(),
+            quote_mint: meta.quote_mint.to_string(),
+            base_symbol: symbol(&meta.base_mint),
+            quote_symbol: symbol(&meta.quote_mint),
+            authority: authority.to_string(),
+            base_decimals: meta.base_decimals,
+            quote_decimals: meta.quote_decimals,
+            base_atoms_per_base_lot: meta.base_atoms_per_base_lot,
+            quote_atoms_per_quote_lot: meta.quote_atoms_per_quote_lot,
+            tick_size_in_quote_atoms_per_base_unit: meta.tick_size_in_quote_atoms_per_base_unit,
+        });
+    }
+    let path = write_cache(cluster, "markets", &markets)?;
+    println!(
+        "Cached metadata for {} market(s) at {}",
+        markets.len(),
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use phoenix_sdk::sdk_client::*;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::{get_market_data_and_clock, load_market};
+use crate::helpers::output_helpers::{format_base_lots, format_quote_lots};
+use crate::helpers::progress_helpers::ProgressReporter;
+use crate::print_info;
+
+// Base locked, base free, quote locked, quote free, in lots
+type Balances = [u64; 4];
+
+pub async fn process_watch_balance(
+    market_pubkey: &Pubkey,
+    trader_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    interval_seconds: u64,
+    progress_file: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut progress = ProgressReporter::new(progress_file, "watch-balance")?;
+    let format_lots = |index: usize, lots: u64| {
+        if index < 2 {
+            format_base_lots(sdk, market_pubkey, lots)
+        } else {
+            format_quote_lots(sdk, market_pubkey, lots)
+        }
+    };
+    let labels = ["base locked", "base free", "quote locked", "quote free"];
+
+    print_info!(
+        "Watching trader {} on market {} every {} second(s)",
+        trader_pubkey,
+        market_pubkey,
+        interval_seconds
+    );
+    progress.emit(
+        "start",
+        json!({
+            "market": market_pubkey.to_string(),
+            "trader": trader_pubkey.to_string(),
+        }),
+    )?;
+    let mut previous: Option<Balances> = None;
+    loop {
+        match get_balances(sdk, market_pubkey, trader_pubkey).await {
+            Ok((slot, balances)) => {
+                let timestamp = get_unix_timestamp()?;
+                progress.emit("heartbeat", json!({ "slot": slot }))?;
+                match previous {
+                    None => {
+                        let mut initial = vec![];
+                        for (i, label) in labels.iter().enumerate() {
+                            initial.push(format!("{} {}", label, format_lots(i, balances[i])?));
+                        }
+                        println!("{} slot {}: {}", timestamp, slot, initial.join(", "));
+                    }
+                    Some(previous) if previous != balances => {
+                        let mut changes = vec![];
+                        for (i, label) in labels.iter().enumerate() {
+                            let change = balances[i] as i128 - previous[i] as i128;
+                            if change != 0 {
+                                changes.push(format!(
+                                    "{} {}{}",
+                                    label,
+                                    if change < 0 { "-" } else { "+" },
+                                    format_lots(i, change.unsigned_abs() as u64)?
+                                ));
+                            }
+                        }
+                        println!("{} slot {}: {}", timestamp, slot, changes.join(", "));
+                    }
+                    _ => {}
+                }
+                previous = Some(balances);
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to fetch trader state");
+                progress.emit("error", json!({ "message": e.to_string() }))?;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+    }
+}
+
+async fn get_balances(
+    sdk: &SDKClient,
+    market_pubkey: &Pubkey,
+    trader_pubkey: &Pubkey,
+) -> anyhow::Result<(u64, Balances)> {
+    let (market_account_data, clock) =
+        get_market_data_and_clock(&sdk.client, market_pubkey).await?;
+    let (_, market) = load_market(&market_account_data)?;
+    // A trader without a seat holds nothing on the market
+    let balances = market
+        .get_trader_state(trader_pubkey)
+        .map(|state| {
+            [
+                u64::from(state.base_lots_locked),
+                u64::from(state.base_lots_free),
+                u64::from(state.quote_lots_locked),
+                u64::from(state.quote_lots_free),
+            ]
+        })
+        .unwrap_or_default();
+    Ok((clock.slot, balances))
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use phoenix_sdk::sdk_client::*;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::market_helpers::get_book_levels;
+use crate::helpers::print_helpers::print_book;
+
+// Clear the terminal and move the cursor to the top left corner
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+
+// Poll the book and redraw it in place until interrupted. Failed polls leave the last book on
+// screen and are retried at the next interval.
+pub async fn process_watch_book(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    depth: u64,
+    interval_ms: u64,
+    precision: Option<usize>,
+) -> anyhow::Result<()> {
+    loop {
+        let book = match get_book_levels(market_pubkey, &sdk.client, depth).await {
+            Ok(book) => book,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to fetch the book");
+                tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+                continue;
+            }
+        };
+        print!("{}", CLEAR_SCREEN);
+        println!(
+            "Market: {} (refreshing every {}ms)",
+            market_pubkey, interval_ms
+        );
+        if book.bids.is_empty() && book.asks.is_empty() {
+            println!("Book is empty");
+        } else {
+            print_book(sdk, market_pubkey, &book, None, precision, false)?;
+        }
+        tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+    }
+}
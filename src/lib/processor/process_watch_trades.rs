@@ -0,0 +1,158 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use colored::Colorize;
+use futures::StreamExt;
+use phoenix::state::Side;
+use phoenix_sdk::sdk_client::*;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::helpers::color_helpers::get_theme;
+use crate::helpers::output_helpers::{
+    display_base_size, display_price, format_timestamp, OutputFormat,
+};
+use crate::helpers::print_helpers::{get_event_client_order_ids, get_event_log_entry};
+use crate::print_info;
+
+const MAX_RETRIES: u32 = 4;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+// Subscribe to the logs of transactions mentioning the market and print their fills as they land,
+// until interrupted. Buys are colored as bids and sells as asks, and json or jsonl output prints
+// one JSON object per fill. Dropped websocket connections are reconnected, and the command fails
+// once reconnecting fails MAX_RETRIES times in a row.
+pub async fn process_watch_trades(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    ws_url: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let ws_url = match ws_url {
+        Some(ws_url) => ws_url.to_string(),
+        None => solana_cli_config::Config::compute_websocket_url(&sdk.client.url()),
+    };
+    let mut failures = 0;
+    let mut connected = false;
+    loop {
+        let pubsub = match PubsubClient::new(&ws_url).await {
+            Ok(pubsub) => pubsub,
+            Err(e) => {
+                failures = wait_to_reconnect(failures, e.into()).await?;
+                continue;
+            }
+        };
+        let (mut notifications, unsubscribe) = match pubsub
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![market_pubkey.to_string()]),
+                RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                failures = wait_to_reconnect(failures, e.into()).await?;
+                continue;
+            }
+        };
+        failures = 0;
+        if connected {
+            tracing::info!("Reconnected, trades made while disconnected were not printed");
+        } else {
+            print_info!("Watching trades on market {}", market_pubkey);
+            connected = true;
+        }
+
+        while let Some(notification) = notifications.next().await {
+            if notification.value.err.is_some() {
+                continue;
+            }
+            let signature = Signature::from_str(&notification.value.signature)?;
+            print_trades(market_pubkey, sdk, &signature, output).await?;
+        }
+        unsubscribe().await;
+        failures =
+            wait_to_reconnect(failures, anyhow::anyhow!("Websocket connection closed")).await?;
+    }
+}
+
+// Back off before the next connection attempt, or give up with the error once the retries are
+// used up. Returns the updated count of consecutive failures.
+async fn wait_to_reconnect(failures: u32, error: anyhow::Error) -> anyhow::Result<u32> {
+    if failures >= MAX_RETRIES {
+        return Err(error.context("Failed to reconnect to the websocket"));
+    }
+    let delay = RETRY_BASE_DELAY * 2u32.pow(failures);
+    tracing::warn!(
+        error = %error,
+        retry_in_ms = delay.as_millis() as u64,
+        "Websocket disconnected, reconnecting"
+    );
+    tokio::time::sleep(delay).await;
+    Ok(failures + 1)
+}
+
+async fn print_trades(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    signature: &Signature,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    // The logs are truncated for large transactions, so the events are read from the
+    // transaction itself. The node may not serve a transaction yet when its logs arrive, so
+    // reading it is retried.
+    let mut attempt = 0;
+    let events = loop {
+        match sdk.parse_events_from_transaction(signature).await {
+            Some(events) => break events,
+            None if attempt < MAX_RETRIES => {
+                tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt)).await;
+                attempt += 1;
+            }
+            None => {
+                tracing::warn!(
+                    %signature,
+                    "Failed to parse events from transaction, its trades are not printed"
+                );
+                return Ok(());
+            }
+        }
+    };
+    let theme = get_theme();
+    let client_order_ids = get_event_client_order_ids(&events);
+    for (event, client_order_ids) in events
+        .iter()
+        .zip(client_order_ids)
+        .filter(|(e, _)| e.market == *market_pubkey)
+    {
+        let fill = match &event.details {
+            MarketEventDetails::Fill(fill) => fill,
+            _ => continue,
+        };
+        if matches!(output, OutputFormat::Json | OutputFormat::Jsonl) {
+            if let Some(entry) = get_event_log_entry(sdk, event, client_order_ids)? {
+                println!("{}", entry.to_json());
+            }
+            continue;
+        }
+        // The filled side is the resting order's, so a filled ask was bought by the taker
+        let (label, color) = match fill.side_filled {
+            Side::Ask => ("BUY ", theme.bid),
+            Side::Bid => ("SELL", theme.ask),
+        };
+        println!(
+            "{} {} {:>15} @ {:<15} {}",
+            format_timestamp(event.timestamp as i64),
+            label.color(color),
+            display_base_size(sdk, market_pubkey, fill.base_lots_filled)?,
+            display_price(sdk, market_pubkey, fill.price_in_ticks)?,
+            signature
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use colored::Colorize;
+use phoenix_sdk::sdk_client::*;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::helpers::color_helpers::get_theme;
+use crate::helpers::history_helpers::get_unix_timestamp;
+use crate::helpers::market_helpers::get_vault_reconciliation;
+use crate::helpers::output_helpers::format_atoms;
+use crate::helpers::progress_helpers::ProgressReporter;
+use crate::print_info;
+
+pub async fn process_watch_vaults(
+    market_pubkey: &Pubkey,
+    sdk: &SDKClient,
+    interval_seconds: u64,
+    progress_file: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut progress = ProgressReporter::new(progress_file, "watch-vaults")?;
+    let meta = sdk.get_market_metadata(market_pubkey).await?;
+    print_info!(
+        "Watching vaults for market {} every {} second(s)",
+        market_pubkey,
+        interval_seconds
+    );
+    progress.emit("start", json!({ "market": market_pubkey.to_string() }))?;
+    loop {
+        match get_vault_reconciliation(sdk, market_pubkey).await {
+            Ok(reconciliation) => {
+                let base_drift = reconciliation.base_drift();
+                let quote_drift = reconciliation.quote_drift();
+                let timestamp = get_unix_timestamp()?;
+                progress.emit(
+                    "heartbeat",
+                    json!({
+                        "slot": reconciliation.slot,
+                        "base_drift": base_drift.to_string(),
+                        "quote_drift": quote_drift.to_string(),
+                    }),
+                )?;
+                if base_drift == 0 && quote_drift == 0 {
+                    println!(
+                        "{} slot {}: {} base {}, quote {}",
+                        timestamp,
+                        reconciliation.slot,
+                        "OK".green(),
+                        format_atoms(reconciliation.base_vault_balance, meta.base_decimals),
+                        format_atoms(reconciliation.quote_vault_balance, meta.quote_decimals),
+                    );
+                } else {
+                    eprintln!(
+                        "{} slot {}: {} base vault {} (drift {} atoms), quote vault {} (drift {} atoms)",
+                        timestamp,
+                        reconciliation.slot,
+                        "DRIFT".color(get_theme().warning),
+                        reconciliation.base_vault,
+                        base_drift,
+                        reconciliation.quote_vault,
+                        quote_drift,
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to reconcile vaults");
+                progress.emit("error", json!({ "message": e.to_string() }))?;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+    }
+}
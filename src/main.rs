@@ -1,22 +1,40 @@
 mod command;
+mod parse;
 
 use crate::command::PhoenixCLICommand;
 use anyhow::anyhow;
+use chrono_tz::Tz;
 use clap::Parser;
 use ellipsis_client::EllipsisClient;
+use phoenix_cli_processor::helpers::color_helpers::{configure_colors, ColorChoice};
+use phoenix_cli_processor::helpers::error_helpers::{classify_error, print_error_json, UserAbort};
+use phoenix_cli_processor::helpers::log_helpers::{
+    init_logging, new_timed_rpc_client, new_timed_sdk_client, LogFormat,
+};
+use phoenix_cli_processor::helpers::market_helpers::BookGrouping;
+use phoenix_cli_processor::helpers::output_helpers::{
+    load_report_template, set_csv_metadata, set_quiet, set_raw_units, set_time_display,
+    OutputFormat,
+};
+use phoenix_cli_processor::helpers::print_helpers::EventLogFormat;
 use phoenix_cli_processor::processor::process_claim_seat::process_claim_seat;
 use phoenix_cli_processor::processor::process_evict_seat::process_evict_seat;
 use phoenix_cli_processor::processor::{
-    process_get_all_markets::*, process_get_book_levels::*, process_get_full_book::*, process_get_market::*, process_get_market_status::*, process_get_open_orders::*, process_get_seat_info::*, process_get_seat_manager_info::*, process_get_top_of_book::*, process_get_traders_for_market::*, process_get_transaction::*, process_mint_tokens::*, process_mint_tokens_for_market::*, process_request_seat::*, process_get_uncollected_revenue::*
+    process_get_all_markets::*, process_get_book_levels::*, process_get_full_book::*, process_get_market::*, process_get_market_status::*, process_get_open_orders::*, process_get_seat_info::*, process_get_seat_manager_info::*, process_get_top_of_book::*, process_get_traders_for_market::*, process_get_transaction::*, process_mint_tokens::*, process_mint_tokens_for_market::*, process_request_seat::*, process_get_uncollected_revenue::*, process_fund_makers::*, process_get_stale_orders::*, process_get_maker_activity::*, process_watch_vaults::*, process_record_book_depth::*, process_record_fee_snapshot::*, process_get_fee_accrual::*, process_simulate_maker_removal::*, process_get_queue_position::*, process_get_level::*, process_get_priority_fees::*, process_rotate_keypair::*, process_serve_books::*, process_warm_cache::*, process_get_revenue_statement::*, process_get_balance_ledger::*, process_watch_balance::*, process_snipe::*, process_estimate_incentives::*, process_migrate_quotes::*, process_get_settlement_report::*, process_get_book_orders::*, process_get_bbo::*, process_get_spread::*, process_get_all_seats::*, process_get_trader_fills::*, process_get_recent_trades::*, process_get_candles::*, process_get_volume::*, process_get_liquidity::*, process_get_fees::*, process_get_balances::*, process_inspect_tx::*, process_get_order::*, process_get_vwap::*, process_watch_book::*, process_watch_trades::*, process_compare_books::*, process_get_trader_markets::*, process_get_vaults::*, process_get_book_at_slot::*, process_get_events::*, process_get_events_range::*, process_get_maker_leaderboard::*, process_get_taker_leaderboard::*
 };
 use phoenix_sdk::sdk_client::*;
 use solana_cli_config::{Config, ConfigInput, CONFIG_FILE};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::signer::keypair::{read_keypair_file, Keypair};
 use solana_sdk::signer::Signer;
 
 #[derive(Parser)]
-#[command(author, version, about)]
+#[command(
+    author,
+    version,
+    about,
+    after_help = "Exit codes: 1 other error, 2 usage error, 3 RPC failure, 4 deserialization failure, 5 insufficient funds, 6 aborted by user. Ctrl-C stops the watch-*, record-* and serve-books commands with exit code 0"
+)]
 struct Args {
     #[clap(subcommand)]
     command: PhoenixCLICommand,
@@ -29,6 +47,62 @@ struct Args {
     /// Optionally include a commitment level. Defaults to your Solana CLI config file.
     #[clap(global = true, short, long)]
     commitment: Option<String>,
+    /// Optionally write newline-delimited JSON progress and heartbeat events from long-running commands to this path.
+    /// Use /dev/fd/<n> to write to an inherited file descriptor.
+    #[clap(global = true, long)]
+    progress_file: Option<String>,
+    /// For commands that send transactions, print the instructions as JSON (program id, accounts, base64 data)
+    /// instead of sending them, so they can be composed into larger transactions. Not supported by rotate-keypair
+    #[clap(global = true, long)]
+    ix_only: bool,
+    /// Output format for command results. Use json to get machine readable results, including errors, which are
+    /// printed to stderr as {code, message, context}. json is not supported by commands that send transactions,
+    /// stream or write files, except watch-trades which prints one object per fill, nor by get-maker-activity,
+    /// get-fee-accrual, get-queue-position, get-level, get-priority-fees, get-revenue-statement,
+    /// get-balance-ledger, get-settlement-report, get-stale-orders, simulate-maker-removal and
+    /// estimate-incentives. csv is supported by the
+    /// book and traders-for-market commands, with columns price,size,side for books and an extra trader column
+    /// for get-book-levels. table is supported
+    /// by get-all-markets, get-traders-for-market and get-seat-manager-info. yaml is supported by get-market.
+    /// markdown is supported by get-all-markets and get-uncollected-revenue
+    #[clap(global = true, long, value_enum, default_value = "text")]
+    output: OutputFormat,
+    /// Render results through this handlebars template file instead of the default layout. The template
+    /// receives the same fields as --output json. Supported by get-market, get-all-markets and
+    /// get-uncollected-revenue
+    #[clap(global = true, long, conflicts_with = "output")]
+    template: Option<String>,
+    /// Show full pubkeys in table output instead of truncating them
+    #[clap(global = true, long)]
+    wide: bool,
+    /// Suppress informational messages, such as progress, that are otherwise written to stderr
+    #[clap(global = true, long)]
+    quiet: bool,
+    /// Show prices in ticks, sizes in base and quote lots and token balances in atoms instead of
+    /// converting them to token units
+    #[clap(global = true, long)]
+    raw_units: bool,
+    /// Start CSV exports with a `# slot=... blockhash=... rpc_endpoint=... cli_version=...` comment line.
+    /// Off by default so the CSV loads as-is into tools that don't skip comments
+    #[clap(global = true, long)]
+    csv_metadata: bool,
+    /// Timezone to show event timestamps in, e.g. America/New_York
+    #[clap(global = true, long, default_value = "UTC")]
+    timezone: Tz,
+    /// strftime format for event timestamps, e.g. '%Y-%m-%d %H:%M:%S %Z'. Defaults to ISO-8601
+    #[clap(global = true, long)]
+    time_format: Option<String>,
+    /// When to color output. Auto colors terminal output unless NO_COLOR is set. Bid, ask and warning colors
+    /// can be remapped in the theme section of ~/.config/phoenix-cli/config.json
+    #[clap(global = true, long, value_enum, default_value = "auto")]
+    color: ColorChoice,
+    /// Format for diagnostic logs, which are written to stderr
+    #[clap(global = true, long, value_enum, default_value = "pretty")]
+    log_format: LogFormat,
+    /// Log more detail: -v for info, -vv for debug, including every RPC request with its latency.
+    /// RUST_LOG overrides this
+    #[clap(global = true, short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
 }
 
 pub fn get_network(network_str: &str) -> &str {
@@ -44,12 +118,189 @@ pub fn get_payer_keypair_from_path(path: &str) -> anyhow::Result<Keypair> {
     read_keypair_file(&*shellexpand::tilde(path)).map_err(|e| anyhow!(e.to_string()))
 }
 
+// csv and table output only make sense for commands that list rows
+fn check_output_supported(command: &PhoenixCLICommand, output: OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Csv
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetTopOfBook { .. }
+                    | PhoenixCLICommand::GetBookLevels { .. }
+                    | PhoenixCLICommand::GetFullBook { .. }
+                    | PhoenixCLICommand::GetTradersForMarket { .. }
+                    | PhoenixCLICommand::GetCandles { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--output csv is only supported by the book, get-traders-for-market and get-candles commands"
+            ))
+        }
+        OutputFormat::Table
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetAllMarkets { .. }
+                    | PhoenixCLICommand::GetTradersForMarket { .. }
+                    | PhoenixCLICommand::GetSeatManagerInfo { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--output table is only supported by get-all-markets, get-traders-for-market and get-seat-manager-info"
+            ))
+        }
+        OutputFormat::Yaml if !matches!(command, PhoenixCLICommand::GetMarket { .. }) => Err(
+            anyhow!("--output yaml is only supported by get-market"),
+        ),
+        OutputFormat::Markdown
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetAllMarkets { .. }
+                    | PhoenixCLICommand::GetUncollectedRevenue { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--output markdown is only supported by get-all-markets and get-uncollected-revenue"
+            ))
+        }
+        OutputFormat::Template
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetMarket { .. }
+                    | PhoenixCLICommand::GetAllMarkets { .. }
+                    | PhoenixCLICommand::GetUncollectedRevenue { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--template is only supported by get-market, get-all-markets and get-uncollected-revenue"
+            ))
+        }
+        OutputFormat::Json
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetMarket { .. }
+                    | PhoenixCLICommand::GetAllMarkets { .. }
+                    | PhoenixCLICommand::GetTradersForMarket { .. }
+                    | PhoenixCLICommand::GetBbo { .. }
+                    | PhoenixCLICommand::GetSpread { .. }
+                    | PhoenixCLICommand::GetLiquidity { .. }
+                    | PhoenixCLICommand::GetBookAtSlot { .. }
+                    | PhoenixCLICommand::GetOrder { .. }
+                    | PhoenixCLICommand::CompareBooks { .. }
+                    | PhoenixCLICommand::GetTopOfBook { .. }
+                    | PhoenixCLICommand::GetBookLevels { .. }
+                    | PhoenixCLICommand::GetFullBook { .. }
+                    | PhoenixCLICommand::InspectTx { .. }
+                    | PhoenixCLICommand::GetTransaction { .. }
+                    | PhoenixCLICommand::GetEvents { .. }
+                    | PhoenixCLICommand::GetEventsRange { .. }
+                    | PhoenixCLICommand::GetFees { .. }
+                    | PhoenixCLICommand::GetMarketStatus { .. }
+                    | PhoenixCLICommand::GetAllSeats { .. }
+                    | PhoenixCLICommand::GetSeatInfo { .. }
+                    | PhoenixCLICommand::GetOpenOrders { .. }
+                    | PhoenixCLICommand::GetSeatManagerInfo { .. }
+                    | PhoenixCLICommand::GetUncollectedRevenue { .. }
+                    | PhoenixCLICommand::GetRecentTrades { .. }
+                    | PhoenixCLICommand::GetCandles { .. }
+                    | PhoenixCLICommand::GetVolume { .. }
+                    | PhoenixCLICommand::GetMakerLeaderboard { .. }
+                    | PhoenixCLICommand::GetTakerLeaderboard { .. }
+                    | PhoenixCLICommand::GetVwap { .. }
+                    | PhoenixCLICommand::GetVaults { .. }
+                    | PhoenixCLICommand::GetBalances { .. }
+                    | PhoenixCLICommand::GetTraderMarkets { .. }
+                    | PhoenixCLICommand::GetTraderFills { .. }
+                    | PhoenixCLICommand::WatchTrades { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--output json is only supported by read-only commands that print their results; this command does not"
+            ))
+        }
+        OutputFormat::Jsonl
+            if !matches!(
+                command,
+                PhoenixCLICommand::GetTransaction { .. }
+                    | PhoenixCLICommand::GetEvents { .. }
+                    | PhoenixCLICommand::GetEventsRange { .. }
+                    | PhoenixCLICommand::WatchTrades { .. }
+            ) =>
+        {
+            Err(anyhow!(
+                "--output jsonl is only supported by get-transaction, get-events, get-events-range and watch-trades"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Streaming and recording commands run until interrupted, so Ctrl-C is how they finish normally
+fn runs_until_interrupted(command: &PhoenixCLICommand) -> bool {
+    matches!(
+        command,
+        PhoenixCLICommand::WatchBook { .. }
+            | PhoenixCLICommand::WatchTrades { .. }
+            | PhoenixCLICommand::WatchVaults { .. }
+            | PhoenixCLICommand::WatchBalance { .. }
+            | PhoenixCLICommand::RecordBookDepth { .. }
+            | PhoenixCLICommand::RecordFeeSnapshot { .. }
+            | PhoenixCLICommand::ServeBooks { .. }
+    )
+}
+
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() {
     let cli = Args::parse();
+    // Errors are reported as JSON whenever results are, so scripts only need one parser
+    let json_errors = cli.output == OutputFormat::Json;
+    let runs_until_interrupted = runs_until_interrupted(&cli.command);
+    let result = tokio::select! {
+        result = run(cli) => result,
+        _ = tokio::signal::ctrl_c() => {
+            if runs_until_interrupted {
+                Ok(())
+            } else {
+                Err(UserAbort.into())
+            }
+        }
+    };
+    if let Err(error) = result {
+        let code = classify_error(&error);
+        if json_errors {
+            print_error_json(&error, code);
+        } else {
+            eprintln!("Error: {:?}", error);
+        }
+        std::process::exit(code.exit_code());
+    }
+}
+
+async fn run(mut cli: Args) -> anyhow::Result<()> {
+    if let Some(template) = &cli.template {
+        load_report_template(template)?;
+        cli.output = OutputFormat::Template;
+    }
+    init_logging(cli.log_format, cli.verbose);
+    check_output_supported(&cli.command, cli.output)?;
+    if cli.ix_only && matches!(cli.command, PhoenixCLICommand::RotateKeypair { .. }) {
+        // Later steps depend on the balances left by earlier ones, so they can't be built up front
+        return Err(anyhow!(
+            "rotate-keypair is a multi-step flow and does not support --ix-only"
+        ));
+    }
+    if cli.raw_units && matches!(cli.command, PhoenixCLICommand::CompareBooks { .. }) {
+        // Tick and lot sizes differ between the markets, so raw values can't be set side by side
+        return Err(anyhow!(
+            "compare-books compares markets in token units and does not support --raw-units"
+        ));
+    }
+    set_quiet(cli.quiet);
+    set_raw_units(cli.raw_units);
+    set_csv_metadata(cli.csv_metadata);
+    set_time_display(cli.timezone, cli.time_format.clone())?;
+    configure_colors(cli.color)?;
     let config = match CONFIG_FILE.as_ref() {
         Some(config_file) => Config::load(config_file).unwrap_or_else(|_| {
-            println!("Failed to load config file: {}", config_file);
+            tracing::warn!(config_file = %config_file, "Failed to load config file");
             Config::default()
         }),
         None => Config::default(),
@@ -59,51 +310,248 @@ async fn main() -> anyhow::Result<()> {
     let payer = get_payer_keypair_from_path(&cli.keypair_path.unwrap_or(config.keypair_path))
         .expect("Keypair file does not exist. Please run `solana-keygen new`");
     let network_url = &get_network(&cli.url.unwrap_or(config.json_rpc_url)).to_string();
-    let client = EllipsisClient::from_rpc(
-        RpcClient::new_with_commitment(network_url.to_string(), commitment),
-        &payer,
-    )?;
+    tracing::info!(url = %network_url, commitment = ?commitment.commitment, "Connecting");
+    let client = EllipsisClient::from_rpc(new_timed_rpc_client(network_url, commitment), &payer)?;
 
-    let mut sdk = SDKClient::new(&payer, network_url).await?;
+    let mut sdk = new_timed_sdk_client(&payer, network_url, CommitmentConfig::confirmed()).await?;
 
     match cli.command {
-        PhoenixCLICommand::GetMarket { market_pubkey } => {
+        PhoenixCLICommand::GetMarket {
+            market_pubkey,
+            export_toml,
+        } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_market(&market_pubkey, &sdk).await?
+            process_get_market(&market_pubkey, &sdk, export_toml.as_deref(), cli.output).await?
         }
-        PhoenixCLICommand::GetAllMarkets { no_gpa } => {
+        PhoenixCLICommand::GetAllMarkets {
+            no_gpa,
+            base,
+            quote,
+            stats,
+            sort_by,
+        } => {
+            let filter = MarketFilter { base, quote };
+            let stats = (stats || sort_by.is_some()).then_some(&mut sdk);
             if no_gpa {
-                process_get_all_markets_no_gpa(&client, network_url).await?
+                process_get_all_markets_no_gpa(
+                    &client,
+                    network_url,
+                    &filter,
+                    stats,
+                    sort_by,
+                    cli.output,
+                    cli.wide,
+                )
+                .await?
             } else {
-                process_get_all_markets(&client).await?
+                process_get_all_markets(&client, &filter, stats, sort_by, cli.output, cli.wide)
+                    .await?
             }
         }
-        PhoenixCLICommand::GetTradersForMarket { market_pubkey } => {
+        PhoenixCLICommand::GetTradersForMarket {
+            market_pubkey,
+            sort_by,
+            offset,
+            limit,
+        } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_traders_for_market(&market_pubkey, &sdk).await?
+            process_get_traders_for_market(
+                &market_pubkey,
+                &sdk,
+                sort_by,
+                offset,
+                limit,
+                cli.output,
+                cli.wide,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetBbo { market_pubkey } => {
+            process_get_bbo(&market_pubkey, &sdk, cli.output).await?
+        }
+        PhoenixCLICommand::GetSpread {
+            market_pubkey,
+            samples,
+            interval,
+        } => process_get_spread(&market_pubkey, &sdk, samples, interval, cli.output).await?,
+        PhoenixCLICommand::GetLiquidity { market_pubkey, bps } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_liquidity(&market_pubkey, &sdk, &bps, cli.output).await?
+        }
+        PhoenixCLICommand::GetBookAtSlot {
+            market_pubkey,
+            slot,
+            precision,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_book_at_slot(&market_pubkey, &sdk, slot, precision, cli.output).await?
+        }
+        PhoenixCLICommand::GetOrder {
+            market_pubkey,
+            order_sequence_number,
+            window,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_order(
+                &market_pubkey,
+                &sdk,
+                order_sequence_number,
+                window,
+                cli.output,
+            )
+            .await?
+        }
+        PhoenixCLICommand::CompareBooks { market_a, market_b } => {
+            sdk.add_market(&market_a).await?;
+            sdk.add_market(&market_b).await?;
+            process_compare_books(&market_a, &market_b, &sdk, cli.output).await?
         }
-        PhoenixCLICommand::GetTopOfBook { market_pubkey } => {
+        PhoenixCLICommand::GetTopOfBook {
+            market_pubkey,
+            format,
+            precision,
+        } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_top_of_book(&market_pubkey, &sdk).await?
+            process_get_top_of_book(
+                &market_pubkey,
+                &sdk,
+                format.as_deref(),
+                precision,
+                cli.output,
+            )
+            .await?
         }
         PhoenixCLICommand::GetBookLevels {
             market_pubkey,
             levels,
+            format,
+            precision,
         } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_book_levels(&market_pubkey, &sdk, levels).await?
+            process_get_book_levels(
+                &market_pubkey,
+                &sdk,
+                levels,
+                format.as_deref(),
+                precision,
+                cli.output,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetFullBook {
+            market_pubkey,
+            depth,
+            group_ticks,
+            group_bps,
+            chart,
+            cumulative,
+            orders,
+            trader,
+            min_price,
+            max_price,
+            format,
+            precision,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            if orders {
+                process_get_book_orders(
+                    &market_pubkey,
+                    &sdk,
+                    trader.as_ref(),
+                    min_price,
+                    max_price,
+                    cli.output,
+                )
+                .await?
+            } else {
+                let grouping = group_ticks
+                    .map(BookGrouping::Ticks)
+                    .or(group_bps.map(BookGrouping::Bps));
+                process_get_full_book(
+                    &market_pubkey,
+                    &sdk,
+                    depth,
+                    grouping,
+                    chart,
+                    cumulative,
+                    format.as_deref(),
+                    precision,
+                    cli.output,
+                )
+                .await?
+            }
+        }
+        PhoenixCLICommand::InspectTx { signature } => {
+            process_inspect_tx(&signature, &mut sdk, cli.output).await?
+        }
+        PhoenixCLICommand::GetTransaction {
+            signature,
+            client_order_id,
+            format,
+            pretty,
+            compact,
+        } => {
+            let format = match (format.as_deref(), pretty, compact) {
+                _ if matches!(cli.output, OutputFormat::Json | OutputFormat::Jsonl) => {
+                    EventLogFormat::JsonLines
+                }
+                (Some(template), _, _) => EventLogFormat::Template(template),
+                (None, true, _) => EventLogFormat::Pretty,
+                (None, _, true) => EventLogFormat::Compact,
+                _ => EventLogFormat::Default,
+            };
+            process_get_transaction(&signature, &mut sdk, client_order_id, &format).await?
+        }
+        PhoenixCLICommand::GetEvents { signature, market } => {
+            let format = if matches!(cli.output, OutputFormat::Json | OutputFormat::Jsonl) {
+                EventLogFormat::JsonLines
+            } else {
+                EventLogFormat::Default
+            };
+            process_get_events(&signature, &mut sdk, market.as_ref(), &format).await?
         }
-        PhoenixCLICommand::GetFullBook { market_pubkey } => {
+        PhoenixCLICommand::GetEventsRange {
+            market_pubkey,
+            from_slot,
+            to_slot,
+            before,
+            until,
+            checkpoint_file,
+        } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_full_book(&market_pubkey, &sdk).await?
+            let range = EventRange {
+                from_slot,
+                to_slot,
+                before,
+                until,
+                checkpoint_file,
+            };
+            let format = if matches!(cli.output, OutputFormat::Json | OutputFormat::Jsonl) {
+                EventLogFormat::JsonLines
+            } else {
+                EventLogFormat::Default
+            };
+            process_get_events_range(
+                &market_pubkey,
+                &mut sdk,
+                &range,
+                &format,
+                cli.progress_file.as_deref(),
+            )
+            .await?
         }
-        PhoenixCLICommand::GetTransaction { signature } => {
-            process_get_transaction(&signature, &mut sdk).await?
+        PhoenixCLICommand::GetFees { market_pubkey } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_fees(&market_pubkey, &sdk, cli.output).await?
         }
         PhoenixCLICommand::GetMarketStatus { market_pubkey } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_market_status(&market_pubkey, &sdk).await?
+            process_get_market_status(&market_pubkey, &sdk, cli.output).await?
         }
+        PhoenixCLICommand::GetAllSeats {
+            market_pubkey,
+            status,
+        } => process_get_all_seats(&market_pubkey, &sdk, status, cli.output).await?,
         PhoenixCLICommand::GetSeatInfo {
             market_pubkey,
             trader_pubkey,
@@ -113,30 +561,47 @@ async fn main() -> anyhow::Result<()> {
                 &market_pubkey,
                 &trader_pubkey.unwrap_or_else(|| payer.pubkey()),
                 &sdk,
+                cli.output,
             )
             .await?
         }
         PhoenixCLICommand::GetOpenOrders {
             market_pubkey,
             trader_pubkey,
+            all_markets,
         } => {
-            sdk.add_market(&market_pubkey).await?;
-            process_get_open_orders(
-                &market_pubkey,
-                &trader_pubkey.unwrap_or_else(|| payer.pubkey()),
-                &sdk,
-            )
-            .await?
+            let trader_pubkey = trader_pubkey.unwrap_or_else(|| payer.pubkey());
+            match market_pubkey {
+                Some(market_pubkey) if !all_markets => {
+                    sdk.add_market(&market_pubkey).await?;
+                    process_get_open_orders(&market_pubkey, &trader_pubkey, &sdk, cli.output)
+                        .await?
+                }
+                _ => {
+                    process_get_open_orders_all_markets(&trader_pubkey, &mut sdk, cli.output)
+                        .await?
+                }
+            }
         }
         PhoenixCLICommand::RequestSeat { market_pubkey } => {
             sdk.add_market(&market_pubkey).await?;
-            process_request_seat(&market_pubkey, &sdk).await?
+            process_request_seat(&market_pubkey, &sdk, cli.ix_only).await?
         }
         PhoenixCLICommand::MintTokens {
             mint_ticker,
             recipient_pubkey,
             amount,
-        } => process_mint_tokens(&client, &payer, &recipient_pubkey, mint_ticker, amount).await?,
+        } => {
+            process_mint_tokens(
+                &client,
+                &payer,
+                &recipient_pubkey,
+                mint_ticker,
+                amount,
+                cli.ix_only,
+            )
+            .await?
+        }
         PhoenixCLICommand::MintTokensForMarket {
             market_pubkey,
             recipient_pubkey,
@@ -150,26 +615,364 @@ async fn main() -> anyhow::Result<()> {
                 &recipient_pubkey,
                 base_amount,
                 quote_amount,
+                cli.ix_only,
             )
             .await?
         }
         PhoenixCLICommand::GetSeatManagerInfo { market_pubkey } => {
             sdk.add_market(&market_pubkey).await?;
-            process_get_seat_manager_info(&sdk.client, &market_pubkey).await?;
+            process_get_seat_manager_info(&sdk.client, &market_pubkey, cli.output, cli.wide)
+                .await?;
         }
         PhoenixCLICommand::ClaimSeat { market_pubkey } => {
             sdk.add_market(&market_pubkey).await?;
-            process_claim_seat(&sdk.client, &market_pubkey).await?
+            process_claim_seat(&sdk.client, &market_pubkey, cli.ix_only).await?
         }
         PhoenixCLICommand::EvictSeat {
             market_pubkey,
             trader_to_evict,
         } => {
             sdk.add_market(&market_pubkey).await?;
-            process_evict_seat(&sdk.client, &market_pubkey, &trader_to_evict).await?
+            process_evict_seat(&sdk.client, &market_pubkey, &trader_to_evict, cli.ix_only).await?
+        }
+        PhoenixCLICommand::GetUncollectedRevenue {
+            stablecoins,
+            live_stablecoin_prices,
+        } => {
+            process_get_uncollected_revenue(
+                &client,
+                network_url,
+                &stablecoins,
+                live_stablecoin_prices,
+                cli.output,
+            )
+            .await?;
+        }
+        PhoenixCLICommand::FundMakers {
+            market_pubkey,
+            makers_file,
+            treasury_keypair_path,
+            execute,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            let treasury = match treasury_keypair_path {
+                Some(path) => get_payer_keypair_from_path(&path)?,
+                None => payer.insecure_clone(),
+            };
+            process_fund_makers(
+                &sdk,
+                &market_pubkey,
+                &treasury,
+                &makers_file,
+                execute,
+                cli.ix_only,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetStaleOrders {
+            market_pubkey,
+            max_distance_bps,
+            max_age_orders,
+            max_order_age,
+            cancel,
+        } => {
+            process_get_stale_orders(
+                &mut sdk,
+                market_pubkey,
+                max_distance_bps,
+                max_age_orders,
+                max_order_age,
+                cancel,
+                cli.ix_only,
+            )
+            .await?
         }
-        PhoenixCLICommand::GetUncollectedRevenue => {
-            process_get_uncollected_revenue(&client, network_url).await?;
+        PhoenixCLICommand::GetMakerActivity {
+            market_pubkey,
+            window,
+        } => process_get_maker_activity(&market_pubkey, &sdk, window).await?,
+        PhoenixCLICommand::GetRecentTrades {
+            market_pubkey,
+            count,
+            lookback,
+            max_transactions,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_recent_trades(
+                &market_pubkey,
+                &sdk,
+                count,
+                lookback,
+                max_transactions,
+                cli.output,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetCandles {
+            market_pubkey,
+            interval,
+            from,
+            to,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_candles(&market_pubkey, &sdk, interval, from, to, cli.output).await?
+        }
+        PhoenixCLICommand::GetVolume {
+            market_pubkey,
+            window,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_volume(&market_pubkey, &sdk, window, cli.output).await?
+        }
+        PhoenixCLICommand::GetMakerLeaderboard {
+            market_pubkey,
+            window,
+            limit,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_maker_leaderboard(&market_pubkey, &sdk, window, limit, cli.output).await?
+        }
+        PhoenixCLICommand::GetTakerLeaderboard {
+            market_pubkey,
+            window,
+            sort_by,
+            limit,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_taker_leaderboard(&market_pubkey, &sdk, window, sort_by, limit, cli.output)
+                .await?
+        }
+        PhoenixCLICommand::GetVwap {
+            market_pubkey,
+            window,
+            for_size,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_vwap(&market_pubkey, &sdk, window, for_size, cli.output).await?
+        }
+        PhoenixCLICommand::WatchBook {
+            market_pubkey,
+            depth,
+            interval,
+            precision,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_watch_book(&market_pubkey, &sdk, depth, interval, precision).await?
+        }
+        PhoenixCLICommand::WatchTrades {
+            market_pubkey,
+            ws_url,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_watch_trades(&market_pubkey, &sdk, ws_url.as_deref(), cli.output).await?
+        }
+        PhoenixCLICommand::GetVaults { market_pubkey } => {
+            process_get_vaults(&market_pubkey, &sdk, cli.output).await?
+        }
+        PhoenixCLICommand::WatchVaults {
+            market_pubkey,
+            interval,
+        } => {
+            process_watch_vaults(&market_pubkey, &sdk, interval, cli.progress_file.as_deref())
+                .await?
+        }
+        PhoenixCLICommand::RecordBookDepth {
+            market_pubkey,
+            out,
+            bands,
+            interval,
+            samples,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_record_book_depth(
+                &market_pubkey,
+                &sdk,
+                &bands,
+                interval,
+                samples,
+                &out,
+                cli.progress_file.as_deref(),
+            )
+            .await?
+        }
+        PhoenixCLICommand::RecordFeeSnapshot { out, compress } => {
+            process_record_fee_snapshot(&mut sdk, &out, compress).await?
+        }
+        PhoenixCLICommand::GetFeeAccrual { snapshot_file } => {
+            process_get_fee_accrual(&snapshot_file)?
+        }
+        PhoenixCLICommand::SimulateMakerRemoval {
+            market_pubkey,
+            maker_pubkey,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_simulate_maker_removal(&market_pubkey, &maker_pubkey, &sdk).await?
+        }
+        PhoenixCLICommand::GetQueuePosition {
+            market_pubkey,
+            trader_pubkey,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_queue_position(
+                &market_pubkey,
+                &trader_pubkey.unwrap_or_else(|| payer.pubkey()),
+                &sdk,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetLevel {
+            market_pubkey,
+            price,
+            side,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_level(&market_pubkey, &sdk, price, side).await?
+        }
+        PhoenixCLICommand::GetPriorityFees {
+            market_pubkey,
+            samples,
+            interval,
+        } => process_get_priority_fees(&market_pubkey, &sdk, samples, interval).await?,
+        PhoenixCLICommand::RotateKeypair {
+            market_pubkey,
+            new_keypair_path,
+            execute,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            let new_keypair = get_payer_keypair_from_path(&new_keypair_path)?;
+            process_rotate_keypair(&market_pubkey, &sdk, &new_keypair, execute).await?
+        }
+        PhoenixCLICommand::ServeBooks {
+            markets,
+            port,
+            interval,
+        } => process_serve_books(&mut sdk, markets, port, interval).await?,
+        PhoenixCLICommand::WarmCache => process_warm_cache(&mut sdk).await?,
+        PhoenixCLICommand::GetRevenueStatement {
+            snapshot_file,
+            start,
+            end,
+            out,
+        } => process_get_revenue_statement(&snapshot_file, start, end, out.as_deref())?,
+        PhoenixCLICommand::GetBalanceLedger {
+            wallet,
+            start,
+            end,
+            out,
+        } => {
+            process_get_balance_ledger(
+                &mut sdk,
+                &wallet.unwrap_or_else(|| payer.pubkey()),
+                start,
+                end,
+                out.as_deref(),
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetBalances { wallet } => {
+            process_get_balances(&sdk, &wallet.unwrap_or_else(|| payer.pubkey()), cli.output)
+                .await?
+        }
+        PhoenixCLICommand::GetTraderMarkets { trader } => {
+            process_get_trader_markets(&sdk, &trader, cli.output).await?
+        }
+        PhoenixCLICommand::GetTraderFills {
+            trader,
+            market_pubkey,
+            start,
+            end,
+            out,
+        } => {
+            let trader = trader.unwrap_or_else(|| payer.pubkey());
+            process_get_trader_fills(
+                &mut sdk,
+                &trader,
+                &market_pubkey.unwrap_or(trader),
+                start,
+                end,
+                out.as_deref(),
+                cli.output,
+            )
+            .await?
+        }
+        PhoenixCLICommand::WatchBalance {
+            trader_pubkey,
+            market_pubkey,
+            interval,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_watch_balance(
+                &market_pubkey,
+                &trader_pubkey,
+                &sdk,
+                interval,
+                cli.progress_file.as_deref(),
+            )
+            .await?
+        }
+        PhoenixCLICommand::Snipe {
+            market_pubkey,
+            side,
+            size,
+            size_in_lots,
+            limit_price,
+            price_in_ticks,
+            interval,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_snipe(
+                &market_pubkey,
+                &sdk,
+                side,
+                size,
+                size_in_lots,
+                limit_price,
+                price_in_ticks,
+                interval,
+                cli.ix_only,
+            )
+            .await?
+        }
+        PhoenixCLICommand::EstimateIncentives {
+            market_pubkey,
+            rules_file,
+            maker,
+            window,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_estimate_incentives(
+                &market_pubkey,
+                &sdk,
+                &rules_file,
+                maker.as_ref(),
+                window,
+                cli.wide,
+            )
+            .await?
+        }
+        PhoenixCLICommand::MigrateQuotes {
+            old_market_pubkey,
+            new_market_pubkey,
+            execute,
+        } => {
+            sdk.add_market(&old_market_pubkey).await?;
+            sdk.add_market(&new_market_pubkey).await?;
+            process_migrate_quotes(
+                &old_market_pubkey,
+                &new_market_pubkey,
+                &sdk,
+                execute,
+                cli.ix_only,
+            )
+            .await?
+        }
+        PhoenixCLICommand::GetSettlementReport {
+            market_pubkey,
+            out,
+            watch,
+        } => {
+            sdk.add_market(&market_pubkey).await?;
+            process_get_settlement_report(&market_pubkey, &sdk, out.as_deref(), watch).await?
         }
     }
 
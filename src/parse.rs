@@ -0,0 +1,218 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use phoenix::state::Side;
+
+// Shared value parsers for command arguments, so units and error messages are consistent across
+// commands. Numbers may use _ or , as digit separators.
+
+pub fn parse_side(side: &str) -> Result<Side, String> {
+    match side.to_lowercase().as_str() {
+        "bid" | "buy" | "b" => Ok(Side::Bid),
+        "ask" | "sell" | "a" | "s" => Ok(Side::Ask),
+        _ => Err(format!("Invalid side: {}. Expected bid or ask", side)),
+    }
+}
+
+// Parse a duration such as 500ms, 30s, 5m, 2h, or 1d into milliseconds. A bare number is taken as
+// seconds.
+pub fn parse_duration_ms(duration: &str) -> Result<u64, String> {
+    let cleaned = strip_separators(duration);
+    let (value, unit) = match cleaned.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => cleaned.split_at(i),
+        None => (cleaned.as_str(), "s"),
+    };
+    let value = value.parse::<u64>().map_err(|_| {
+        format!(
+            "Invalid duration: {}. Expected e.g. 90, 500ms, 30s, 5m, 2h, or 1d",
+            duration
+        )
+    })?;
+    let multiplier = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => {
+            return Err(format!(
+                "Invalid duration unit in {}. Expected ms, s, m, h, or d",
+                duration
+            ))
+        }
+    };
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Invalid duration: {} is too large", duration))
+}
+
+// Same as parse_duration_ms, but in seconds, for durations that don't need finer resolution
+pub fn parse_duration(duration: &str) -> Result<u64, String> {
+    match parse_duration_ms(duration)? {
+        ms if ms % 1_000 != 0 => Err(format!(
+            "Invalid duration: {}. Expected a whole number of seconds",
+            duration
+        )),
+        ms => Ok(ms / 1_000),
+    }
+}
+
+// Same as parse_duration, but rejects an empty window, which results are averaged over
+pub fn parse_window(duration: &str) -> Result<u64, String> {
+    match parse_duration(duration)? {
+        0 => Err(format!(
+            "Invalid window: {}. Expected a non-zero duration",
+            duration
+        )),
+        seconds => Ok(seconds),
+    }
+}
+
+// Parse a point in time as a unix timestamp, an RFC 3339 time such as 2024-01-31T00:00:00Z, or a
+// duration such as 2h or 7d, taken as that long before now
+pub fn parse_timestamp(timestamp: &str) -> Result<i64, String> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(timestamp.trim()) {
+        return Ok(time.timestamp());
+    }
+    if let Ok(unix_timestamp) = strip_separators(timestamp).parse::<i64>() {
+        return Ok(unix_timestamp);
+    }
+    let ago = parse_duration(timestamp).map_err(|_| {
+        format!(
+            "Invalid time: {}. Expected a unix timestamp, an RFC 3339 time, or a duration ago such as 2h",
+            timestamp
+        )
+    })?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    Ok(now.saturating_sub(ago) as i64)
+}
+
+// Parse a size, price, or notional amount such as 1.5k, 23_450.5, or $10k. The optional suffix
+// k, m, or b multiplies by a thousand, million, or billion, and a leading $ is ignored.
+pub fn parse_amount(amount: &str) -> Result<f64, String> {
+    let cleaned = strip_separators(amount);
+    let cleaned = cleaned.strip_prefix('$').unwrap_or(&cleaned);
+    let (value, multiplier) = match cleaned.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&cleaned[..cleaned.len() - 1], 1e3),
+        Some('m') => (&cleaned[..cleaned.len() - 1], 1e6),
+        Some('b') => (&cleaned[..cleaned.len() - 1], 1e9),
+        _ => (cleaned, 1.0),
+    };
+    match value.parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok(value * multiplier),
+        _ => Err(format!(
+            "Invalid amount: {}. Expected a non-negative number, e.g. 1.5k, 23_450.5, or $10k",
+            amount
+        )),
+    }
+}
+
+// Parse a whole number of atoms, such as 100_000_000 or 100m. Suffixes are as for parse_amount,
+// and the result must be a whole number. Whole numbers are parsed exactly, only values with a
+// decimal point go through f64.
+pub fn parse_atoms(atoms: &str) -> Result<u64, String> {
+    let invalid = || {
+        format!(
+            "Invalid amount: {}. Expected a whole number of atoms",
+            atoms
+        )
+    };
+    let cleaned = strip_separators(atoms);
+    let cleaned = cleaned.strip_prefix('$').unwrap_or(&cleaned);
+    let (value, multiplier) = match cleaned.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&cleaned[..cleaned.len() - 1], 1_000),
+        Some('m') => (&cleaned[..cleaned.len() - 1], 1_000_000),
+        Some('b') => (&cleaned[..cleaned.len() - 1], 1_000_000_000),
+        _ => (cleaned, 1),
+    };
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        return value
+            .parse::<u64>()
+            .ok()
+            .and_then(|value| value.checked_mul(multiplier))
+            .ok_or_else(invalid);
+    }
+    let value = parse_amount(atoms)?;
+    // u64::MAX rounds up to 2^64 as an f64, so anything at or above it is out of range
+    if value.fract() != 0.0 || value >= u64::MAX as f64 {
+        return Err(invalid());
+    }
+    Ok(value as u64)
+}
+
+fn strip_separators(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .filter(|c| *c != '_' && *c != ',')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1.5k"), Ok(1_500.0));
+        assert_eq!(parse_amount("$10k"), Ok(10_000.0));
+        assert_eq!(parse_amount("23_450.5"), Ok(23_450.5));
+        assert_eq!(parse_amount("1,000"), Ok(1_000.0));
+        assert!(parse_amount("abc").is_err());
+        assert!(parse_amount("-5").is_err());
+        assert!(parse_amount("").is_err());
+    }
+
+    #[test]
+    fn test_parse_atoms() {
+        assert_eq!(parse_atoms("100_000_000"), Ok(100_000_000));
+        assert_eq!(parse_atoms("1.5k"), Ok(1_500));
+        assert_eq!(parse_atoms("18446744073709551615"), Ok(u64::MAX));
+        assert!(parse_atoms("1.5").is_err());
+        assert!(parse_atoms("18446744073709551616").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(90));
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("5m"), Ok(300));
+        assert_eq!(parse_duration("2h"), Ok(7_200));
+        assert_eq!(parse_duration("1d"), Ok(86_400));
+        assert_eq!(parse_duration("2000ms"), Ok(2));
+        assert!(parse_duration("500ms").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("1.5h").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("500ms"), Ok(500));
+        assert_eq!(parse_duration_ms("90s"), Ok(90_000));
+        assert_eq!(parse_duration_ms("2"), Ok(2_000));
+        assert!(parse_duration_ms("ms").is_err());
+        assert!(parse_duration_ms("18446744073709551615s").is_err());
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("1h"), Ok(3_600));
+        assert!(parse_window("0s").is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1700000000"), Ok(1_700_000_000));
+        assert_eq!(parse_timestamp("2024-01-01T00:00:00Z"), Ok(1_704_067_200));
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let hour_ago = parse_timestamp("1h").unwrap();
+        assert!((now - 3_600 - hour_ago).abs() <= 1);
+        assert!(parse_timestamp("yesterday").is_err());
+    }
+}